@@ -0,0 +1,332 @@
+//! Response cache, opt-in with `ai.cache: true`. Completions are stored
+//! under `.process/cache/ai/<key>.json`, where the key hashes the provider,
+//! the model, the rendered prompt (system, messages, schema), max_tokens
+//! and temperature — so re-running a pass on unchanged inputs, or after a
+//! crash, doesn't pay for the same call twice. Answers from a fallback
+//! provider are not stored: they would be served under the primary's key
+//! long after the primary is back.
+
+use crate::notice::{self, Notice};
+use crate::provider::{AiProvider, ChunkSink, CompletionRequest, CompletionResponse};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime};
+
+/// Cache directory, relative to the project root
+pub const CACHE_DIR: &str = ".process/cache/ai";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Serve stored responses, store new ones
+    Use,
+    /// Always call the provider, replacing what is stored (`--refresh`)
+    Refresh,
+    /// Neither read nor write (`--no-cache`, or `ai.cache` unset)
+    Off,
+}
+
+/// Set once by the CLI from `--no-cache` / `--refresh`; takes precedence
+/// over `ai.cache` for the whole run
+static MODE_OVERRIDE: OnceLock<CacheMode> = OnceLock::new();
+
+pub fn override_mode(mode: CacheMode) {
+    let _ = MODE_OVERRIDE.set(mode);
+}
+
+/// What is stored per key
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    provider: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    response: CompletionResponse,
+}
+
+/// A project's response cache
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    mode: CacheMode,
+}
+
+impl ResponseCache {
+    /// The cache under `project_root`, on when `enabled` (`ai.cache`)
+    /// unless the run overrides it
+    pub fn for_project(project_root: &Path, enabled: bool) -> Self {
+        let configured = if enabled { CacheMode::Use } else { CacheMode::Off };
+        Self::new(project_root.join(CACHE_DIR), MODE_OVERRIDE.get().copied().unwrap_or(configured))
+    }
+
+    pub fn new(dir: PathBuf, mode: CacheMode) -> Self {
+        Self { dir, mode }
+    }
+
+    pub fn mode(&self) -> CacheMode {
+        self.mode
+    }
+
+    /// Route `provider`'s completions through the cache; unchanged when off
+    pub fn wrap(&self, provider: Arc<dyn AiProvider>) -> Arc<dyn AiProvider> {
+        if self.mode == CacheMode::Off {
+            return provider;
+        }
+        Arc::new(CachedProvider { inner: provider, cache: self.clone() })
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    fn get(&self, key: &str) -> Option<CompletionResponse> {
+        if self.mode != CacheMode::Use {
+            return None;
+        }
+        let content = std::fs::read_to_string(self.entry_path(key)).ok()?;
+        // An unreadable entry is a miss; the call replaces it
+        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+        Some(CompletionResponse { cached: true, ..entry.response })
+    }
+
+    /// Written aside and renamed into place, so a reader in another run
+    /// never sees half an entry
+    fn put(&self, key: &str, entry: &CacheEntry) -> Result<()> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let path = self.entry_path(key);
+        let tmp = self.dir.join(format!(".{}.{}-{}.tmp", key, std::process::id(), COUNTER.fetch_add(1, Ordering::Relaxed)));
+        let result = std::fs::write(&tmp, serde_json::to_string_pretty(entry)?).and_then(|()| std::fs::rename(&tmp, &path));
+        if result.is_err() {
+            std::fs::remove_file(&tmp).ok();
+        }
+        result.with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Entries removed by `prune`
+#[derive(Debug, Default, PartialEq)]
+pub struct PruneStats {
+    pub removed: usize,
+    pub bytes: u64,
+}
+
+/// Delete cached responses under `project_root`: those stored longer ago
+/// than `older_than`, or all of them
+pub fn prune(project_root: &Path, older_than: Option<Duration>) -> Result<PruneStats> {
+    let dir = project_root.join(CACHE_DIR);
+    let mut stats = PruneStats::default();
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Ok(stats);
+    };
+    let now = SystemTime::now();
+    for entry in entries {
+        let entry = entry?;
+        let meta = entry.metadata()?;
+        if !meta.is_file() {
+            continue;
+        }
+        let age = meta.modified().ok().and_then(|m| now.duration_since(m).ok()).unwrap_or_default();
+        if older_than.is_some_and(|limit| age < limit) {
+            continue;
+        }
+        std::fs::remove_file(entry.path()).with_context(|| format!("Failed to remove {}", entry.path().display()))?;
+        stats.removed += 1;
+        stats.bytes += meta.len();
+    }
+    Ok(stats)
+}
+
+/// Serves repeated requests from a `ResponseCache`
+struct CachedProvider {
+    inner: Arc<dyn AiProvider>,
+    cache: ResponseCache,
+}
+
+impl CachedProvider {
+    fn key(&self, request: &CompletionRequest) -> String {
+        let material = json!({
+            "provider": self.inner.label(),
+            "model": request.model.clone().or_else(|| self.inner.model()),
+            "system": request.system,
+            "messages": request.messages,
+            "response_schema": request.response_schema,
+            "max_tokens": request.max_tokens,
+            "temperature": request.temperature,
+        });
+        format!("{:x}", Sha256::digest(material.to_string().as_bytes()))
+    }
+
+    fn store(&self, key: &str, response: &CompletionResponse) {
+        if response.provider.as_ref().is_some_and(|p| *p != self.inner.primary()) {
+            return;
+        }
+        let entry = CacheEntry {
+            provider: response.provider.clone().unwrap_or_else(|| self.inner.name().to_string()),
+            model: response.model.clone(),
+            response: response.clone(),
+        };
+        // A response we can't cache is still a good response
+        if let Err(err) = self.cache.put(key, &entry) {
+            notice::emit(Notice::NotCached { error: format!("{:#}", err) });
+        }
+    }
+}
+
+#[async_trait]
+impl AiProvider for CachedProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn label(&self) -> String {
+        self.inner.label()
+    }
+
+    fn primary(&self) -> String {
+        self.inner.primary()
+    }
+
+    fn model(&self) -> Option<String> {
+        self.inner.model()
+    }
+
+    async fn is_available(&self) -> bool {
+        self.inner.is_available().await
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+        let key = self.key(request);
+        if let Some(response) = self.cache.get(&key) {
+            return Ok(response);
+        }
+        let response = self.inner.complete(request).await?;
+        self.store(&key, &response);
+        Ok(response)
+    }
+
+    async fn complete_stream(&self, request: &CompletionRequest, on_chunk: ChunkSink<'_>) -> Result<CompletionResponse> {
+        let key = self.key(request);
+        if let Some(response) = self.cache.get(&key) {
+            on_chunk(&response.content);
+            return Ok(response);
+        }
+        let response = self.inner.complete_stream(request, on_chunk).await?;
+        self.store(&key, &response);
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fallback::FallbackChain;
+
+    /// Answers with a call counter and reports a configurable model
+    struct Counting {
+        calls: AtomicUsize,
+        model: &'static str,
+    }
+
+    #[async_trait]
+    impl AiProvider for Counting {
+        fn name(&self) -> &'static str { "counting" }
+        fn priority(&self) -> u8 { 0 }
+        fn model(&self) -> Option<String> { Some(self.model.to_string()) }
+        async fn is_available(&self) -> bool { true }
+        async fn complete(&self, _request: &CompletionRequest) -> Result<CompletionResponse> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(CompletionResponse { content: format!("answer {}", n), usage: None, provider: None, model: None, cached: false })
+        }
+    }
+
+    fn counting(model: &'static str) -> Arc<Counting> {
+        Arc::new(Counting { calls: AtomicUsize::new(0), model })
+    }
+
+    #[tokio::test]
+    async fn test_identical_requests_are_served_from_cache() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::new(tmp.path().join("ai"), CacheMode::Use);
+        let inner = counting("m1");
+        let provider = cache.wrap(inner.clone());
+        let request = CompletionRequest::new("hello");
+
+        let first = provider.complete(&request).await.unwrap();
+        let mut streamed = String::new();
+        let second = provider.complete_stream(&request, &mut |c: &str| streamed.push_str(c)).await.unwrap();
+        assert_eq!((first.cached, second.cached), (false, true));
+        assert_eq!(second.content, "answer 1");
+        assert_eq!(streamed, "answer 1");
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+
+        // max_tokens, the prompt and the provider's model are all part of the key
+        provider.complete(&CompletionRequest { max_tokens: Some(10), ..request.clone() }).await.unwrap();
+        provider.complete(&CompletionRequest::new("hello again")).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+        let other_model = cache.wrap(counting("m2"));
+        assert!(!other_model.complete(&request).await.unwrap().cached);
+    }
+
+    /// Always unavailable, so a chain moves past it
+    struct Down;
+
+    #[async_trait]
+    impl AiProvider for Down {
+        fn name(&self) -> &'static str { "down" }
+        fn priority(&self) -> u8 { 0 }
+        async fn is_available(&self) -> bool { false }
+        async fn complete(&self, _request: &CompletionRequest) -> Result<CompletionResponse> {
+            anyhow::bail!("down")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fallback_answers_are_not_cached() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::new(tmp.path().join("ai"), CacheMode::Use);
+        let inner = counting("m");
+        let request = CompletionRequest::new("hello");
+
+        let chain = cache.wrap(Arc::new(FallbackChain::new(vec![Arc::new(Down), inner.clone()])));
+        chain.complete(&request).await.unwrap();
+        assert!(!chain.complete(&request).await.unwrap().cached);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+        assert!(!tmp.path().join("ai").exists());
+
+        // The chain's primary answering is cached as usual
+        let chain = cache.wrap(Arc::new(FallbackChain::new(vec![inner.clone(), Arc::new(Down)])));
+        chain.complete(&request).await.unwrap();
+        assert!(chain.complete(&request).await.unwrap().cached);
+        let files: Vec<_> = std::fs::read_dir(tmp.path().join("ai")).unwrap().map(|e| e.unwrap().file_name()).collect();
+        assert_eq!(files.len(), 1, "temporary files left behind: {:?}", files);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_replaces_and_prune_removes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join(CACHE_DIR);
+        let inner = counting("m");
+        let request = CompletionRequest::new("hello");
+
+        ResponseCache::new(dir.clone(), CacheMode::Use).wrap(inner.clone()).complete(&request).await.unwrap();
+        let refreshed = ResponseCache::new(dir.clone(), CacheMode::Refresh).wrap(inner.clone()).complete(&request).await.unwrap();
+        assert_eq!((refreshed.content.as_str(), refreshed.cached), ("answer 2", false));
+        let hit = ResponseCache::new(dir.clone(), CacheMode::Use).wrap(inner.clone()).complete(&request).await.unwrap();
+        assert_eq!(hit.content, "answer 2");
+
+        assert_eq!(prune(tmp.path(), Some(Duration::from_secs(3600))).unwrap().removed, 0);
+        assert_eq!(prune(tmp.path(), None).unwrap().removed, 1);
+        assert!(std::fs::read_dir(&dir).unwrap().next().is_none());
+    }
+}
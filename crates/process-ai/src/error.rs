@@ -0,0 +1,112 @@
+//! Typed failures from HTTP providers. They travel inside `anyhow::Error`;
+//! callers that care downcast, e.g.
+//! `err.downcast_ref::<ProviderError>().is_some_and(ProviderError::is_retryable)`.
+
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ProviderError {
+    /// HTTP 429: too many requests or tokens for the account's limits
+    #[error("{provider} rate limited the request (HTTP 429){}: {message}", fmt_retry_after(.retry_after))]
+    RateLimited { provider: String, retry_after: Option<Duration>, message: String },
+
+    /// HTTP 401/403: missing, invalid or unauthorised API key
+    #[error("{provider} rejected the credentials (HTTP {status}): {message}")]
+    Auth { provider: String, status: u16, message: String },
+
+    /// HTTP 529/503: the service is temporarily over capacity
+    #[error("{provider} is overloaded (HTTP {status}){}: {message}", fmt_retry_after(.retry_after))]
+    Overloaded { provider: String, status: u16, retry_after: Option<Duration>, message: String },
+
+    /// Other 4xx: the request itself is wrong (model name, token limit, payload)
+    #[error("{provider} rejected the request (HTTP {status}): {message}")]
+    BadRequest { provider: String, status: u16, message: String },
+
+    /// Other 5xx
+    #[error("{provider} server error (HTTP {status}): {message}")]
+    Server { provider: String, status: u16, message: String },
+
+    /// No response within the configured timeout
+    #[error("{provider} did not respond within {}s", .timeout.as_secs())]
+    Timeout { provider: String, timeout: Duration },
+
+    /// Connection failed before any response
+    #[error("Failed to reach {provider}: {message}")]
+    Network { provider: String, message: String },
+}
+
+fn fmt_retry_after(retry_after: &Option<Duration>) -> String {
+    retry_after
+        .map(|d| format!(", retry after {}s", d.as_secs_f32()))
+        .unwrap_or_default()
+}
+
+impl ProviderError {
+    /// Classify an error status. `retry_after` comes from the response headers.
+    pub fn from_status(provider: &str, status: u16, retry_after: Option<Duration>, body: &str) -> Self {
+        let provider = provider.to_string();
+        let message = error_message(body);
+        match status {
+            429 => Self::RateLimited { provider, retry_after, message },
+            401 | 403 => Self::Auth { provider, status, message },
+            503 | 529 => Self::Overloaded { provider, status, retry_after, message },
+            500..=599 => Self::Server { provider, status, message },
+            _ => Self::BadRequest { provider, status, message },
+        }
+    }
+
+    /// Whether the same request may succeed if sent again later
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::RateLimited { .. } | Self::Overloaded { .. } | Self::Server { .. } | Self::Timeout { .. } | Self::Network { .. }
+        )
+    }
+
+    /// How long the server asked us to wait, if it said
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after, .. } | Self::Overloaded { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+/// The `error.message` of a JSON error body (Anthropic, OpenAI, Gemini) or the
+/// `error` string (Ollama), else the body itself
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| {
+            v["error"]["message"]
+                .as_str()
+                .or_else(|| v["error"].as_str())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| body.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_status_classifies_and_extracts_message() {
+        let body = r#"{"type":"error","error":{"type":"rate_limit_error","message":"Slow down"}}"#;
+        let err = ProviderError::from_status("claude", 429, Some(Duration::from_secs(2)), body);
+        assert!(matches!(err, ProviderError::RateLimited { .. }));
+        assert!(err.is_retryable());
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(2)));
+        assert_eq!(err.to_string(), "claude rate limited the request (HTTP 429), retry after 2s: Slow down");
+
+        assert!(matches!(ProviderError::from_status("openai", 401, None, "{}"), ProviderError::Auth { .. }));
+        assert!(matches!(ProviderError::from_status("claude", 529, None, ""), ProviderError::Overloaded { .. }));
+        assert!(matches!(ProviderError::from_status("openai", 502, None, ""), ProviderError::Server { .. }));
+
+        let bad = ProviderError::from_status("ollama", 404, None, r#"{"error":"model 'x' not found"}"#);
+        assert!(matches!(bad, ProviderError::BadRequest { .. }));
+        assert!(!bad.is_retryable());
+        assert!(bad.to_string().ends_with("model 'x' not found"));
+    }
+}
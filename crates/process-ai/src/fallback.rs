@@ -0,0 +1,112 @@
+//! Ordered fallback between providers (`ai.fallback`): when one fails —
+//! after its own retries — or is unavailable, the same request goes to the
+//! next. A model picked for the request (by a route or override) names a
+//! model of the first provider, so later ones get the request without it
+//! and use their own. The response records which provider actually
+//! answered.
+
+use crate::notice::{self, Notice};
+use crate::provider::{AiProvider, ChunkSink, CompletionRequest, CompletionResponse};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+pub struct FallbackChain {
+    providers: Vec<Arc<dyn AiProvider>>,
+}
+
+impl FallbackChain {
+    /// Providers in the order they are tried
+    pub fn new(providers: Vec<Arc<dyn AiProvider>>) -> Self {
+        Self { providers }
+    }
+
+    pub fn members(&self) -> &[Arc<dyn AiProvider>] {
+        &self.providers
+    }
+
+    async fn run(&self, request: &CompletionRequest, mut on_chunk: Option<ChunkSink<'_>>) -> Result<CompletionResponse> {
+        let mut failures: Vec<String> = Vec::new();
+        let mut last_error = None;
+        let fallback_request = CompletionRequest { model: None, ..request.clone() };
+
+        for (i, provider) in self.providers.iter().enumerate() {
+            if !provider.is_available().await {
+                failures.push(format!("{}: unavailable", provider.name()));
+                continue;
+            }
+            if last_error.is_some() {
+                notice::emit(Notice::FallingBack { provider: provider.name().to_string() });
+            }
+
+            let request = if i == 0 { request } else { &fallback_request };
+            let result = match on_chunk.as_mut() {
+                Some(sink) => provider.complete_stream(request, &mut **sink).await,
+                None => provider.complete(request).await,
+            };
+            match result {
+                Ok(mut response) => {
+                    response.provider.get_or_insert_with(|| provider.name().to_string());
+                    return Ok(response);
+                }
+                Err(err) => {
+                    notice::emit(Notice::ProviderFailed {
+                        provider: provider.name().to_string(),
+                        error: format!("{:#}", err),
+                    });
+                    failures.push(format!("{}: {:#}", provider.name(), err));
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        let summary = format!("Every provider in the fallback chain failed ({})", failures.join("; "));
+        Err(match last_error {
+            // Keep the last typed error reachable for callers that downcast
+            Some(err) => err.context(summary),
+            None => anyhow!(summary),
+        })
+    }
+}
+
+#[async_trait]
+impl AiProvider for FallbackChain {
+    fn name(&self) -> &'static str {
+        "fallback"
+    }
+
+    fn priority(&self) -> u8 {
+        self.providers.iter().map(|p| p.priority()).max().unwrap_or(0)
+    }
+
+    fn label(&self) -> String {
+        self.providers.iter().map(|p| p.name()).collect::<Vec<_>>().join(" → ")
+    }
+
+    fn primary(&self) -> String {
+        self.providers.first().map(|p| p.primary()).unwrap_or_else(|| self.name().to_string())
+    }
+
+    /// Every member's model, in order, since any of them may answer
+    fn model(&self) -> Option<String> {
+        let models: Vec<String> = self.providers.iter().map(|p| p.model().unwrap_or_default()).collect();
+        Some(models.join(" → "))
+    }
+
+    async fn is_available(&self) -> bool {
+        for provider in &self.providers {
+            if provider.is_available().await {
+                return true;
+            }
+        }
+        false
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+        self.run(request, None).await
+    }
+
+    async fn complete_stream(&self, request: &CompletionRequest, on_chunk: ChunkSink<'_>) -> Result<CompletionResponse> {
+        self.run(request, Some(on_chunk)).await
+    }
+}
@@ -1,3 +1,11 @@
+pub mod cache;
+pub mod error;
+pub mod fallback;
+pub mod notice;
+pub mod prompts;
 pub mod provider;
 pub mod providers;
 pub mod registry;
+pub mod retry;
+pub mod stream;
+pub mod structured;
@@ -0,0 +1,98 @@
+//! Things worth telling the user that aren't errors: retries, fallbacks,
+//! reprompts, and cache or fixture writes that failed. The library never
+//! prints them; the binary installs a handler with `set_handler` and
+//! decides how they are shown. Without one they are dropped.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Notice {
+    /// A retryable failure; the request is sent again after `delay`
+    Retrying { error: String, delay: Duration, attempt: u32, max_retries: u32 },
+    /// A fallback chain member failed
+    ProviderFailed { provider: String, error: String },
+    /// A fallback chain moved on to `provider`
+    FallingBack { provider: String },
+    /// A structured reply was rejected and the model is asked again
+    Reprompting { error: String, attempt: usize, max: usize },
+    /// A good response could not be written to the response cache
+    NotCached { error: String },
+    /// A completion was saved as a replay fixture
+    Recorded { path: PathBuf },
+    /// A completion could not be saved as a replay fixture
+    NotRecorded { error: String },
+    /// A completion could not be added to the usage ledger
+    UsageNotRecorded { error: String },
+    /// An `ai.profiles` entry named after a built-in provider
+    ReservedProfile { name: String },
+}
+
+impl Notice {
+    /// Whether something went wrong, as opposed to plain progress
+    pub fn is_warning(&self) -> bool {
+        !matches!(self, Notice::Recorded { .. })
+    }
+}
+
+impl fmt::Display for Notice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Notice::Retrying { error, delay, attempt, max_retries } => write!(
+                f,
+                "{} — retrying in {:.1}s (attempt {}/{})",
+                error,
+                delay.as_secs_f32(),
+                attempt,
+                max_retries
+            ),
+            Notice::ProviderFailed { provider, error } => write!(f, "{} failed: {}", provider, error),
+            Notice::FallingBack { provider } => write!(f, "Falling back to {}", provider),
+            Notice::Reprompting { error, attempt, max } => write!(f, "{} — asking again ({}/{})", error, attempt, max),
+            Notice::NotCached { error } => write!(f, "Response not cached: {}", error),
+            Notice::Recorded { path } => write!(f, "Recorded {}", path.display()),
+            Notice::NotRecorded { error } => write!(f, "Fixture not recorded: {}", error),
+            Notice::UsageNotRecorded { error } => write!(f, "Usage not recorded: {}", error),
+            Notice::ReservedProfile { name } => {
+                write!(f, "Ignoring ai.profiles.{}: the name is reserved for a built-in provider", name)
+            }
+        }
+    }
+}
+
+type Handler = Box<dyn Fn(&Notice) + Send + Sync>;
+
+static HANDLER: OnceLock<Handler> = OnceLock::new();
+
+/// Send notices to `handler` for the rest of the run; only the first call
+/// takes effect
+pub fn set_handler(handler: impl Fn(&Notice) + Send + Sync + 'static) {
+    let _ = HANDLER.set(Box::new(handler));
+}
+
+/// Pass `notice` to the installed handler, if any
+pub fn emit(notice: Notice) {
+    if let Some(handler) = HANDLER.get() {
+        handler(&notice);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notices_read_as_before() {
+        let retry = Notice::Retrying {
+            error: "rate limited".into(),
+            delay: Duration::from_millis(1500),
+            attempt: 1,
+            max_retries: 3,
+        };
+        assert_eq!(retry.to_string(), "rate limited — retrying in 1.5s (attempt 1/3)");
+        assert!(retry.is_warning());
+        assert!(!Notice::Recorded { path: "f.yaml".into() }.is_warning());
+    }
+}
@@ -0,0 +1,171 @@
+use anyhow::{Context, Result};
+use include_dir::{include_dir, Dir};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tera::Tera;
+
+static BUILT_IN_TEMPLATES: Dir = include_dir!("$CARGO_MANIFEST_DIR/../../templates/prompts");
+
+pub struct PromptEngine {
+    provider: String,
+    /// `<project>/.process/prompts`, checked before the built-in templates
+    project_prompts: Option<PathBuf>,
+}
+
+impl PromptEngine {
+    /// Engine using only the built-in templates
+    pub fn new(provider: &str) -> Self {
+        let provider = if provider == "auto" {
+            "_default".to_string()
+        } else {
+            provider.to_string()
+        };
+        Self { provider, project_prompts: None }
+    }
+
+    /// Engine that prefers the project's `.process/prompts/` overrides
+    pub fn for_project(project_root: &Path, provider: &str) -> Self {
+        Self {
+            project_prompts: Some(project_root.join(".process/prompts")),
+            ..Self::new(provider)
+        }
+    }
+
+    pub fn render(&self, template_name: &str, ctx: &tera::Context) -> Result<String> {
+        let filename = format!("{}.md.tera", template_name);
+        let template_content = self.find_template(&filename)?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "Template '{}' not found for provider '{}' in any lookup location",
+                filename,
+                self.provider
+            )
+        })?;
+        Self::render_content(&filename, &template_content, ctx)
+    }
+
+    /// Render the optional `<template>.system.md.tera` companion: stable
+    /// instructions sent as the system prompt, apart from the per-call task
+    pub fn render_system(&self, template_name: &str, ctx: &tera::Context) -> Result<Option<String>> {
+        let filename = format!("{}.system.md.tera", template_name);
+        self.find_template(&filename)?
+            .map(|content| Self::render_content(&filename, &content, ctx))
+            .transpose()
+    }
+
+    fn render_content(filename: &str, template_content: &str, ctx: &tera::Context) -> Result<String> {
+        let mut tera = Tera::default();
+        tera.add_raw_template(filename, template_content)
+            .with_context(|| format!("Failed to parse template '{}'", filename))?;
+
+        tera.render(filename, ctx)
+            .with_context(|| format!("Failed to render template '{}'", filename))
+    }
+
+    fn find_template(&self, filename: &str) -> Result<Option<String>> {
+        if let Some(prompts) = &self.project_prompts {
+            // 1. Project-local: .process/prompts/<provider>/
+            let local_provider = prompts.join(&self.provider).join(filename);
+            if local_provider.exists() {
+                return fs::read_to_string(&local_provider)
+                    .map(Some)
+                    .with_context(|| format!("Failed to read {}", local_provider.display()));
+            }
+
+            // 2. Project-local: .process/prompts/_default/
+            let local_default = prompts.join("_default").join(filename);
+            if local_default.exists() {
+                return fs::read_to_string(&local_default)
+                    .map(Some)
+                    .with_context(|| format!("Failed to read {}", local_default.display()));
+            }
+        }
+
+        // 3. Built-in: <provider>/
+        let builtin_provider = format!("{}/{}", self.provider, filename);
+        if let Some(file) = BUILT_IN_TEMPLATES.get_file(&builtin_provider) {
+            return Ok(Some(file
+                .contents_utf8()
+                .context("Built-in template is not valid UTF-8")?
+                .to_string()));
+        }
+
+        // 4. Built-in: _default/
+        let builtin_default = format!("_default/{}", filename);
+        if let Some(file) = BUILT_IN_TEMPLATES.get_file(&builtin_default) {
+            return Ok(Some(file
+                .contents_utf8()
+                .context("Built-in template is not valid UTF-8")?
+                .to_string()));
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_diverge_template() {
+        let engine = PromptEngine::new("auto");
+        let mut ctx = tera::Context::new();
+        ctx.insert("seed", "name: test-project\ngoal: build something");
+        let result = engine.render("diverge", &ctx).unwrap();
+        assert!(result.contains("software architect"));
+        assert!(result.contains("test-project"));
+        assert!(result.contains("--- SEED ---"));
+    }
+
+    #[test]
+    fn test_render_converge_template() {
+        let engine = PromptEngine::new("auto");
+        let mut ctx = tera::Context::new();
+        ctx.insert("seed", "name: test");
+        ctx.insert("diverge_summary", "proposals: []");
+        let result = engine.render("converge", &ctx).unwrap();
+        assert!(result.contains("--- SEED ---"));
+        assert!(result.contains("--- DIVERGE SUMMARY ---"));
+    }
+
+    #[test]
+    fn test_auto_provider_maps_to_default() {
+        let engine = PromptEngine::new("auto");
+        assert_eq!(engine.provider, "_default");
+    }
+
+    #[test]
+    fn test_project_override_wins() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join(".process/prompts/_default");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("diverge.md.tera"), "custom {{ seed }}").unwrap();
+
+        let mut ctx = tera::Context::new();
+        ctx.insert("seed", "s");
+        assert_eq!(PromptEngine::for_project(tmp.path(), "auto").render("diverge", &ctx).unwrap(), "custom s");
+        assert_ne!(PromptEngine::new("auto").render("diverge", &ctx).unwrap(), "custom s");
+    }
+
+    #[test]
+    fn test_system_companion_is_optional() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join(".process/prompts/_default");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("diverge.system.md.tera"), "Rules: {{ seed }}").unwrap();
+
+        let mut ctx = tera::Context::new();
+        ctx.insert("seed", "s");
+        let engine = PromptEngine::for_project(tmp.path(), "auto");
+        assert_eq!(engine.render_system("diverge", &ctx).unwrap().as_deref(), Some("Rules: s"));
+        assert_eq!(engine.render_system("converge", &ctx).unwrap(), None);
+    }
+
+    #[test]
+    fn test_missing_template_errors() {
+        let engine = PromptEngine::new("auto");
+        let ctx = tera::Context::new();
+        let result = engine.render("nonexistent", &ctx);
+        assert!(result.is_err());
+    }
+}
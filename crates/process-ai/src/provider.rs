@@ -1,18 +1,128 @@
 use async_trait::async_trait;
 use anyhow::Result;
+use process_config::config::Route;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompletionRequest {
-    pub prompt: String,
+    /// Stable instructions (rules, seed, output format), sent as the
+    /// provider's system prompt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    /// The conversation, oldest first; normally ends with a user turn
+    pub messages: Vec<Message>,
     pub max_tokens: Option<usize>,
     pub model: Option<String>,
+    /// Sampling temperature; the provider's default when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Ask for JSON matching this schema, using the provider's native
+    /// structured-output support where it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_schema: Option<ResponseSchema>,
+    /// Prompt template the request was rendered from, if any; names
+    /// replay fixtures
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+/// A named JSON schema for structured output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseSchema {
+    /// Identifier the provider sees (OpenAI schema name, Anthropic tool name)
+    pub name: String,
+    pub schema: serde_json::Value,
+}
+
+impl CompletionRequest {
+    /// A single user turn with no system prompt and provider defaults
+    pub fn new(prompt: impl Into<String>) -> Self {
+        Self {
+            system: None,
+            messages: vec![Message::user(prompt)],
+            max_tokens: None,
+            model: None,
+            temperature: None,
+            response_schema: None,
+            template: None,
+        }
+    }
+
+    /// Apply what `ai.routes` chose; the route's settings win over the
+    /// request's defaults
+    pub fn routed(mut self, route: &Route) -> Self {
+        self.model = route.model.clone().or(self.model);
+        self.max_tokens = route.max_tokens.or(self.max_tokens);
+        self.temperature = route.temperature.or(self.temperature);
+        self
+    }
+
+    /// The whole request as one text, for providers that take a single
+    /// prompt (CLI, manual). A lone user turn is passed through unchanged;
+    /// a response schema becomes a closing instruction.
+    pub fn transcript(&self) -> String {
+        let mut parts: Vec<String> = self.system.iter().cloned().collect();
+        match self.messages.as_slice() {
+            [only] if only.role == Role::User => parts.push(only.content.clone()),
+            messages => parts.extend(messages.iter().map(|m| format!("{}:\n{}", m.role.label(), m.content))),
+        }
+        if let Some(schema) = &self.response_schema {
+            parts.push(format!(
+                "Respond with only a JSON object (no prose, no code fence) matching this JSON schema:\n{}",
+                serde_json::to_string_pretty(&schema.schema).unwrap_or_default()
+            ));
+        }
+        parts.join("\n\n")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    User,
+    Assistant,
+}
+
+impl Role {
+    fn label(self) -> &'static str {
+        match self {
+            Role::User => "User",
+            Role::Assistant => "Assistant",
+        }
+    }
+}
+
+/// One turn of a conversation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Message {
+    pub role: Role,
+    pub content: String,
+}
+
+impl Message {
+    pub fn user(content: impl Into<String>) -> Self {
+        Self { role: Role::User, content: content.into() }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self { role: Role::Assistant, content: content.into() }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompletionResponse {
     pub content: String,
     pub usage: Option<TokenUsage>,
+    /// Provider that produced the response, when it came through a
+    /// fallback chain rather than straight from the provider asked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Model that answered, for providers that choose one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Served from the response cache rather than a new call
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cached: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,10 +132,62 @@ pub struct TokenUsage {
     pub total_tokens: usize,
 }
 
+/// Receives text from `complete_stream` as it arrives
+pub type ChunkSink<'a> = &'a mut (dyn FnMut(&str) + Send);
+
 #[async_trait]
 pub trait AiProvider: Send + Sync {
-    fn name(&self) -> &'static str;
+    fn name(&self) -> &str;
     fn priority(&self) -> u8;
+
+    /// How the provider is shown to the user; a chain lists its members
+    fn label(&self) -> String {
+        self.name().to_string()
+    }
+
+    /// The provider that answers when nothing fails; a chain's first member
+    fn primary(&self) -> String {
+        self.name().to_string()
+    }
+
+    /// Model used when a request names none, if the provider has one
+    fn model(&self) -> Option<String> {
+        None
+    }
+
     async fn is_available(&self) -> bool;
     async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse>;
+
+    /// Like `complete`, but hands text to `on_chunk` as it is generated.
+    /// The returned response still carries the full content and usage.
+    /// Providers that cannot stream deliver the whole response as one chunk.
+    async fn complete_stream(
+        &self,
+        request: &CompletionRequest,
+        on_chunk: ChunkSink<'_>,
+    ) -> Result<CompletionResponse> {
+        let response = self.complete(request).await?;
+        on_chunk(&response.content);
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcript_flattens_roles() {
+        assert_eq!(CompletionRequest::new("just this").transcript(), "just this");
+
+        let request = CompletionRequest {
+            system: Some("Be terse.".into()),
+            messages: vec![Message::user("Propose"), Message::assistant("A"), Message::user("Challenge A")],
+            ..CompletionRequest::new("")
+        };
+        assert_eq!(
+            request.transcript(),
+            "Be terse.\n\nUser:\nPropose\n\nAssistant:\nA\n\nUser:\nChallenge A"
+        );
+    }
 }
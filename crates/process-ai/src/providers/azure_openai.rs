@@ -0,0 +1,198 @@
+use crate::provider::{AiProvider, ChunkSink, CompletionRequest, CompletionResponse};
+use crate::providers::openai::{chat_payload, parse_chat_response, stream_chat};
+use crate::retry::RequestPolicy;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use process_config::config::ProviderConfig;
+use reqwest::{Client, Response};
+use std::env;
+
+/// GA version that supports `json_schema` output and streamed usage
+const DEFAULT_API_VERSION: &str = "2024-10-21";
+
+/// Azure OpenAI: Chat Completions against a deployment on the resource
+/// at `base_url`. The model is the deployment name.
+pub struct AzureOpenAiProvider {
+    client: Client,
+    config: ProviderConfig,
+    policy: RequestPolicy,
+}
+
+impl AzureOpenAiProvider {
+    pub fn new(config: Option<ProviderConfig>) -> Self {
+        Self {
+            client: Client::new(),
+            config: config.unwrap_or_default(),
+            policy: RequestPolicy::default(),
+        }
+    }
+
+    /// Timeout and retry behaviour for API calls
+    pub fn with_policy(mut self, policy: RequestPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    fn get_api_key(&self) -> Result<String> {
+        self.config.api_key.clone()
+            .or_else(|| env::var("AZURE_OPENAI_API_KEY").ok())
+            .ok_or_else(|| anyhow!("Missing AZURE_OPENAI_API_KEY"))
+    }
+
+    fn get_endpoint(&self) -> Result<String> {
+        self.config.base_url.clone()
+            .or_else(|| env::var("AZURE_OPENAI_ENDPOINT").ok())
+            .map(|url| url.trim_end_matches('/').to_string())
+            .ok_or_else(|| anyhow!("Missing AZURE_OPENAI_ENDPOINT"))
+    }
+
+    fn get_deployment(&self) -> Option<String> {
+        self.config.model.clone()
+            .or_else(|| env::var("AZURE_OPENAI_DEPLOYMENT").ok())
+    }
+
+    fn get_api_version(&self) -> String {
+        self.config.api_version.clone()
+            .or_else(|| env::var("OPENAI_API_VERSION").ok())
+            .unwrap_or_else(|| DEFAULT_API_VERSION.to_string())
+    }
+
+    /// The request's deployment, else the configured one
+    fn resolve_deployment(&self, request: &CompletionRequest) -> Result<String> {
+        request.model.clone()
+            .or_else(|| self.get_deployment())
+            .ok_or_else(|| anyhow!("Missing Azure OpenAI deployment (ai.azure_openai.model or AZURE_OPENAI_DEPLOYMENT)"))
+    }
+
+    /// Key, endpoint and a deployment are all needed before a call can be made
+    fn is_configured(&self) -> bool {
+        self.get_api_key().is_ok() && self.get_endpoint().is_ok() && self.get_deployment().is_some()
+    }
+
+    /// POST the request to the deployment's Chat Completions, retrying per
+    /// the policy
+    async fn send(&self, request: &CompletionRequest, stream: bool) -> Result<Response> {
+        let api_key = self.get_api_key()?;
+        let endpoint = self.get_endpoint()?;
+        let deployment = self.resolve_deployment(request)?;
+        let max_tokens = request.max_tokens.or(self.config.max_tokens).unwrap_or(4096);
+
+        let url = format!(
+            "{}/openai/deployments/{}/chat/completions?api-version={}",
+            endpoint, deployment, self.get_api_version()
+        );
+        let payload = chat_payload(request, max_tokens, stream);
+
+        self.policy.send(self.name(), || {
+            self.client.post(&url)
+                .header("api-key", &api_key)
+                .header("content-type", "application/json")
+                .json(&payload)
+        }).await
+    }
+}
+
+#[async_trait]
+impl AiProvider for AzureOpenAiProvider {
+    fn name(&self) -> &'static str {
+        "azure-openai"
+    }
+
+    fn priority(&self) -> u8 {
+        // Ahead of OpenAI: Azure credentials are only set deliberately
+        if self.is_configured() { 85 } else { 0 }
+    }
+
+    fn model(&self) -> Option<String> {
+        self.get_deployment()
+    }
+
+    async fn is_available(&self) -> bool {
+        self.is_configured()
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+        let deployment = self.resolve_deployment(request)?;
+        let response = self.send(request, false).await?;
+        let body = self.policy.read_json(self.name(), response).await?;
+        let (content, usage) = parse_chat_response(&body)?;
+
+        Ok(CompletionResponse { content, usage, provider: None, model: Some(deployment), cached: false })
+    }
+
+    async fn complete_stream(
+        &self,
+        request: &CompletionRequest,
+        on_chunk: ChunkSink<'_>,
+    ) -> Result<CompletionResponse> {
+        let deployment = self.resolve_deployment(request)?;
+        let response = self.send(request, true).await?;
+
+        let (content, usage) = stream_chat(self.name(), "Azure OpenAI", response, self.policy.timeout, on_chunk).await?;
+        Ok(CompletionResponse { content, usage, provider: None, model: Some(deployment), cached: false })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ProviderError;
+    use crate::providers::mock_server::{MockResponse, MockServer};
+
+    fn provider(url: &str) -> AzureOpenAiProvider {
+        AzureOpenAiProvider::new(Some(ProviderConfig {
+            api_key: Some("azure-key".into()),
+            model: Some("design-gpt4o".into()),
+            base_url: Some(format!("{}/", url)),
+            api_version: Some("2025-01-01-preview".into()),
+            ..Default::default()
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_stream_targets_deployment_with_api_key() {
+        // Azure sends content-filter results in a first chunk with no choices
+        let server = MockServer::start(vec![MockResponse::chunked("text/event-stream", &[
+            "data: {\"choices\":[],\"prompt_filter_results\":[{\"prompt_index\":0}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"Hi\"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\" there\"}}]}\n\n",
+            "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":5,\"completion_tokens\":2,\"total_tokens\":7}}\n\n",
+            "data: [DONE]\n\n",
+        ])]).await;
+
+        let mut chunks = Vec::new();
+        let response = provider(&server.url)
+            .complete_stream(&CompletionRequest::new("hello"), &mut |chunk: &str| chunks.push(chunk.to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(chunks, vec!["Hi", " there"]);
+        assert_eq!(response.usage.unwrap().total_tokens, 7);
+        assert_eq!(response.model.as_deref(), Some("design-gpt4o"));
+
+        let (path, body) = &server.requests()[0];
+        assert_eq!(path, "/openai/deployments/design-gpt4o/chat/completions?api-version=2025-01-01-preview");
+        assert_eq!(server.header(0, "api-key").as_deref(), Some("azure-key"));
+        assert!(server.header(0, "authorization").is_none());
+        // The deployment picks the model
+        assert!(body.get("model").is_none());
+        assert_eq!(body["messages"][0]["content"], "hello");
+    }
+
+    #[tokio::test]
+    async fn test_error_status_is_classified() {
+        let server = MockServer::start(vec![MockResponse::json(
+            401,
+            r#"{"error":{"code":"401","message":"Access denied due to invalid subscription key."}}"#,
+        )]).await;
+
+        let err = provider(&server.url).complete(&CompletionRequest::new("hi")).await.unwrap_err();
+        match err.downcast_ref::<ProviderError>() {
+            Some(ProviderError::Auth { provider, message, .. }) => {
+                assert_eq!(provider, "azure-openai");
+                assert!(message.contains("invalid subscription key"));
+            }
+            other => panic!("expected an auth error, got {:?}", other),
+        }
+    }
+}
@@ -1,29 +1,35 @@
-use crate::provider::{AiProvider, CompletionRequest, CompletionResponse, TokenUsage};
-use anyhow::{Result, Context, anyhow};
+use crate::provider::{AiProvider, ChunkSink, CompletionRequest, CompletionResponse, TokenUsage};
+use crate::error::ProviderError;
+use crate::retry::RequestPolicy;
+use crate::stream;
+use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use process_config::config::ProviderConfig;
-use reqwest::Client;
-use serde_json::{json, Value};
+use reqwest::{Client, Response};
+use serde_json::json;
 use std::env;
 
 pub struct ClaudeProvider {
     client: Client,
     config: ProviderConfig,
+    policy: RequestPolicy,
 }
 
 impl ClaudeProvider {
     pub fn new(config: Option<ProviderConfig>) -> Self {
         Self {
             client: Client::new(),
-            config: config.unwrap_or(ProviderConfig {
-                api_key: None,
-                model: None,
-                base_url: None,
-                max_tokens: None,
-            }),
+            config: config.unwrap_or_default(),
+            policy: RequestPolicy::default(),
         }
     }
 
+    /// Timeout and retry behaviour for API calls
+    pub fn with_policy(mut self, policy: RequestPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
     fn get_api_key(&self) -> Result<String> {
         // Priority: Config > Env Var (config key and base_url are paired)
         self.config.api_key.clone()
@@ -37,6 +43,11 @@ impl ClaudeProvider {
             .unwrap_or_else(|| "claude-sonnet-4-5-20250929".to_string())
     }
 
+    /// The request's model, else the configured one
+    fn resolve_model(&self, request: &CompletionRequest) -> String {
+        request.model.clone().unwrap_or_else(|| self.get_model())
+    }
+
     fn get_base_url(&self) -> String {
         self.config.base_url.clone()
             .or_else(|| env::var("ANTHROPIC_BASE_URL").ok())
@@ -44,87 +55,258 @@ impl ClaudeProvider {
             .trim_end_matches('/')
             .to_string()
     }
-}
-
-#[async_trait]
-impl AiProvider for ClaudeProvider {
-    fn name(&self) -> &'static str {
-        "claude"
-    }
-
-    fn priority(&self) -> u8 {
-        // High priority if configured
-        if self.get_api_key().is_ok() { 90 } else { 0 }
-    }
 
-    async fn is_available(&self) -> bool {
-        self.get_api_key().is_ok()
-    }
-
-    async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+    /// POST the request to the Messages API, retrying per the policy
+    async fn send(&self, request: &CompletionRequest, stream: bool) -> Result<Response> {
         let api_key = self.get_api_key()?;
-        let model = request.model.clone().unwrap_or_else(|| self.get_model());
+        let model = self.resolve_model(request);
         let base_url = self.get_base_url();
         let max_tokens = request.max_tokens.or(self.config.max_tokens).unwrap_or(4096);
 
         let url = format!("{}/v1/messages", base_url);
 
-        let payload = json!({
+        let mut payload = json!({
             "model": model,
             "max_tokens": max_tokens,
-            "messages": [
-                {"role": "user", "content": request.prompt}
-            ]
+            "messages": request.messages
         });
+        if let Some(system) = &request.system {
+            payload["system"] = json!(system);
+        }
+        if let Some(temperature) = request.temperature {
+            payload["temperature"] = json!(temperature);
+        }
+        // Structured output: force a single tool whose input is the schema
+        if let Some(schema) = &request.response_schema {
+            payload["tools"] = json!([{
+                "name": schema.name,
+                "description": "Record the response in the required structure",
+                "input_schema": schema.schema
+            }]);
+            payload["tool_choice"] = json!({"type": "tool", "name": schema.name});
+        }
+        if stream {
+            payload["stream"] = json!(true);
+        }
 
         let is_custom_endpoint = self.config.base_url.is_some()
             || env::var("ANTHROPIC_BASE_URL").is_ok();
 
-        let mut req = self.client.post(&url)
-            .header("anthropic-version", "2023-06-01")
-            .header("content-type", "application/json");
+        self.policy.send(self.name(), || {
+            let req = self.client.post(&url)
+                .header("anthropic-version", "2023-06-01")
+                .header("content-type", "application/json");
 
-        if is_custom_endpoint {
-            req = req.header("Authorization", format!("Bearer {}", api_key));
-        } else {
-            req = req.header("x-api-key", &api_key);
-        }
+            let req = if is_custom_endpoint {
+                req.header("Authorization", format!("Bearer {}", api_key))
+            } else {
+                req.header("x-api-key", &api_key)
+            };
+            req.json(&payload)
+        }).await
+    }
+}
 
-        let response = req
-            .json(&payload)
-            .send()
-            .await
-            .context("Failed to send request to Claude API")?;
+#[async_trait]
+impl AiProvider for ClaudeProvider {
+    fn name(&self) -> &'static str {
+        "claude"
+    }
 
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow!("Claude API Error: {}", error_text));
-        }
+    fn priority(&self) -> u8 {
+        // High priority if configured
+        if self.get_api_key().is_ok() { 90 } else { 0 }
+    }
+
+    fn model(&self) -> Option<String> {
+        Some(self.get_model())
+    }
+
+    async fn is_available(&self) -> bool {
+        self.get_api_key().is_ok()
+    }
 
-        let body: Value = response.json().await.context("Failed to parse JSON response")?;
+    async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+        let response = self.send(request, false).await?;
+        let body = self.policy.read_json(self.name(), response).await?;
         
-        // Extract content
-        let content = body["content"][0]["text"]
-            .as_str()
-            .ok_or_else(|| anyhow!("Invalid response format: missing content[0].text"))?
-            .to_string();
+        // Extract content: the forced tool's input for structured output, else the text
+        let blocks = body["content"]
+            .as_array()
+            .ok_or_else(|| anyhow!("Invalid response format: missing content"))?;
+        let content = match blocks.iter().find(|b| b["type"] == "tool_use") {
+            Some(tool_use) => tool_use["input"].to_string(),
+            None => blocks.iter().filter_map(|b| b["text"].as_str()).collect(),
+        };
 
         // Extract usage if available
-        let usage = if let Some(u) = body.get("usage") {
+        let usage = body.get("usage").map(|u| {
             let prompt_tokens = u["input_tokens"].as_u64().unwrap_or(0) as usize;
             let completion_tokens = u["output_tokens"].as_u64().unwrap_or(0) as usize;
-            Some(TokenUsage {
+            TokenUsage {
                 prompt_tokens,
                 completion_tokens,
                 total_tokens: prompt_tokens + completion_tokens,
-            })
-        } else {
-            None
-        };
+            }
+        });
 
         Ok(CompletionResponse {
             content,
             usage,
+            provider: None,
+            model: Some(self.resolve_model(request)),
+            cached: false,
+        })
+    }
+
+    async fn complete_stream(
+        &self,
+        request: &CompletionRequest,
+        on_chunk: ChunkSink<'_>,
+    ) -> Result<CompletionResponse> {
+        let response = self.send(request, true).await?;
+
+        let mut content = String::new();
+        let mut usage: Option<TokenUsage> = None;
+        stream::for_each_event(self.name(), response, self.policy.timeout, |data| {
+            let event = stream::parse_json(data)?;
+            match event["type"].as_str() {
+                Some("message_start") => {
+                    let prompt_tokens = event["message"]["usage"]["input_tokens"].as_u64().unwrap_or(0) as usize;
+                    usage = Some(TokenUsage { prompt_tokens, completion_tokens: 0, total_tokens: prompt_tokens });
+                }
+                Some("content_block_delta") => {
+                    // `text_delta`, or `input_json_delta` pieces of a forced tool call
+                    let delta = &event["delta"];
+                    if let Some(text) = delta["text"].as_str().or_else(|| delta["partial_json"].as_str()) {
+                        content.push_str(text);
+                        on_chunk(text);
+                    }
+                }
+                Some("message_delta") => {
+                    if let (Some(u), Some(tokens)) = (usage.as_mut(), event["usage"]["output_tokens"].as_u64()) {
+                        u.completion_tokens = tokens as usize;
+                        u.total_tokens = u.prompt_tokens + u.completion_tokens;
+                    }
+                }
+                Some("error") => {
+                    // Errors after the 200 arrive as events; map them to the statuses they'd have had
+                    let status = match event["error"]["type"].as_str() {
+                        Some("rate_limit_error") => 429,
+                        Some("overloaded_error") => 529,
+                        Some("authentication_error") => 401,
+                        Some("permission_error") => 403,
+                        Some("invalid_request_error") => 400,
+                        _ => 500,
+                    };
+                    return Err(ProviderError::from_status(self.name(), status, None, data).into());
+                }
+                _ => {}
+            }
+            Ok(())
         })
+        .await?;
+
+        Ok(CompletionResponse { content, usage, provider: None, model: Some(self.resolve_model(request)), cached: false })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::ResponseSchema;
+    use crate::providers::mock_server::{MockResponse, MockServer};
+
+    fn provider(server: &MockServer) -> ClaudeProvider {
+        ClaudeProvider::new(Some(ProviderConfig {
+            api_key: Some("test-key".into()),
+            model: Some("claude-test".into()),
+            base_url: Some(server.url.clone()),
+            max_tokens: None,
+            ..Default::default()
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_stream_yields_deltas_and_usage() {
+        let server = MockServer::start(vec![MockResponse::chunked("text/event-stream", &[
+            "event: message_start\ndata: {\"type\":\"message_start\",\"message\":{\"usage\":{\"input_tokens\":12}}}\n\n",
+            "event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"Hel",
+            "lo\"}}\n\nevent: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\" world\"}}\n\n",
+            "event: message_delta\ndata: {\"type\":\"message_delta\",\"usage\":{\"output_tokens\":3}}\n\n",
+            "event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n",
+        ])]).await;
+
+        let mut chunks = Vec::new();
+        let request = CompletionRequest {
+            system: Some("Answer in English.".into()),
+            max_tokens: Some(100),
+            ..CompletionRequest::new("hi")
+        };
+        let response = provider(&server)
+            .complete_stream(&request, &mut |chunk: &str| chunks.push(chunk.to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(chunks, vec!["Hello", " world"]);
+        assert_eq!(response.content, "Hello world");
+        let usage = response.usage.unwrap();
+        assert_eq!((usage.prompt_tokens, usage.completion_tokens, usage.total_tokens), (12, 3, 15));
+
+        let (path, body) = &server.requests()[0];
+        assert_eq!(path, "/v1/messages");
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["model"], "claude-test");
+        assert_eq!(body["system"], "Answer in English.");
+        assert_eq!(body["messages"], json!([{"role": "user", "content": "hi"}]));
+    }
+
+    #[tokio::test]
+    async fn test_complete_without_stream() {
+        let server = MockServer::start(vec![MockResponse::json(
+            200,
+            r#"{"content":[{"type":"text","text":"Done"}],"usage":{"input_tokens":4,"output_tokens":1}}"#,
+        )]).await;
+
+        let request = CompletionRequest::new("hi");
+        let response = provider(&server).complete(&request).await.unwrap();
+        assert_eq!(response.content, "Done");
+        assert_eq!(response.usage.unwrap().total_tokens, 5);
+        assert!(server.requests()[0].1.get("stream").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_response_schema_forces_tool_and_returns_its_input() {
+        let server = MockServer::start(vec![MockResponse::json(
+            200,
+            r#"{"content":[{"type":"tool_use","name":"verdict","input":{"verdict":"pass"}}]}"#,
+        )]).await;
+
+        let request = CompletionRequest {
+            response_schema: Some(ResponseSchema {
+                name: "verdict".into(),
+                schema: json!({"type": "object", "properties": {"verdict": {"type": "string"}}}),
+            }),
+            ..CompletionRequest::new("judge")
+        };
+        let response = provider(&server).complete(&request).await.unwrap();
+        assert_eq!(response.content, r#"{"verdict":"pass"}"#);
+
+        let body = &server.requests()[0].1;
+        assert_eq!(body["tools"][0]["name"], "verdict");
+        assert_eq!(body["tools"][0]["input_schema"]["type"], "object");
+        assert_eq!(body["tool_choice"], json!({"type": "tool", "name": "verdict"}));
+    }
+
+    #[tokio::test]
+    async fn test_stream_error_event_fails() {
+        let server = MockServer::start(vec![MockResponse::chunked("text/event-stream", &[
+            "event: error\ndata: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n\n",
+        ])]).await;
+
+        let request = CompletionRequest::new("hi");
+        let err = provider(&server).complete_stream(&request, &mut |_: &str| {}).await.unwrap_err();
+        assert!(err.to_string().contains("Overloaded"), "{}", err);
+        assert!(matches!(err.downcast_ref::<ProviderError>(), Some(ProviderError::Overloaded { .. })));
     }
 }
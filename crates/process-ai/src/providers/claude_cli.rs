@@ -7,6 +7,7 @@ use std::io::Write;
 /// Claude CLI Provider — calls the `claude` command-line tool directly.
 /// This is the highest-priority provider when available because it uses
 /// the user's authenticated CLI session (no API key management needed).
+#[derive(Default)]
 pub struct ClaudeCliProvider;
 
 impl ClaudeCliProvider {
@@ -57,7 +58,7 @@ impl AiProvider for ClaudeCliProvider {
             .context("Failed to spawn claude CLI")?;
 
         if let Some(mut stdin) = child.stdin.take() {
-            stdin.write_all(request.prompt.as_bytes())
+            stdin.write_all(request.transcript().as_bytes())
                 .context("Failed to write prompt to claude CLI stdin")?;
         }
 
@@ -78,6 +79,9 @@ impl AiProvider for ClaudeCliProvider {
         Ok(CompletionResponse {
             content,
             usage: None,
+            provider: None,
+            model: None,
+            cached: false,
         })
     }
 }
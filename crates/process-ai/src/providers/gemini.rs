@@ -0,0 +1,270 @@
+use crate::provider::{AiProvider, ChunkSink, CompletionRequest, CompletionResponse, Role, TokenUsage};
+use crate::retry::RequestPolicy;
+use crate::stream;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use process_config::config::ProviderConfig;
+use reqwest::{Client, Response};
+use serde_json::{json, Value};
+use std::env;
+
+/// Google Gemini through the Generative Language API
+pub struct GeminiProvider {
+    client: Client,
+    config: ProviderConfig,
+    policy: RequestPolicy,
+}
+
+impl GeminiProvider {
+    pub fn new(config: Option<ProviderConfig>) -> Self {
+        Self {
+            client: Client::new(),
+            config: config.unwrap_or_default(),
+            policy: RequestPolicy::default(),
+        }
+    }
+
+    /// Timeout and retry behaviour for API calls
+    pub fn with_policy(mut self, policy: RequestPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    fn get_api_key(&self) -> Result<String> {
+        self.config.api_key.clone()
+            .or_else(|| env::var("GEMINI_API_KEY").ok())
+            .ok_or_else(|| anyhow!("Missing GEMINI_API_KEY"))
+    }
+
+    fn get_model(&self) -> String {
+        self.config.model.clone()
+            .or_else(|| env::var("GEMINI_MODEL").ok())
+            .unwrap_or_else(|| "gemini-2.5-pro".to_string())
+    }
+
+    /// The request's model, else the configured one
+    fn resolve_model(&self, request: &CompletionRequest) -> String {
+        request.model.clone().unwrap_or_else(|| self.get_model())
+    }
+
+    fn get_base_url(&self) -> String {
+        self.config.base_url.clone()
+            .or_else(|| env::var("GEMINI_BASE_URL").ok())
+            .unwrap_or_else(|| "https://generativelanguage.googleapis.com".to_string())
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    /// POST the request to `generateContent` (or its streaming variant),
+    /// retrying per the policy
+    async fn send(&self, request: &CompletionRequest, stream: bool) -> Result<Response> {
+        let api_key = self.get_api_key()?;
+        let model = self.resolve_model(request);
+        let base_url = self.get_base_url();
+        let max_tokens = request.max_tokens.or(self.config.max_tokens).unwrap_or(4096);
+
+        let url = if stream {
+            format!("{}/v1beta/models/{}:streamGenerateContent?alt=sse", base_url, model)
+        } else {
+            format!("{}/v1beta/models/{}:generateContent", base_url, model)
+        };
+
+        // Gemini calls the assistant turn "model"
+        let contents: Vec<Value> = request.messages.iter().map(|m| {
+            let role = match m.role {
+                Role::User => "user",
+                Role::Assistant => "model",
+            };
+            json!({"role": role, "parts": [{"text": m.content}]})
+        }).collect();
+
+        let mut payload = json!({
+            "contents": contents,
+            "generationConfig": {"maxOutputTokens": max_tokens}
+        });
+        if let Some(temperature) = request.temperature {
+            payload["generationConfig"]["temperature"] = json!(temperature);
+        }
+        if let Some(system) = &request.system {
+            payload["systemInstruction"] = json!({"parts": [{"text": system}]});
+        }
+        if let Some(schema) = &request.response_schema {
+            payload["generationConfig"]["responseMimeType"] = json!("application/json");
+            payload["generationConfig"]["responseJsonSchema"] = schema.schema.clone();
+        }
+
+        self.policy.send(self.name(), || {
+            self.client.post(&url)
+                .header("x-goog-api-key", &api_key)
+                .header("content-type", "application/json")
+                .json(&payload)
+        }).await
+    }
+}
+
+#[async_trait]
+impl AiProvider for GeminiProvider {
+    fn name(&self) -> &'static str {
+        "gemini"
+    }
+
+    fn priority(&self) -> u8 {
+        if self.get_api_key().is_ok() { 70 } else { 0 }
+    }
+
+    fn model(&self) -> Option<String> {
+        Some(self.get_model())
+    }
+
+    async fn is_available(&self) -> bool {
+        self.get_api_key().is_ok()
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+        let response = self.send(request, false).await?;
+        let body = self.policy.read_json(self.name(), response).await?;
+
+        let content = candidate_text(&body);
+        if content.is_empty() {
+            return Err(anyhow!("Gemini returned no text ({})", no_text_reason(&body)));
+        }
+
+        Ok(CompletionResponse {
+            content,
+            usage: body.get("usageMetadata").map(parse_usage),
+            provider: None,
+            model: Some(self.resolve_model(request)),
+            cached: false,
+        })
+    }
+
+    async fn complete_stream(
+        &self,
+        request: &CompletionRequest,
+        on_chunk: ChunkSink<'_>,
+    ) -> Result<CompletionResponse> {
+        let response = self.send(request, true).await?;
+
+        let mut content = String::new();
+        let mut usage = None;
+        let mut last = Value::Null;
+        stream::for_each_event(self.name(), response, self.policy.timeout, |data| {
+            let event = stream::parse_json(data)?;
+            if let Some(error) = event.get("error") {
+                return Err(anyhow!("Gemini API Error: {}", error["message"].as_str().unwrap_or(data)));
+            }
+            let text = candidate_text(&event);
+            if !text.is_empty() {
+                content.push_str(&text);
+                on_chunk(&text);
+            }
+            // Every chunk carries the running totals; the last one is final
+            if let Some(u) = event.get("usageMetadata") {
+                usage = Some(parse_usage(u));
+            }
+            last = event;
+            Ok(())
+        })
+        .await?;
+
+        if content.is_empty() {
+            return Err(anyhow!("Gemini returned no text ({})", no_text_reason(&last)));
+        }
+        Ok(CompletionResponse { content, usage, provider: None, model: Some(self.resolve_model(request)), cached: false })
+    }
+}
+
+/// The text parts of the first candidate, joined
+fn candidate_text(body: &Value) -> String {
+    body["candidates"][0]["content"]["parts"]
+        .as_array()
+        .map(|parts| parts.iter().filter_map(|p| p["text"].as_str()).collect())
+        .unwrap_or_default()
+}
+
+/// Why a response has no text: a blocked prompt or the finish reason
+fn no_text_reason(body: &Value) -> String {
+    if let Some(reason) = body["promptFeedback"]["blockReason"].as_str() {
+        return format!("prompt blocked: {}", reason);
+    }
+    match body["candidates"][0]["finishReason"].as_str() {
+        Some(reason) => format!("finish reason: {}", reason),
+        None => "no candidates".to_string(),
+    }
+}
+
+fn parse_usage(u: &Value) -> TokenUsage {
+    let prompt_tokens = u["promptTokenCount"].as_u64().unwrap_or(0) as usize;
+    // Thinking models bill their reasoning as output too
+    let completion_tokens = (u["candidatesTokenCount"].as_u64().unwrap_or(0)
+        + u["thoughtsTokenCount"].as_u64().unwrap_or(0)) as usize;
+    TokenUsage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens: prompt_tokens + completion_tokens,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{Message, ResponseSchema};
+    use crate::providers::mock_server::{MockResponse, MockServer};
+
+    fn provider(url: &str) -> GeminiProvider {
+        GeminiProvider::new(Some(ProviderConfig {
+            api_key: Some("test-key".into()),
+            base_url: Some(url.to_string()),
+            ..Default::default()
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_stream_maps_roles_and_schema() {
+        let server = MockServer::start(vec![MockResponse::chunked("text/event-stream", &[
+            "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\"Hi\"}]}}],\"usageMetadata\":{\"promptTokenCount\":7}}\n\n",
+            "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\" th",
+            "ere\"}]},\"finishReason\":\"STOP\"}],\"usageMetadata\":{\"promptTokenCount\":7,\"candidatesTokenCount\":2,\"thoughtsTokenCount\":3}}\n\n",
+        ])]).await;
+
+        let mut chunks = Vec::new();
+        let request = CompletionRequest {
+            system: Some("You review designs.".into()),
+            messages: vec![Message::user("Propose"), Message::assistant("A"), Message::user("Challenge A")],
+            model: Some("gemini-test".into()),
+            response_schema: Some(ResponseSchema { name: "critique".into(), schema: json!({"type": "object"}) }),
+            ..CompletionRequest::new("")
+        };
+        let response = provider(&server.url)
+            .complete_stream(&request, &mut |chunk: &str| chunks.push(chunk.to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(chunks, vec!["Hi", " there"]);
+        assert_eq!(response.content, "Hi there");
+        let usage = response.usage.unwrap();
+        assert_eq!((usage.prompt_tokens, usage.completion_tokens), (7, 5));
+        assert_eq!(response.model.as_deref(), Some("gemini-test"));
+
+        let (path, body) = &server.requests()[0];
+        assert_eq!(path, "/v1beta/models/gemini-test:streamGenerateContent?alt=sse");
+        assert_eq!(server.header(0, "x-goog-api-key").as_deref(), Some("test-key"));
+        let roles: Vec<&str> = body["contents"].as_array().unwrap().iter().map(|c| c["role"].as_str().unwrap()).collect();
+        assert_eq!(roles, ["user", "model", "user"]);
+        assert_eq!(body["systemInstruction"]["parts"][0]["text"], "You review designs.");
+        assert_eq!(body["generationConfig"]["responseMimeType"], "application/json");
+        assert_eq!(body["generationConfig"]["responseJsonSchema"]["type"], "object");
+    }
+
+    #[tokio::test]
+    async fn test_blocked_prompt_is_an_error() {
+        let server = MockServer::start(vec![MockResponse::json(
+            200,
+            r#"{"promptFeedback":{"blockReason":"SAFETY"},"usageMetadata":{"promptTokenCount":4}}"#,
+        )]).await;
+
+        let err = provider(&server.url).complete(&CompletionRequest::new("hi")).await.unwrap_err();
+        assert!(err.to_string().contains("prompt blocked: SAFETY"), "{}", err);
+        assert_eq!(server.requests()[0].0, "/v1beta/models/gemini-2.5-pro:generateContent");
+    }
+}
@@ -6,6 +6,7 @@ use std::io::{self, Write, BufRead};
 /// Manual Provider — displays the prompt to the user and waits for
 /// them to paste the AI response. Zero-dependency fallback that works
 /// with any AI model via copy-paste.
+#[derive(Default)]
 pub struct ManualProvider;
 
 impl ManualProvider {
@@ -39,7 +40,7 @@ impl AiProvider for ManualProvider {
         writeln!(out, "{}", "=".repeat(60))?;
         writeln!(out, "\nCopy the following prompt to your preferred AI:\n")?;
         writeln!(out, "{}", "-".repeat(40))?;
-        writeln!(out, "{}", request.prompt)?;
+        writeln!(out, "{}", request.transcript())?;
         writeln!(out, "{}", "-".repeat(40))?;
         writeln!(out, "\nPaste the AI response below.")?;
         writeln!(out, "End with an empty line followed by 'END' on its own line:\n")?;
@@ -65,6 +66,9 @@ impl AiProvider for ManualProvider {
         Ok(CompletionResponse {
             content,
             usage: None,
+            provider: None,
+            model: None,
+            cached: false,
         })
     }
 }
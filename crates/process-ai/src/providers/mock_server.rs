@@ -0,0 +1,130 @@
+//! Minimal local HTTP server for provider tests. Each connection gets the
+//! next scripted response (the last one repeats); bodies are written in
+//! the given pieces so streaming parsers see real chunk boundaries.
+
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<String>,
+}
+
+impl MockResponse {
+    /// 200 response whose body is sent as separate writes
+    pub fn chunked(content_type: &str, body: &[&str]) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("content-type".into(), content_type.into())],
+            body: body.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    pub fn json(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: vec![body.to_string()],
+        }
+    }
+}
+
+/// One request as the server saw it
+struct Received {
+    path: String,
+    /// Header names lowercased
+    headers: Vec<(String, String)>,
+    body: Value,
+}
+
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Received>>>,
+}
+
+impl MockServer {
+    pub async fn start(responses: Vec<MockResponse>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let seen = Arc::clone(&requests);
+        tokio::spawn(async move {
+            let mut served = 0;
+            while let Ok((socket, _)) = listener.accept().await {
+                let response = &responses[served.min(responses.len() - 1)];
+                served += 1;
+                if let Some(request) = read_request(socket, response).await {
+                    seen.lock().unwrap().push(request);
+                }
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// Path and JSON body of every request received so far
+    pub fn requests(&self) -> Vec<(String, Value)> {
+        self.requests.lock().unwrap().iter().map(|r| (r.path.clone(), r.body.clone())).collect()
+    }
+
+    /// Value of header `name` on the `index`th request
+    pub fn header(&self, index: usize, name: &str) -> Option<String> {
+        let requests = self.requests.lock().unwrap();
+        let (_, value) = requests.get(index)?.headers.iter().find(|(n, _)| n == name)?;
+        Some(value.clone())
+    }
+}
+
+async fn read_request(mut socket: TcpStream, response: &MockResponse) -> Option<Received> {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 4096];
+    let header_end = loop {
+        let n = socket.read(&mut buf).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        data.extend_from_slice(&buf[..n]);
+        if let Some(pos) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&data[..header_end]).to_string();
+    let path = head.split_whitespace().nth(1).unwrap_or("/").to_string();
+    let headers: Vec<(String, String)> = head
+        .lines()
+        .skip(1)
+        .filter_map(|l| l.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+    let length = headers
+        .iter()
+        .find(|(name, _)| name == "content-length")
+        .map_or(0, |(_, value)| value.parse().unwrap_or(0));
+    while data.len() < header_end + length {
+        let n = socket.read(&mut buf).await.ok()?;
+        if n == 0 {
+            break;
+        }
+        data.extend_from_slice(&buf[..n]);
+    }
+    let body = serde_json::from_slice(&data[header_end..]).unwrap_or(Value::Null);
+
+    let mut head = format!("HTTP/1.1 {} Mock\r\nconnection: close\r\n", response.status);
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+    socket.write_all(head.as_bytes()).await.ok()?;
+    for piece in &response.body {
+        socket.write_all(piece.as_bytes()).await.ok()?;
+        socket.flush().await.ok()?;
+        tokio::task::yield_now().await;
+    }
+    socket.shutdown().await.ok();
+    Some(Received { path, headers, body })
+}
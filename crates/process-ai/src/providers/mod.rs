@@ -1,5 +1,12 @@
 pub mod claude;
 pub mod openai;
 pub mod ollama;
+pub mod gemini;
+pub mod azure_openai;
+pub mod openai_compatible;
 pub mod claude_cli;
 pub mod manual;
+pub mod replay;
+
+#[cfg(test)]
+pub(crate) mod mock_server;
@@ -1,29 +1,34 @@
-use crate::provider::{AiProvider, CompletionRequest, CompletionResponse};
-use anyhow::{Result, Context, anyhow};
+use crate::provider::{AiProvider, ChunkSink, CompletionRequest, CompletionResponse, TokenUsage};
+use crate::retry::RequestPolicy;
+use crate::stream;
+use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use process_config::config::ProviderConfig;
-use reqwest::Client;
+use reqwest::{Client, Response};
 use serde_json::{json, Value};
 use std::env;
 
 pub struct OllamaProvider {
     client: Client,
     config: ProviderConfig,
+    policy: RequestPolicy,
 }
 
 impl OllamaProvider {
     pub fn new(config: Option<ProviderConfig>) -> Self {
         Self {
             client: Client::new(),
-            config: config.unwrap_or(ProviderConfig {
-                api_key: None,
-                model: None,
-                base_url: None,
-                max_tokens: None,
-            }),
+            config: config.unwrap_or_default(),
+            policy: RequestPolicy::default(),
         }
     }
 
+    /// Timeout and retry behaviour for API calls
+    pub fn with_policy(mut self, policy: RequestPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
     fn get_model(&self) -> String {
         env::var("OLLAMA_MODEL")
             .ok()
@@ -31,6 +36,11 @@ impl OllamaProvider {
             .unwrap_or_else(|| "llama3.1".to_string())
     }
 
+    /// The request's model, else the configured one
+    fn resolve_model(&self, request: &CompletionRequest) -> String {
+        request.model.clone().unwrap_or_else(|| self.get_model())
+    }
+
     fn get_base_url(&self) -> String {
         env::var("OLLAMA_BASE_URL")
             .ok()
@@ -39,6 +49,38 @@ impl OllamaProvider {
             .trim_end_matches('/')
             .to_string()
     }
+
+    /// POST the request to the chat API, retrying per the policy
+    async fn send(&self, request: &CompletionRequest, stream: bool) -> Result<Response> {
+        let model = self.resolve_model(request);
+        let base_url = self.get_base_url();
+
+        let url = format!("{}/api/chat", base_url);
+
+        let mut messages = Vec::new();
+        if let Some(system) = &request.system {
+            messages.push(json!({"role": "system", "content": system}));
+        }
+        messages.extend(request.messages.iter().map(|m| json!(m)));
+
+        let mut payload = json!({
+            "model": model,
+            "messages": messages,
+            "stream": stream
+        });
+        if let Some(schema) = &request.response_schema {
+            payload["format"] = schema.schema.clone();
+        }
+        if let Some(temperature) = request.temperature {
+            payload["options"] = json!({"temperature": temperature});
+        }
+
+        self.policy.send(self.name(), || {
+            self.client.post(&url)
+                .header("content-type", "application/json")
+                .json(&payload)
+        }).await
+    }
 }
 
 #[async_trait]
@@ -52,6 +94,10 @@ impl AiProvider for OllamaProvider {
         30
     }
 
+    fn model(&self) -> Option<String> {
+        Some(self.get_model())
+    }
+
     async fn is_available(&self) -> bool {
         let url = format!("{}/api/tags", self.get_base_url());
         self.client.get(&url)
@@ -63,39 +109,111 @@ impl AiProvider for OllamaProvider {
     }
 
     async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
-        let model = request.model.clone().unwrap_or_else(|| self.get_model());
-        let base_url = self.get_base_url();
-
-        let url = format!("{}/api/generate", base_url);
-
-        let payload = json!({
-            "model": model,
-            "prompt": request.prompt,
-            "stream": false
-        });
-
-        let response = self.client.post(&url)
-            .header("content-type", "application/json")
-            .json(&payload)
-            .send()
-            .await
-            .context("Failed to send request to Ollama")?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow!("Ollama API Error: {}", error_text));
-        }
-
-        let body: Value = response.json().await.context("Failed to parse JSON response")?;
+        let response = self.send(request, false).await?;
+        let body = self.policy.read_json(self.name(), response).await?;
         
-        let content = body["response"]
+        let content = body["message"]["content"]
             .as_str()
-            .ok_or_else(|| anyhow!("Invalid response format: missing 'response' field"))?
+            .ok_or_else(|| anyhow!("Invalid response format: missing 'message.content' field"))?
             .to_string();
 
         Ok(CompletionResponse {
             content,
-            usage: None, // Ollama doesn't provide token usage in the same way
+            usage: parse_usage(&body),
+            provider: None,
+            model: Some(self.resolve_model(request)),
+            cached: false,
+        })
+    }
+
+    async fn complete_stream(
+        &self,
+        request: &CompletionRequest,
+        on_chunk: ChunkSink<'_>,
+    ) -> Result<CompletionResponse> {
+        let response = self.send(request, true).await?;
+
+        // One JSON object per line; the last has `done: true` and the counts
+        let mut content = String::new();
+        let mut usage = None;
+        stream::for_each_line(self.name(), response, self.policy.timeout, |line| {
+            if line.trim().is_empty() {
+                return Ok(());
+            }
+            let event = stream::parse_json(line)?;
+            if let Some(error) = event["error"].as_str() {
+                return Err(anyhow!("Ollama API Error: {}", error));
+            }
+            if let Some(text) = event["message"]["content"].as_str().filter(|t| !t.is_empty()) {
+                content.push_str(text);
+                on_chunk(text);
+            }
+            if event["done"].as_bool() == Some(true) {
+                usage = parse_usage(&event);
+            }
+            Ok(())
         })
+        .await?;
+
+        Ok(CompletionResponse { content, usage, provider: None, model: Some(self.resolve_model(request)), cached: false })
+    }
+}
+
+/// Token counts from a final (`done`) response, when Ollama reports them
+fn parse_usage(body: &Value) -> Option<TokenUsage> {
+    let prompt_tokens = body["prompt_eval_count"].as_u64()? as usize;
+    let completion_tokens = body["eval_count"].as_u64().unwrap_or(0) as usize;
+    Some(TokenUsage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens: prompt_tokens + completion_tokens,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::ResponseSchema;
+    use crate::providers::mock_server::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn test_stream_reads_ndjson_until_done() {
+        let server = MockServer::start(vec![MockResponse::chunked("application/x-ndjson", &[
+            "{\"message\":{\"role\":\"assistant\",\"content\":\"Loc\"},\"done\":false}\n{\"mess",
+            "age\":{\"role\":\"assistant\",\"content\":\"al\"},\"done\":false}\n",
+            "{\"message\":{\"role\":\"assistant\",\"content\":\"\"},\"done\":true,\"prompt_eval_count\":5,\"eval_count\":2}\n",
+        ])]).await;
+        let provider = OllamaProvider::new(Some(ProviderConfig {
+            api_key: None,
+            model: Some("llama-test".into()),
+            base_url: Some(server.url.clone()),
+            max_tokens: None,
+            ..Default::default()
+        }));
+
+        let mut chunks = Vec::new();
+        let request = CompletionRequest {
+            system: Some("Be brief.".into()),
+            response_schema: Some(ResponseSchema { name: "answer".into(), schema: json!({"type": "object"}) }),
+            ..CompletionRequest::new("hi")
+        };
+        let response = provider
+            .complete_stream(&request, &mut |chunk: &str| chunks.push(chunk.to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(chunks, vec!["Loc", "al"]);
+        assert_eq!(response.content, "Local");
+        let usage = response.usage.unwrap();
+        assert_eq!((usage.prompt_tokens, usage.completion_tokens), (5, 2));
+
+        let (path, body) = &server.requests()[0];
+        assert_eq!(path, "/api/chat");
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["messages"], json!([
+            {"role": "system", "content": "Be brief."},
+            {"role": "user", "content": "hi"},
+        ]));
+        assert_eq!(body["format"], json!({"type": "object"}));
     }
 }
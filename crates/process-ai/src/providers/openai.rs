@@ -1,29 +1,35 @@
-use crate::provider::{AiProvider, CompletionRequest, CompletionResponse, TokenUsage};
-use anyhow::{Result, Context, anyhow};
+use crate::provider::{AiProvider, ChunkSink, CompletionRequest, CompletionResponse, TokenUsage};
+use crate::retry::RequestPolicy;
+use crate::stream;
+use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use process_config::config::ProviderConfig;
-use reqwest::Client;
+use reqwest::{Client, Response};
 use serde_json::{json, Value};
 use std::env;
+use std::time::Duration;
 
 pub struct OpenAiProvider {
     client: Client,
     config: ProviderConfig,
+    policy: RequestPolicy,
 }
 
 impl OpenAiProvider {
     pub fn new(config: Option<ProviderConfig>) -> Self {
         Self {
             client: Client::new(),
-            config: config.unwrap_or(ProviderConfig {
-                api_key: None,
-                model: None,
-                base_url: None,
-                max_tokens: None,
-            }),
+            config: config.unwrap_or_default(),
+            policy: RequestPolicy::default(),
         }
     }
 
+    /// Timeout and retry behaviour for API calls
+    pub fn with_policy(mut self, policy: RequestPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
     fn get_api_key(&self) -> Result<String> {
         if let Ok(key) = env::var("OPENAI_API_KEY") {
             return Ok(key);
@@ -38,6 +44,11 @@ impl OpenAiProvider {
             .unwrap_or_else(|| "gpt-4o".to_string())
     }
 
+    /// The request's model, else the configured one
+    fn resolve_model(&self, request: &CompletionRequest) -> String {
+        request.model.clone().unwrap_or_else(|| self.get_model())
+    }
+
     fn get_base_url(&self) -> String {
         env::var("OPENAI_BASE_URL")
             .ok()
@@ -46,6 +57,26 @@ impl OpenAiProvider {
             .trim_end_matches('/')
             .to_string()
     }
+
+    /// POST the request to Chat Completions, retrying per the policy
+    async fn send(&self, request: &CompletionRequest, stream: bool) -> Result<Response> {
+        let api_key = self.get_api_key()?;
+        let model = self.resolve_model(request);
+        let base_url = self.get_base_url();
+        let max_tokens = request.max_tokens.or(self.config.max_tokens).unwrap_or(4096);
+
+        let url = format!("{}/v1/chat/completions", base_url);
+
+        let mut payload = chat_payload(request, max_tokens, stream);
+        payload["model"] = json!(model);
+
+        self.policy.send(self.name(), || {
+            self.client.post(&url)
+                .header("Authorization", format!("Bearer {}", api_key))
+                .header("content-type", "application/json")
+                .json(&payload)
+        }).await
+    }
 }
 
 #[async_trait]
@@ -58,61 +89,165 @@ impl AiProvider for OpenAiProvider {
         if self.get_api_key().is_ok() { 80 } else { 0 }
     }
 
+    fn model(&self) -> Option<String> {
+        Some(self.get_model())
+    }
+
     async fn is_available(&self) -> bool {
         self.get_api_key().is_ok()
     }
 
     async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
-        let api_key = self.get_api_key()?;
-        let model = request.model.clone().unwrap_or_else(|| self.get_model());
-        let base_url = self.get_base_url();
-        let max_tokens = request.max_tokens.or(self.config.max_tokens).unwrap_or(4096);
+        let response = self.send(request, false).await?;
+        let body = self.policy.read_json(self.name(), response).await?;
+        let (content, usage) = parse_chat_response(&body)?;
 
-        let url = format!("{}/v1/chat/completions", base_url);
+        Ok(CompletionResponse { content, usage, provider: None, model: Some(self.resolve_model(request)), cached: false })
+    }
 
-        let payload = json!({
-            "model": model,
-            "max_tokens": max_tokens,
-            "messages": [
-                {"role": "user", "content": request.prompt}
-            ]
+    async fn complete_stream(
+        &self,
+        request: &CompletionRequest,
+        on_chunk: ChunkSink<'_>,
+    ) -> Result<CompletionResponse> {
+        let response = self.send(request, true).await?;
+
+        let (content, usage) = stream_chat(self.name(), "OpenAI", response, self.policy.timeout, on_chunk).await?;
+        Ok(CompletionResponse { content, usage, provider: None, model: Some(self.resolve_model(request)), cached: false })
+    }
+}
+
+/// Chat Completions body for `request`, without `model`: OpenAI names it
+/// in the payload, Azure OpenAI in the deployment URL
+pub(crate) fn chat_payload(request: &CompletionRequest, max_tokens: usize, stream: bool) -> Value {
+    let mut messages = Vec::new();
+    if let Some(system) = &request.system {
+        messages.push(json!({"role": "system", "content": system}));
+    }
+    messages.extend(request.messages.iter().map(|m| json!(m)));
+
+    let mut payload = json!({
+        "max_tokens": max_tokens,
+        "messages": messages
+    });
+    if let Some(temperature) = request.temperature {
+        payload["temperature"] = json!(temperature);
+    }
+    if let Some(schema) = &request.response_schema {
+        payload["response_format"] = json!({
+            "type": "json_schema",
+            "json_schema": {"name": schema.name, "schema": schema.schema}
         });
+    }
+    if stream {
+        payload["stream"] = json!(true);
+        payload["stream_options"] = json!({"include_usage": true});
+    }
+    payload
+}
 
-        let response = self.client.post(&url)
-            .header("Authorization", format!("Bearer {}", api_key))
-            .header("content-type", "application/json")
-            .json(&payload)
-            .send()
-            .await
-            .context("Failed to send request to OpenAI API")?;
+/// Content and usage of a non-streamed Chat Completions response
+pub(crate) fn parse_chat_response(body: &Value) -> Result<(String, Option<TokenUsage>)> {
+    let content = body["choices"][0]["message"]["content"]
+        .as_str()
+        .ok_or_else(|| anyhow!("Invalid response format: missing choices[0].message.content"))?
+        .to_string();
+    Ok((content, body.get("usage").map(parse_usage)))
+}
 
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow!("OpenAI API Error: {}", error_text));
+/// Read a streamed Chat Completions response, passing each delta to
+/// `on_chunk`; `api` names the service in error messages
+pub(crate) async fn stream_chat(
+    provider: &str,
+    api: &str,
+    response: Response,
+    timeout: Duration,
+    on_chunk: ChunkSink<'_>,
+) -> Result<(String, Option<TokenUsage>)> {
+    let mut content = String::new();
+    let mut usage = None;
+    stream::for_each_event(provider, response, timeout, |data| {
+        if data == "[DONE]" {
+            return Ok(());
+        }
+        let event = stream::parse_json(data)?;
+        if let Some(error) = event.get("error") {
+            return Err(anyhow!("{} API Error: {}", api, error["message"].as_str().unwrap_or(data)));
+        }
+        if let Some(text) = event["choices"][0]["delta"]["content"].as_str().filter(|t| !t.is_empty()) {
+            content.push_str(text);
+            on_chunk(text);
         }
+        // Sent in a final chunk with no choices when include_usage is set
+        if let Some(u) = event.get("usage").filter(|u| !u.is_null()) {
+            usage = Some(parse_usage(u));
+        }
+        Ok(())
+    })
+    .await?;
+    Ok((content, usage))
+}
 
-        let body: Value = response.json().await.context("Failed to parse JSON response")?;
-        
-        let content = body["choices"][0]["message"]["content"]
-            .as_str()
-            .ok_or_else(|| anyhow!("Invalid response format: missing choices[0].message.content"))?
-            .to_string();
-
-        let usage = if let Some(u) = body.get("usage") {
-            let prompt_tokens = u["prompt_tokens"].as_u64().unwrap_or(0) as usize;
-            let completion_tokens = u["completion_tokens"].as_u64().unwrap_or(0) as usize;
-            Some(TokenUsage {
-                prompt_tokens,
-                completion_tokens,
-                total_tokens: prompt_tokens + completion_tokens,
-            })
-        } else {
-            None
-        };
+fn parse_usage(u: &Value) -> TokenUsage {
+    let prompt_tokens = u["prompt_tokens"].as_u64().unwrap_or(0) as usize;
+    let completion_tokens = u["completion_tokens"].as_u64().unwrap_or(0) as usize;
+    TokenUsage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens: prompt_tokens + completion_tokens,
+    }
+}
 
-        Ok(CompletionResponse {
-            content,
-            usage,
-        })
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{Message, ResponseSchema};
+    use crate::providers::mock_server::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn test_stream_yields_deltas_and_usage() {
+        let server = MockServer::start(vec![MockResponse::chunked("text/event-stream", &[
+            "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\",\"content\":\"\"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"Hi\"}}]}\n\ndata: {\"choices\":[{\"delta\":{\"con",
+            "tent\":\" there\"}}]}\n\n",
+            "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":7,\"completion_tokens\":2,\"total_tokens\":9}}\n\n",
+            "data: [DONE]\n\n",
+        ])]).await;
+        let provider = OpenAiProvider::new(Some(ProviderConfig {
+            api_key: Some("test-key".into()),
+            model: None,
+            base_url: Some(server.url.clone()),
+            max_tokens: None,
+            ..Default::default()
+        }));
+
+        let mut chunks = Vec::new();
+        let request = CompletionRequest {
+            system: Some("You review designs.".into()),
+            messages: vec![Message::user("Propose"), Message::assistant("A"), Message::user("Challenge A")],
+            model: Some("gpt-test".into()),
+            temperature: Some(0.5),
+            response_schema: Some(ResponseSchema { name: "critique".into(), schema: json!({"type": "object"}) }),
+            ..CompletionRequest::new("")
+        };
+        let response = provider
+            .complete_stream(&request, &mut |chunk: &str| chunks.push(chunk.to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(chunks, vec!["Hi", " there"]);
+        assert_eq!(response.content, "Hi there");
+        assert_eq!(response.usage.unwrap().total_tokens, 9);
+
+        let (path, body) = &server.requests()[0];
+        assert_eq!(path, "/v1/chat/completions");
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["stream_options"]["include_usage"], true);
+        assert_eq!(body["temperature"], 0.5);
+        let roles: Vec<&str> = body["messages"].as_array().unwrap().iter().map(|m| m["role"].as_str().unwrap()).collect();
+        assert_eq!(roles, ["system", "user", "assistant", "user"]);
+        assert_eq!(body["messages"][0]["content"], "You review designs.");
+        assert_eq!(body["response_format"]["type"], "json_schema");
+        assert_eq!(body["response_format"]["json_schema"]["name"], "critique");
     }
 }
@@ -0,0 +1,191 @@
+use crate::provider::{AiProvider, ChunkSink, CompletionRequest, CompletionResponse};
+use crate::providers::openai::{chat_payload, parse_chat_response, stream_chat};
+use crate::retry::RequestPolicy;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use process_config::config::ProfileConfig;
+use reqwest::{Client, Response};
+use serde_json::json;
+use std::env;
+
+/// A named `ai.profiles` endpoint speaking the Chat Completions API:
+/// LM Studio, vLLM, llama.cpp's server, OpenRouter and the like
+pub struct OpenAiCompatibleProvider {
+    name: String,
+    client: Client,
+    profile: ProfileConfig,
+    policy: RequestPolicy,
+}
+
+impl OpenAiCompatibleProvider {
+    pub fn new(name: impl Into<String>, profile: ProfileConfig) -> Self {
+        Self {
+            name: name.into(),
+            client: Client::new(),
+            profile,
+            policy: RequestPolicy::default(),
+        }
+    }
+
+    /// Timeout and retry behaviour for API calls
+    pub fn with_policy(mut self, policy: RequestPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// The key from `api_key_env`; `None` when the profile needs none
+    fn get_api_key(&self) -> Result<Option<String>> {
+        match &self.profile.api_key_env {
+            Some(var) => env::var(var)
+                .map(Some)
+                .map_err(|_| anyhow!("Missing {} for provider '{}'", var, self.name)),
+            None => Ok(None),
+        }
+    }
+
+    /// The request's model, else the profile's; servers hosting a single
+    /// model accept none
+    fn resolve_model(&self, request: &CompletionRequest) -> Option<String> {
+        request.model.clone().or_else(|| self.profile.model.clone())
+    }
+
+    /// POST the request to `{base_url}/chat/completions`, retrying per the
+    /// policy
+    async fn send(&self, request: &CompletionRequest, stream: bool) -> Result<Response> {
+        let api_key = self.get_api_key()?;
+        let max_tokens = request.max_tokens.or(self.profile.max_tokens).unwrap_or(4096);
+
+        let url = format!("{}/chat/completions", self.profile.base_url.trim_end_matches('/'));
+
+        let mut payload = chat_payload(request, max_tokens, stream);
+        if let Some(model) = self.resolve_model(request) {
+            payload["model"] = json!(model);
+        }
+
+        self.policy.send(self.name(), || {
+            let mut req = self.client.post(&url).header("content-type", "application/json");
+            if let Some(key) = &api_key {
+                req = req.header("Authorization", format!("Bearer {}", key));
+            }
+            for (name, value) in &self.profile.headers {
+                req = req.header(name, value);
+            }
+            req.json(&payload)
+        }).await
+    }
+}
+
+#[async_trait]
+impl AiProvider for OpenAiCompatibleProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn priority(&self) -> u8 {
+        // Configured on purpose, but behind the hosted APIs
+        if self.get_api_key().is_ok() { 50 } else { 0 }
+    }
+
+    fn model(&self) -> Option<String> {
+        self.profile.model.clone()
+    }
+
+    async fn is_available(&self) -> bool {
+        self.get_api_key().is_ok()
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+        let response = self.send(request, false).await?;
+        let body = self.policy.read_json(self.name(), response).await?;
+        let (content, usage) = parse_chat_response(&body)?;
+
+        // Fall back to the model the server says it used
+        let model = self.resolve_model(request).or_else(|| body["model"].as_str().map(str::to_string));
+        Ok(CompletionResponse { content, usage, provider: None, model, cached: false })
+    }
+
+    async fn complete_stream(
+        &self,
+        request: &CompletionRequest,
+        on_chunk: ChunkSink<'_>,
+    ) -> Result<CompletionResponse> {
+        let response = self.send(request, true).await?;
+
+        let (content, usage) = stream_chat(self.name(), &self.name, response, self.policy.timeout, on_chunk).await?;
+        Ok(CompletionResponse { content, usage, provider: None, model: self.resolve_model(request), cached: false })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock_server::{MockResponse, MockServer};
+    use process_config::config::ProfileKind;
+    use std::collections::BTreeMap;
+
+    fn profile(base_url: String) -> ProfileConfig {
+        ProfileConfig {
+            kind: ProfileKind::OpenaiCompatible,
+            base_url,
+            api_key_env: None,
+            model: None,
+            headers: BTreeMap::new(),
+            max_tokens: None,
+            timeout_secs: None,
+            max_retries: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_profile_sends_key_and_extra_headers() {
+        let server = MockServer::start(vec![MockResponse::chunked("text/event-stream", &[
+            "data: {\"choices\":[{\"delta\":{\"content\":\"Hi\"}}]}\n\n",
+            "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":3,\"completion_tokens\":1,\"total_tokens\":4}}\n\n",
+            "data: [DONE]\n\n",
+        ])]).await;
+        std::env::set_var("PROCESS_TEST_OPENROUTER_KEY", "or-key");
+        let provider = OpenAiCompatibleProvider::new("openrouter", ProfileConfig {
+            api_key_env: Some("PROCESS_TEST_OPENROUTER_KEY".into()),
+            model: Some("anthropic/claude-sonnet-4.5".into()),
+            headers: BTreeMap::from([("HTTP-Referer".to_string(), "https://example.com".to_string())]),
+            ..profile(format!("{}/api/v1/", server.url))
+        });
+
+        assert_eq!(provider.name(), "openrouter");
+        assert_eq!(provider.priority(), 50);
+        let mut streamed = String::new();
+        let response = provider
+            .complete_stream(&CompletionRequest::new("hello"), &mut |c: &str| streamed.push_str(c))
+            .await
+            .unwrap();
+        assert_eq!((streamed.as_str(), response.usage.unwrap().total_tokens), ("Hi", 4));
+
+        let (path, body) = &server.requests()[0];
+        assert_eq!(path, "/api/v1/chat/completions");
+        assert_eq!(body["model"], "anthropic/claude-sonnet-4.5");
+        assert_eq!(server.header(0, "authorization").as_deref(), Some("Bearer or-key"));
+        assert_eq!(server.header(0, "http-referer").as_deref(), Some("https://example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_local_profile_needs_no_key_or_model() {
+        let server = MockServer::start(vec![MockResponse::json(
+            200,
+            r#"{"model":"qwen2.5-coder","choices":[{"message":{"role":"assistant","content":"ok"}}]}"#,
+        )]).await;
+        let provider = OpenAiCompatibleProvider::new("local", profile(server.url.clone()));
+
+        assert!(provider.is_available().await);
+        let response = provider.complete(&CompletionRequest::new("hi")).await.unwrap();
+        assert_eq!(response.content, "ok");
+        assert_eq!(response.model.as_deref(), Some("qwen2.5-coder"));
+        assert!(server.requests()[0].1.get("model").is_none());
+        assert!(server.header(0, "authorization").is_none());
+
+        let missing = OpenAiCompatibleProvider::new("remote", ProfileConfig {
+            api_key_env: Some("PROCESS_TEST_UNSET_KEY".into()),
+            ..profile(server.url.clone())
+        });
+        assert!(!missing.is_available().await);
+    }
+}
@@ -0,0 +1,220 @@
+//! Record/replay for deterministic, offline runs (`ai.replay`).
+//!
+//! A fixture is one YAML file per response in the fixture directory,
+//! named after the prompt template (`diverge.yaml`) or, for requests
+//! rendered from no template or that need telling apart, the prompt hash
+//! (`3f2a….yaml`). The `replay` provider answers from these files; with
+//! `record: true` the real provider's responses are written to them.
+
+use crate::notice::{self, Notice};
+use crate::provider::{AiProvider, ChunkSink, CompletionRequest, CompletionResponse, TokenUsage};
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// One recorded response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Fixture {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_hash: Option<String>,
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+}
+
+/// Short hash of everything the model is shown: system prompt, messages
+/// and response schema
+pub fn prompt_hash(request: &CompletionRequest) -> String {
+    let digest = format!("{:x}", Sha256::digest(request.transcript().as_bytes()));
+    digest[..16].to_string()
+}
+
+/// Serves completions from fixture files
+pub struct ReplayProvider {
+    dir: PathBuf,
+}
+
+impl ReplayProvider {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The fixture for a request: by prompt hash first, then by template
+    fn find(&self, request: &CompletionRequest) -> Result<Fixture> {
+        let hash = prompt_hash(request);
+        let candidates = std::iter::once(hash.as_str()).chain(request.template.as_deref());
+        for name in candidates {
+            let path = self.dir.join(format!("{}.yaml", name));
+            if let Ok(content) = std::fs::read_to_string(&path) {
+                return serde_yaml::from_str(&content).with_context(|| format!("Invalid fixture {}", path.display()));
+            }
+        }
+        Err(anyhow!(
+            "No replay fixture for {} in {} (record one with `ai.replay.record: true`)",
+            match &request.template {
+                Some(template) => format!("template '{}' or prompt hash {}", template, hash),
+                None => format!("prompt hash {}", hash),
+            },
+            self.dir.display()
+        ))
+    }
+}
+
+#[async_trait]
+impl AiProvider for ReplayProvider {
+    fn name(&self) -> &'static str {
+        "replay"
+    }
+
+    fn priority(&self) -> u8 {
+        // Only ever used when asked for by name
+        0
+    }
+
+    async fn is_available(&self) -> bool {
+        self.dir.is_dir()
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+        let fixture = self.find(request)?;
+        Ok(CompletionResponse {
+            content: fixture.content,
+            usage: fixture.usage,
+            provider: None,
+            model: None,
+            cached: false,
+        })
+    }
+}
+
+/// Passes completions through to a real provider and saves each response
+/// as a fixture
+pub struct Recorder {
+    inner: Arc<dyn AiProvider>,
+    dir: PathBuf,
+}
+
+impl Recorder {
+    pub fn new(inner: Arc<dyn AiProvider>, dir: impl Into<PathBuf>) -> Self {
+        Self { inner, dir: dir.into() }
+    }
+
+    fn save(&self, request: &CompletionRequest, response: &CompletionResponse) -> Result<PathBuf> {
+        let hash = prompt_hash(request);
+        let name = request.template.clone().unwrap_or_else(|| hash.clone());
+        let fixture = Fixture {
+            template: request.template.clone(),
+            prompt_hash: Some(hash),
+            content: response.content.clone(),
+            usage: response.usage.clone(),
+        };
+        std::fs::create_dir_all(&self.dir).with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let path = self.dir.join(format!("{}.yaml", name));
+        std::fs::write(&path, serde_yaml::to_string(&fixture)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    fn note(&self, request: &CompletionRequest, response: &CompletionResponse) {
+        match self.save(request, response) {
+            Ok(path) => notice::emit(Notice::Recorded { path }),
+            Err(err) => notice::emit(Notice::NotRecorded { error: format!("{:#}", err) }),
+        }
+    }
+}
+
+#[async_trait]
+impl AiProvider for Recorder {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn label(&self) -> String {
+        format!("{} (recording)", self.inner.label())
+    }
+
+    fn primary(&self) -> String {
+        self.inner.primary()
+    }
+
+    fn model(&self) -> Option<String> {
+        self.inner.model()
+    }
+
+    async fn is_available(&self) -> bool {
+        self.inner.is_available().await
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+        let response = self.inner.complete(request).await?;
+        self.note(request, &response);
+        Ok(response)
+    }
+
+    async fn complete_stream(&self, request: &CompletionRequest, on_chunk: ChunkSink<'_>) -> Result<CompletionResponse> {
+        let response = self.inner.complete_stream(request, on_chunk).await?;
+        self.note(request, &response);
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed;
+
+    #[async_trait]
+    impl AiProvider for Fixed {
+        fn name(&self) -> &'static str { "fixed" }
+        fn priority(&self) -> u8 { 0 }
+        async fn is_available(&self) -> bool { true }
+        async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+            Ok(CompletionResponse { content: format!("re: {}", request.transcript()), usage: None, provider: None, model: None, cached: false })
+        }
+    }
+
+    fn templated(prompt: &str, template: &str) -> CompletionRequest {
+        CompletionRequest { template: Some(template.into()), ..CompletionRequest::new(prompt) }
+    }
+
+    #[tokio::test]
+    async fn test_recorded_responses_replay() {
+        let tmp = tempfile::tempdir().unwrap();
+        let recorder = Recorder::new(Arc::new(Fixed), tmp.path());
+        recorder.complete(&templated("seed one", "diverge")).await.unwrap();
+        recorder.complete(&CompletionRequest::new("untemplated")).await.unwrap();
+
+        let replay = ReplayProvider::new(tmp.path());
+        assert!(replay.is_available().await);
+        // A template fixture answers any prompt rendered from that template
+        let reply = replay.complete(&templated("seed two", "diverge")).await.unwrap();
+        assert_eq!(reply.content, "re: seed one");
+        let reply = replay.complete(&CompletionRequest::new("untemplated")).await.unwrap();
+        assert_eq!(reply.content, "re: untemplated");
+    }
+
+    #[tokio::test]
+    async fn test_prompt_hash_fixture_wins_over_template() {
+        let tmp = tempfile::tempdir().unwrap();
+        let specific = templated("role: security", "review");
+        std::fs::write(tmp.path().join("review.yaml"), "content: generic\n").unwrap();
+        std::fs::write(tmp.path().join(format!("{}.yaml", prompt_hash(&specific))), "content: specific\n").unwrap();
+
+        let replay = ReplayProvider::new(tmp.path());
+        assert_eq!(replay.complete(&specific).await.unwrap().content, "specific");
+        assert_eq!(replay.complete(&templated("role: other", "review")).await.unwrap().content, "generic");
+
+        let err = replay.complete(&templated("x", "missing")).await.unwrap_err();
+        assert!(err.to_string().contains("template 'missing'"), "{}", err);
+    }
+}
@@ -1,10 +1,35 @@
+use crate::fallback::FallbackChain;
+use crate::notice::{self, Notice};
 use crate::provider::AiProvider;
+use crate::providers::{
+    azure_openai::AzureOpenAiProvider,
+    claude::ClaudeProvider,
+    claude_cli::ClaudeCliProvider,
+    gemini::GeminiProvider,
+    manual::ManualProvider,
+    ollama::OllamaProvider,
+    openai::OpenAiProvider,
+    openai_compatible::OpenAiCompatibleProvider,
+    replay::{Recorder, ReplayProvider},
+};
+use crate::retry::RequestPolicy;
 use anyhow::{Result, anyhow};
+use process_config::config::{Config, ProfileKind};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Providers built into the CLI; `ai.profiles` add more under their own names
+pub const BUILTIN_PROVIDERS: &[&str] = &[
+    "claude", "openai", "azure-openai", "gemini", "ollama", "claude-cli", "manual", "replay",
+];
+
 pub struct AiRegistry {
     providers: HashMap<String, Arc<dyn AiProvider>>,
+    /// Tried in order after the requested provider fails (`ai.fallback`)
+    fallback: Vec<String>,
+    /// Where responses are saved as replay fixtures, when recording
+    record_dir: Option<PathBuf>,
 }
 
 impl Default for AiRegistry {
@@ -17,7 +42,79 @@ impl AiRegistry {
     pub fn new() -> Self {
         Self {
             providers: HashMap::new(),
+            fallback: Vec::new(),
+            record_dir: None,
+        }
+    }
+
+    /// Registry with every built-in provider, configured from `config`
+    pub fn from_config(config: &Config) -> Self {
+        let mut registry = Self::new();
+
+        let policy = |provider| RequestPolicy::from_config(&config.settings, provider);
+
+        // Claude API provider (priority 90 when key available)
+        registry.register(
+            ClaudeProvider::new(config.ai.claude.clone()).with_policy(policy(config.ai.claude.as_ref())),
+        );
+
+        // OpenAI provider (priority 80 when key available)
+        registry.register(
+            OpenAiProvider::new(config.ai.openai.clone()).with_policy(policy(config.ai.openai.as_ref())),
+        );
+
+        // Azure OpenAI provider (priority 85 when key, endpoint and deployment set)
+        registry.register(
+            AzureOpenAiProvider::new(config.ai.azure_openai.clone())
+                .with_policy(policy(config.ai.azure_openai.as_ref())),
+        );
+
+        // Gemini provider (priority 70 when key available)
+        registry.register(
+            GeminiProvider::new(config.ai.gemini.clone()).with_policy(policy(config.ai.gemini.as_ref())),
+        );
+
+        // Ollama local provider (priority 30, always registered)
+        registry.register(
+            OllamaProvider::new(config.ai.ollama.clone()).with_policy(policy(config.ai.ollama.as_ref())),
+        );
+
+        // Claude CLI provider (priority 95 when binary found)
+        registry.register(ClaudeCliProvider::new());
+
+        // Manual provider (priority 1, always available on TTY)
+        registry.register(ManualProvider::new());
+
+        // Named profiles (priority 50 when their key is set)
+        for (name, profile) in &config.ai.profiles {
+            if name == "auto" || BUILTIN_PROVIDERS.contains(&name.as_str()) {
+                notice::emit(Notice::ReservedProfile { name: name.clone() });
+                continue;
+            }
+            let policy = RequestPolicy::from_config(&config.settings, Some(&profile.provider_config()));
+            match profile.kind {
+                ProfileKind::OpenaiCompatible => {
+                    registry.register(OpenAiCompatibleProvider::new(name, profile.clone()).with_policy(policy))
+                }
+            }
         }
+
+        registry.set_fallback(config.ai.fallback.clone());
+        registry
+    }
+
+    /// `from_config` plus what depends on the project: the `replay`
+    /// provider and recording, with `ai.replay.dir` under `project_root`
+    pub fn for_project(project_root: &Path, config: &Config) -> Self {
+        let mut registry = Self::from_config(config);
+        if let Some(replay) = &config.ai.replay {
+            let dir = project_root.join(&replay.dir);
+            registry.register(ReplayProvider::new(dir.clone()));
+            if replay.record {
+                registry.record_dir = Some(dir);
+            }
+        }
+        registry
     }
 
     pub fn register<P: AiProvider + 'static>(&mut self, provider: P) {
@@ -25,18 +122,54 @@ impl AiRegistry {
         self.providers.insert(name, Arc::new(provider));
     }
 
+    /// Providers to try, in order, when the requested one fails
+    pub fn set_fallback(&mut self, names: Vec<String>) {
+        self.fallback = names;
+    }
+
     pub fn provider_exists(&self, name: &str) -> bool {
         self.providers.contains_key(name)
     }
 
+    /// The named provider (or the best available one for `auto`). With a
+    /// fallback list configured, it comes wrapped in a chain that moves on
+    /// to the listed providers when it fails; when recording, its
+    /// responses are saved as fixtures.
     pub async fn get_provider(&self, name: &str) -> Result<Arc<dyn AiProvider>> {
-        if name == "auto" {
-            self.auto_detect().await
+        let provider = self.resolve(name).await?;
+        Ok(match &self.record_dir {
+            Some(dir) => Arc::new(Recorder::new(provider, dir.clone())),
+            None => provider,
+        })
+    }
+
+    async fn resolve(&self, name: &str) -> Result<Arc<dyn AiProvider>> {
+        let primary = if name == "auto" {
+            self.auto_detect().await?
         } else {
-            self.providers.get(name)
-                .cloned()
-                .ok_or_else(|| anyhow!("Provider '{}' not found", name))
+            self.named(name)?
+        };
+        if self.fallback.is_empty() {
+            return Ok(primary);
         }
+
+        let mut chain = vec![primary];
+        for name in &self.fallback {
+            let provider = self.named(name)?;
+            if !chain.iter().any(|p| p.name() == provider.name()) {
+                chain.push(provider);
+            }
+        }
+        if chain.len() == 1 {
+            return Ok(chain.remove(0));
+        }
+        Ok(Arc::new(FallbackChain::new(chain)))
+    }
+
+    fn named(&self, name: &str) -> Result<Arc<dyn AiProvider>> {
+        self.providers.get(name)
+            .cloned()
+            .ok_or_else(|| anyhow!("Provider '{}' not found", name))
     }
 
     async fn auto_detect(&self) -> Result<Arc<dyn AiProvider>> {
@@ -60,6 +193,7 @@ impl AiRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::ProviderError;
     use crate::provider::{CompletionRequest, CompletionResponse};
     use async_trait::async_trait;
 
@@ -67,6 +201,7 @@ mod tests {
         mock_name: &'static str,
         mock_priority: u8,
         mock_available: bool,
+        mock_fails: bool,
     }
 
     impl MockProvider {
@@ -75,8 +210,13 @@ mod tests {
                 mock_name: name,
                 mock_priority: priority,
                 mock_available: available,
+                mock_fails: false,
             }
         }
+
+        fn failing(name: &'static str) -> Self {
+            Self { mock_fails: true, ..Self::new(name, 50, true) }
+        }
     }
 
     #[async_trait]
@@ -93,10 +233,21 @@ mod tests {
             self.mock_available
         }
 
-        async fn complete(&self, _request: &CompletionRequest) -> Result<CompletionResponse> {
+        async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+            if self.mock_fails {
+                return Err(ProviderError::RateLimited {
+                    provider: self.mock_name.to_string(),
+                    retry_after: None,
+                    message: "slow down".to_string(),
+                }
+                .into());
+            }
             Ok(CompletionResponse {
                 content: format!("Response from {}", self.mock_name),
                 usage: None,
+                provider: None,
+                model: request.model.clone(),
+                cached: false,
             })
         }
     }
@@ -184,5 +335,87 @@ mod tests {
         let result = registry.get_provider("auto").await;
         assert!(result.is_err());
     }
-}
 
+    fn request() -> CompletionRequest {
+        CompletionRequest::new("hi")
+    }
+
+    #[tokio::test]
+    async fn test_fallback_moves_to_next_provider_on_failure() {
+        let mut registry = AiRegistry::new();
+        registry.register(MockProvider::failing("claude"));
+        registry.register(MockProvider::new("openai", 80, false));
+        registry.register(MockProvider::new("ollama", 30, true));
+        registry.set_fallback(vec!["claude".into(), "openai".into(), "ollama".into()]);
+
+        let provider = registry.get_provider("claude").await.unwrap();
+        assert_eq!(provider.label(), "claude → openai → ollama");
+
+        let response = provider.complete(&request()).await.unwrap();
+        assert_eq!(response.content, "Response from ollama");
+        assert_eq!(response.provider.as_deref(), Some("ollama"));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_drops_the_primarys_model() {
+        let mut registry = AiRegistry::new();
+        registry.register(MockProvider::failing("claude"));
+        registry.register(MockProvider::new("ollama", 30, true));
+        registry.set_fallback(vec!["ollama".into()]);
+
+        // A route picked a Claude model; ollama must not be asked for it
+        let routed = CompletionRequest { model: Some("claude-opus-4-1".into()), ..request() };
+        let response = registry.get_provider("claude").await.unwrap().complete(&routed).await.unwrap();
+        assert_eq!(response.provider.as_deref(), Some("ollama"));
+        assert_eq!(response.model, None);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_reports_every_failure() {
+        let mut registry = AiRegistry::new();
+        registry.register(MockProvider::failing("claude"));
+        registry.register(MockProvider::failing("openai"));
+        registry.set_fallback(vec!["openai".into()]);
+
+        let err = registry.get_provider("claude").await.unwrap().complete(&request()).await.unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("claude:") && message.contains("openai:"), "{}", message);
+        assert!(err.downcast_ref::<ProviderError>().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_fallback_validates_names_and_skips_lone_chain() {
+        let mut registry = AiRegistry::new();
+        registry.register(MockProvider::new("claude", 90, true));
+
+        registry.set_fallback(vec!["claude".into()]);
+        assert_eq!(registry.get_provider("claude").await.unwrap().name(), "claude");
+
+        registry.set_fallback(vec!["nonexistent".into()]);
+        assert!(registry.get_provider("claude").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_profiles_register_under_their_names() {
+        let profile = |base_url: &str| process_config::config::ProfileConfig {
+            kind: ProfileKind::OpenaiCompatible,
+            base_url: base_url.to_string(),
+            api_key_env: None,
+            model: Some("qwen".into()),
+            headers: Default::default(),
+            max_tokens: None,
+            timeout_secs: None,
+            max_retries: None,
+        };
+        let mut config = Config::default();
+        config.ai.profiles.insert("local-vllm".into(), profile("http://localhost:8000/v1"));
+        config.ai.profiles.insert("openai".into(), profile("http://localhost:9000/v1"));
+        config.ai.fallback = vec!["local-vllm".into()];
+
+        let registry = AiRegistry::from_config(&config);
+        let provider = registry.get_provider("local-vllm").await.unwrap();
+        assert_eq!((provider.name(), provider.model()), ("local-vllm", Some("qwen".to_string())));
+        // A profile can't shadow a built-in provider
+        assert!(registry.named("openai").unwrap().model().is_some_and(|m| m != "qwen"));
+    }
+}
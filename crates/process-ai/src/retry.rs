@@ -0,0 +1,254 @@
+//! Shared request policy for HTTP providers: a timeout, and retries with
+//! exponential backoff and jitter for rate limits, overloads, server errors
+//! and timeouts. A `retry-after` from the server takes precedence over the
+//! computed backoff.
+//!
+//! Only sending the request is retried. Once a streamed body has started,
+//! text has already been shown, so a failure mid-stream is returned as is.
+
+use crate::error::ProviderError;
+use crate::notice::{self, Notice};
+use anyhow::{Context, Result};
+use process_config::config::{ProviderConfig, SettingsConfig};
+use reqwest::{RequestBuilder, Response};
+use serde_json::Value;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Longest `retry-after` we wait for; beyond it the error is returned so
+/// the user can decide (a daily quota won't clear in a few seconds)
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone)]
+pub struct RequestPolicy {
+    /// Wait for the response to start, for a non-streamed body, and between
+    /// streamed chunks
+    pub timeout: Duration,
+    /// Attempts after the first
+    pub max_retries: u32,
+    /// Backoff before the first retry; doubles each attempt
+    pub base_delay: Duration,
+    /// Cap on the computed backoff
+    pub max_delay: Duration,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(120),
+            max_retries: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RequestPolicy {
+    /// `settings`, with the provider's own `timeout_secs`/`max_retries` on top
+    pub fn from_config(settings: &SettingsConfig, provider: Option<&ProviderConfig>) -> Self {
+        Self {
+            timeout: Duration::from_secs(provider.and_then(|p| p.timeout_secs).unwrap_or(settings.timeout_secs)),
+            max_retries: provider.and_then(|p| p.max_retries).unwrap_or(settings.max_retries),
+            ..Self::default()
+        }
+    }
+
+    /// Backoff before retry number `attempt` (0-based): exponential,
+    /// capped, scaled by a random factor in [0.5, 1) so clients that failed
+    /// together don't retry together
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exp = self.base_delay.saturating_mul(2u32.saturating_pow(attempt));
+        exp.min(self.max_delay).mul_f64(0.5 + jitter() * 0.5)
+    }
+
+    /// Send the request built by `build`, retrying retryable failures.
+    /// Returns the first successful response, or the last error as a
+    /// `ProviderError`.
+    pub async fn send(&self, provider: &str, build: impl Fn() -> RequestBuilder) -> Result<Response> {
+        let mut attempt = 0;
+        loop {
+            let err = match self.send_once(provider, build()).await {
+                Ok(response) => return Ok(response),
+                Err(err) => err,
+            };
+
+            let delay = err.retry_after().unwrap_or_else(|| self.backoff(attempt));
+            if !err.is_retryable() || attempt >= self.max_retries || delay > MAX_RETRY_AFTER {
+                return Err(err.into());
+            }
+            attempt += 1;
+            notice::emit(Notice::Retrying {
+                error: err.to_string(),
+                delay,
+                attempt,
+                max_retries: self.max_retries,
+            });
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Read a successful response's JSON body, under the same timeout as
+    /// the send: a server can start a response and then stall
+    pub async fn read_json(&self, provider: &str, response: Response) -> Result<Value> {
+        match tokio::time::timeout(self.timeout, response.json()).await {
+            Err(_) => Err(ProviderError::Timeout { provider: provider.to_string(), timeout: self.timeout }.into()),
+            Ok(Err(e)) if e.is_timeout() => {
+                Err(ProviderError::Timeout { provider: provider.to_string(), timeout: self.timeout }.into())
+            }
+            Ok(body) => body.context("Failed to parse JSON response"),
+        }
+    }
+
+    async fn send_once(&self, provider: &str, request: RequestBuilder) -> Result<Response, ProviderError> {
+        let response = match tokio::time::timeout(self.timeout, request.send()).await {
+            Err(_) => return Err(ProviderError::Timeout { provider: provider.to_string(), timeout: self.timeout }),
+            Ok(Err(e)) if e.is_timeout() => {
+                return Err(ProviderError::Timeout { provider: provider.to_string(), timeout: self.timeout })
+            }
+            Ok(Err(e)) => return Err(ProviderError::Network { provider: provider.to_string(), message: e.to_string() }),
+            Ok(Ok(response)) => response,
+        };
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let retry_after = retry_after(response.headers());
+        let body = response.text().await.unwrap_or_default();
+        Err(ProviderError::from_status(provider, status.as_u16(), retry_after, &body))
+    }
+}
+
+/// `retry-after-ms` (OpenAI) or `retry-after` in seconds. The HTTP-date
+/// form is ignored in favour of our own backoff.
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<f64>().ok();
+    header("retry-after-ms")
+        .map(|ms| ms / 1000.0)
+        .or_else(|| header("retry-after"))
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(Duration::from_secs_f64)
+}
+
+/// Uniform-ish value in [0, 1) from the std hasher's random keys
+fn jitter() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_nanos());
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock_server::{MockResponse, MockServer};
+
+    fn fast_policy(max_retries: u32) -> RequestPolicy {
+        RequestPolicy {
+            timeout: Duration::from_secs(5),
+            max_retries,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        }
+    }
+
+    fn with_headers(mut response: MockResponse, headers: &[(&str, &str)]) -> MockResponse {
+        response.headers.extend(headers.iter().map(|(k, v)| (k.to_string(), v.to_string())));
+        response
+    }
+
+    #[test]
+    fn test_backoff_grows_and_is_capped() {
+        let policy = RequestPolicy::default();
+        for attempt in 0..8 {
+            let delay = policy.backoff(attempt);
+            let full = (policy.base_delay * 2u32.pow(attempt)).min(policy.max_delay);
+            assert!(delay >= full / 2 && delay < full, "attempt {}: {:?}", attempt, delay);
+        }
+    }
+
+    #[test]
+    fn test_provider_settings_override_global() {
+        let settings = SettingsConfig { auto_save: true, timeout_secs: 120, max_retries: 3 };
+        let provider = ProviderConfig { timeout_secs: Some(600), ..Default::default() };
+        let policy = RequestPolicy::from_config(&settings, Some(&provider));
+        assert_eq!(policy.timeout, Duration::from_secs(600));
+        assert_eq!(policy.max_retries, 3);
+    }
+
+    #[tokio::test]
+    async fn test_retries_rate_limit_honouring_retry_after() {
+        let server = MockServer::start(vec![
+            with_headers(MockResponse::json(429, r#"{"error":{"message":"slow down"}}"#), &[("retry-after", "0")]),
+            MockResponse::json(529, r#"{"error":{"message":"overloaded"}}"#),
+            MockResponse::json(200, "{}"),
+        ]).await;
+
+        let client = reqwest::Client::new();
+        let response = fast_policy(3).send("claude", || client.post(&server.url)).await.unwrap();
+        assert!(response.status().is_success());
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_auth_error_is_not_retried() {
+        let server = MockServer::start(vec![MockResponse::json(401, r#"{"error":{"message":"invalid x-api-key"}}"#)]).await;
+
+        let client = reqwest::Client::new();
+        let err = fast_policy(3).send("claude", || client.post(&server.url)).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<ProviderError>(), Some(ProviderError::Auth { status: 401, .. })));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let server = MockServer::start(vec![MockResponse::json(503, "unavailable")]).await;
+
+        let client = reqwest::Client::new();
+        let err = fast_policy(2).send("openai", || client.post(&server.url)).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<ProviderError>(), Some(ProviderError::Overloaded { .. })));
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_slow_response_times_out() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        // Accept connections but never answer
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                held.push(socket);
+            }
+        });
+
+        let policy = RequestPolicy { timeout: Duration::from_millis(50), ..fast_policy(0) };
+        let client = reqwest::Client::new();
+        let err = policy.send("ollama", || client.post(&url)).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<ProviderError>(), Some(ProviderError::Timeout { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_stalled_body_times_out() {
+        use tokio::io::AsyncWriteExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        // Send the headers and part of the body, then stop
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let _ = socket
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 100\r\n\r\n{\"mess")
+                    .await;
+                held.push(socket);
+            }
+        });
+
+        let policy = RequestPolicy { timeout: Duration::from_millis(100), ..fast_policy(0) };
+        let client = reqwest::Client::new();
+        let response = policy.send("ollama", || client.post(&url)).await.unwrap();
+        let err = policy.read_json("ollama", response).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<ProviderError>(), Some(ProviderError::Timeout { .. })));
+    }
+}
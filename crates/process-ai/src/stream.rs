@@ -0,0 +1,145 @@
+//! Incremental parsing of streamed HTTP completions: server-sent events
+//! (Anthropic, OpenAI) and newline-delimited JSON (Ollama).
+
+use crate::error::ProviderError;
+use anyhow::{anyhow, Context, Result};
+use reqwest::Response;
+use std::time::Duration;
+
+/// Splits a byte stream into lines. Bytes are buffered until a newline
+/// arrives, so multi-byte characters split across network chunks survive.
+#[derive(Default)]
+pub struct LineBuffer {
+    pending: Vec<u8>,
+}
+
+impl LineBuffer {
+    /// Add bytes and return every line they complete, without the newline
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.pending.extend_from_slice(bytes);
+        let mut lines = Vec::new();
+        while let Some(pos) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line[..pos]);
+            lines.push(line.strip_suffix('\r').unwrap_or(&line).to_string());
+        }
+        lines
+    }
+
+    /// Whatever is left once the stream ends without a final newline
+    pub fn finish(&mut self) -> Option<String> {
+        let rest = String::from_utf8_lossy(&std::mem::take(&mut self.pending)).trim().to_string();
+        (!rest.is_empty()).then_some(rest)
+    }
+}
+
+/// Assembles server-sent events from lines: `data:` fields are joined and
+/// the event is dispatched at the blank line that ends it.
+#[derive(Default)]
+pub struct SseParser {
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// Feed one line; returns the event's data when the line completes one
+    pub fn line(&mut self, line: &str) -> Option<String> {
+        if line.is_empty() {
+            return self.flush();
+        }
+        if let Some(value) = line.strip_prefix("data:") {
+            self.data.push(value.strip_prefix(' ').unwrap_or(value).to_string());
+        }
+        // `event:`, `id:`, `retry:` and `:` comments carry nothing we need
+        None
+    }
+
+    /// Dispatch a final event the server did not terminate with a blank line
+    pub fn flush(&mut self) -> Option<String> {
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(std::mem::take(&mut self.data).join("\n"))
+    }
+}
+
+/// Read a response body line by line as it arrives. Waiting longer than
+/// `timeout` for the next chunk fails with `ProviderError::Timeout`.
+pub async fn for_each_line(
+    provider: &str,
+    mut response: Response,
+    timeout: Duration,
+    mut on_line: impl FnMut(&str) -> Result<()>,
+) -> Result<()> {
+    let mut buffer = LineBuffer::default();
+    loop {
+        let next = tokio::time::timeout(timeout, response.chunk())
+            .await
+            .map_err(|_| ProviderError::Timeout { provider: provider.to_string(), timeout })?;
+        let Some(bytes) = next.context("Failed to read streamed response")? else {
+            break;
+        };
+        for line in buffer.push(&bytes) {
+            on_line(&line)?;
+        }
+    }
+    if let Some(rest) = buffer.finish() {
+        on_line(&rest)?;
+    }
+    Ok(())
+}
+
+/// Read a server-sent event stream, handing each event's data (usually a
+/// JSON document) to `on_event`
+pub async fn for_each_event(
+    provider: &str,
+    response: Response,
+    timeout: Duration,
+    mut on_event: impl FnMut(&str) -> Result<()>,
+) -> Result<()> {
+    let mut parser = SseParser::default();
+    for_each_line(provider, response, timeout, |line| match parser.line(line) {
+        Some(data) => on_event(&data),
+        None => Ok(()),
+    })
+    .await?;
+    match parser.flush() {
+        Some(data) => on_event(&data),
+        None => Ok(()),
+    }
+}
+
+/// Parse one streamed JSON document
+pub fn parse_json(data: &str) -> Result<serde_json::Value> {
+    serde_json::from_str(data).map_err(|e| anyhow!("Invalid streamed JSON ({}): {}", e, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_buffer_joins_split_lines_and_characters() {
+        let mut buffer = LineBuffer::default();
+        let text = "data: 你好\r\nsecond";
+        let bytes = text.as_bytes();
+        // Split inside the first multi-byte character
+        assert!(buffer.push(&bytes[..7]).is_empty());
+        assert_eq!(buffer.push(&bytes[7..]), vec!["data: 你好"]);
+        assert_eq!(buffer.finish().as_deref(), Some("second"));
+        assert_eq!(buffer.finish(), None);
+    }
+
+    #[test]
+    fn test_sse_parser_dispatches_on_blank_line() {
+        let mut parser = SseParser::default();
+        assert_eq!(parser.line("event: content_block_delta"), None);
+        assert_eq!(parser.line(": keep-alive"), None);
+        assert_eq!(parser.line("data: {\"a\":1}"), None);
+        assert_eq!(parser.line("").as_deref(), Some("{\"a\":1}"));
+        assert_eq!(parser.line(""), None);
+
+        parser.line("data:first");
+        parser.line("data: second");
+        assert_eq!(parser.flush().as_deref(), Some("first\nsecond"));
+    }
+}
@@ -0,0 +1,163 @@
+//! Typed results from schema-constrained completions. The request carries
+//! a `ResponseSchema`; the reply is parsed into `T`, and when that fails the
+//! model is shown its answer and the error and asked again.
+
+use crate::notice::{self, Notice};
+use crate::provider::{AiProvider, ChunkSink, CompletionRequest, Message};
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+
+/// Re-asks after the first answer before giving up
+pub const MAX_REPROMPTS: usize = 2;
+
+/// Parse a reply as `T`. JSON is expected; YAML is accepted too, for
+/// prompt-only providers following a template that asks for YAML, and a
+/// surrounding markdown fence is ignored.
+pub fn parse<T: DeserializeOwned>(content: &str) -> Result<T> {
+    let body = strip_fence(content);
+    match serde_json::from_str(body) {
+        Ok(value) => Ok(value),
+        Err(json_err) => serde_yaml::from_str(body).map_err(|_| json_err).context("Response does not match the schema"),
+    }
+}
+
+/// The request extended with the rejected answer and why it was rejected
+pub fn reprompt(request: &CompletionRequest, content: &str, error: &anyhow::Error) -> CompletionRequest {
+    let mut next = request.clone();
+    next.messages.push(Message::assistant(content));
+    next.messages.push(Message::user(format!(
+        "That response could not be used: {:#}. Reply again with only the JSON object, matching the schema exactly.",
+        error
+    )));
+    next
+}
+
+/// Complete and parse, re-asking up to `MAX_REPROMPTS` times. Every
+/// attempt streams its text to `on_chunk`.
+pub async fn complete_typed<T: DeserializeOwned>(
+    provider: &dyn AiProvider,
+    request: &CompletionRequest,
+    on_chunk: ChunkSink<'_>,
+) -> Result<T> {
+    complete_accepted(provider, request, on_chunk, parse).await
+}
+
+/// Complete until `accept` takes the reply, re-asking up to
+/// `MAX_REPROMPTS` times with the error it returned. `accept` parses and
+/// may check rules the schema alone can't express.
+pub async fn complete_accepted<T>(
+    provider: &dyn AiProvider,
+    request: &CompletionRequest,
+    on_chunk: ChunkSink<'_>,
+    accept: impl Fn(&str) -> Result<T>,
+) -> Result<T> {
+    let mut request = request.clone();
+    let mut attempt = 0;
+    loop {
+        let response = provider.complete_stream(&request, &mut *on_chunk).await?;
+        match accept(&response.content) {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt < MAX_REPROMPTS => {
+                attempt += 1;
+                notice::emit(Notice::Reprompting { error: format!("{:#}", err), attempt, max: MAX_REPROMPTS });
+                request = reprompt(&request, &response.content, &err);
+            }
+            Err(err) => return Err(err.context(format!("Invalid structured output after {} attempts", attempt + 1))),
+        }
+    }
+}
+
+fn strip_fence(content: &str) -> &str {
+    let content = content.trim();
+    let Some(rest) = content.strip_prefix("```") else {
+        return content;
+    };
+    // Drop the language tag line and the closing fence
+    let rest = rest.split_once('\n').map_or("", |(_, body)| body);
+    rest.trim_end().strip_suffix("```").unwrap_or(rest).trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{CompletionResponse, Role};
+    use async_trait::async_trait;
+    use serde::Deserialize;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Verdict {
+        verdict: String,
+        score: u8,
+    }
+
+    /// Replies from a script and keeps the requests it saw
+    struct Scripted {
+        replies: Mutex<Vec<&'static str>>,
+        seen: Mutex<Vec<CompletionRequest>>,
+    }
+
+    #[async_trait]
+    impl AiProvider for Scripted {
+        fn name(&self) -> &'static str { "scripted" }
+        fn priority(&self) -> u8 { 0 }
+        async fn is_available(&self) -> bool { true }
+        async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+            self.seen.lock().unwrap().push(request.clone());
+            let content = self.replies.lock().unwrap().remove(0).to_string();
+            Ok(CompletionResponse { content, usage: None, provider: None, model: None, cached: false })
+        }
+    }
+
+    fn scripted(replies: Vec<&'static str>) -> Scripted {
+        Scripted { replies: Mutex::new(replies), seen: Mutex::new(Vec::new()) }
+    }
+
+    #[test]
+    fn test_parse_accepts_fenced_json_and_yaml() {
+        let expected = Verdict { verdict: "pass".into(), score: 3 };
+        assert_eq!(parse::<Verdict>(r#"{"verdict":"pass","score":3}"#).unwrap(), expected);
+        assert_eq!(parse::<Verdict>("```json\n{\"verdict\":\"pass\",\"score\":3}\n```").unwrap(), expected);
+        assert_eq!(parse::<Verdict>("```yaml\nverdict: pass\nscore: 3\n```").unwrap(), expected);
+        assert!(parse::<Verdict>(r#"{"verdict":"pass"}"#).unwrap_err().root_cause().to_string().contains("score"));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_output_is_reprompted_with_the_error() {
+        let provider = scripted(vec!["Sure! The verdict is pass.", r#"{"verdict":"pass","score":5}"#]);
+        let request = CompletionRequest::new("judge");
+
+        let mut streamed = Vec::new();
+        let verdict: Verdict = complete_typed(&provider, &request, &mut |c: &str| streamed.push(c.to_string())).await.unwrap();
+        assert_eq!(verdict.score, 5);
+        assert_eq!(streamed.len(), 2);
+
+        let seen = provider.seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        let retry = &seen[1].messages;
+        assert_eq!(retry.len(), 3);
+        assert_eq!((retry[1].role, retry[1].content.as_str()), (Role::Assistant, "Sure! The verdict is pass."));
+        assert!(retry[2].content.starts_with("That response could not be used"));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_values_are_reprompted_with_the_reason() {
+        let provider = scripted(vec![r#"{"verdict":"pass","score":11}"#, r#"{"verdict":"pass","score":9}"#]);
+        let accept = |content: &str| {
+            let verdict: Verdict = parse(content)?;
+            anyhow::ensure!(verdict.score <= 10, "score {} is above 10", verdict.score);
+            Ok(verdict)
+        };
+
+        let verdict = complete_accepted(&provider, &CompletionRequest::new("judge"), &mut |_: &str| {}, accept).await.unwrap();
+        assert_eq!(verdict.score, 9);
+        assert!(provider.seen.lock().unwrap()[1].messages[2].content.contains("score 11 is above 10"));
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_reprompts() {
+        let provider = scripted(vec!["no", "still no", "never"]);
+        let err = complete_typed::<Verdict>(&provider, &CompletionRequest::new("judge"), &mut |_: &str| {}).await.unwrap_err();
+        assert!(err.to_string().contains("after 3 attempts"), "{}", err);
+    }
+}
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use config::{Config as ConfigLoader, File, Environment};
 use anyhow::{Result, Context};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::Path;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -15,20 +16,181 @@ pub struct AiConfig {
     pub claude: Option<ProviderConfig>,
     pub openai: Option<ProviderConfig>,
     pub ollama: Option<ProviderConfig>,
+    pub gemini: Option<ProviderConfig>,
+    /// `base_url` is the resource endpoint and `model` the deployment name
+    pub azure_openai: Option<ProviderConfig>,
+    /// Named endpoints, each registered as a provider under its name
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileConfig>,
+    /// Provider, model and limits per pass or prompt template
+    #[serde(default)]
+    pub routes: Vec<RouteRule>,
+    /// Providers tried in order when `provider` fails, e.g.
+    /// `[claude-cli, claude, openai, ollama]`
+    #[serde(default)]
+    pub fallback: Vec<String>,
+    /// Prices used to estimate the cost of each call in the usage ledger
+    #[serde(default)]
+    pub prices: Vec<ModelPrice>,
+    /// Reuse stored responses for identical requests (`.process/cache/ai/`)
+    #[serde(default)]
+    pub cache: bool,
+    /// Fixtures for the `replay` provider, and recording into them
+    #[serde(default)]
+    pub replay: Option<ReplayConfig>,
 }
 
+impl AiConfig {
+    /// The price for a model: an exact match, else the longest entry the
+    /// model name starts with (`claude-sonnet-4-5` covers its dated releases)
+    pub fn price_for(&self, model: &str) -> Option<&ModelPrice> {
+        self.prices
+            .iter()
+            .filter(|p| model.starts_with(&p.model))
+            .max_by_key(|p| p.model.len())
+    }
+
+    /// The route for a completion made by `pass` from `template`. Rules
+    /// are checked in order and each setting comes from the first matching
+    /// rule that sets it, so specific rules go before general ones.
+    pub fn route(&self, pass: &str, template: Option<&str>) -> Route {
+        let mut route = Route::default();
+        let matching = self
+            .routes
+            .iter()
+            .filter(|rule| rule.matches(pass) || template.is_some_and(|t| rule.matches(t)));
+        for rule in matching {
+            route.provider = route.provider.or_else(|| rule.provider.clone());
+            route.model = route.model.or_else(|| rule.model.clone());
+            route.max_tokens = route.max_tokens.or(rule.max_tokens);
+            route.temperature = route.temperature.or(rule.temperature);
+        }
+        route
+    }
+}
+
+/// A named provider endpoint (`ai.profiles.<name>`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileConfig {
+    pub kind: ProfileKind,
+    pub base_url: String,
+    /// Environment variable holding the API key; unset for local servers
+    pub api_key_env: Option<String>,
+    pub model: Option<String>,
+    /// Sent with every request, e.g. OpenRouter's `HTTP-Referer`
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    pub max_tokens: Option<usize>,
+    /// Overrides `settings.timeout_secs` for this profile
+    pub timeout_secs: Option<u64>,
+    /// Overrides `settings.max_retries` for this profile
+    pub max_retries: Option<u32>,
+}
+
+impl ProfileConfig {
+    /// The settings shared with built-in providers (model, limits, retries)
+    pub fn provider_config(&self) -> ProviderConfig {
+        ProviderConfig {
+            model: self.model.clone(),
+            base_url: Some(self.base_url.clone()),
+            max_tokens: self.max_tokens,
+            timeout_secs: self.timeout_secs,
+            max_retries: self.max_retries,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileKind {
+    /// A Chat Completions API at `base_url` (LM Studio, vLLM, llama.cpp,
+    /// OpenRouter, ...)
+    OpenaiCompatible,
+}
+
+/// Settings for the completions a rule matches (`ai.routes`)
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteRule {
+    /// A pass or command (`adopt.infer_conventions`, `branch.review`) or a
+    /// prompt template (`review.security`); a trailing `*` matches by prefix
+    #[serde(rename = "match")]
+    pub pattern: String,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub max_tokens: Option<usize>,
+    pub temperature: Option<f32>,
+}
+
+impl RouteRule {
+    fn matches(&self, name: &str) -> bool {
+        match self.pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == self.pattern,
+        }
+    }
+}
+
+/// What routing chose for one completion; unset fields keep the defaults
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Route {
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub max_tokens: Option<usize>,
+    pub temperature: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayConfig {
+    /// Fixture directory, relative to the project root
+    pub dir: String,
+    /// Save the configured provider's responses to `dir` as they arrive
+    #[serde(default)]
+    pub record: bool,
+}
+
+/// USD per million tokens for one model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub model: String,
+    /// Per million prompt (input) tokens
+    pub input: f64,
+    /// Per million completion (output) tokens
+    pub output: f64,
+}
+
+impl ModelPrice {
+    pub fn cost(&self, prompt_tokens: usize, completion_tokens: usize) -> f64 {
+        (prompt_tokens as f64 * self.input + completion_tokens as f64 * self.output) / 1_000_000.0
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ProviderConfig {
     pub api_key: Option<String>,
     pub model: Option<String>,
     pub base_url: Option<String>,
     pub max_tokens: Option<usize>,
+    /// Overrides `settings.timeout_secs` for this provider
+    pub timeout_secs: Option<u64>,
+    /// Overrides `settings.max_retries` for this provider
+    pub max_retries: Option<u32>,
+    /// Azure OpenAI only: the `api-version` query parameter
+    pub api_version: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SettingsConfig {
     pub auto_save: bool,
+    /// Seconds to wait for an AI response to start, and between streamed chunks
     pub timeout_secs: u64,
+    /// Retries after a rate limit, overload, server error or timeout
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+}
+
+fn default_max_retries() -> u32 {
+    3
 }
 
 impl Default for Config {
@@ -39,17 +201,28 @@ impl Default for Config {
                 claude: None,
                 openai: None,
                 ollama: None,
+                gemini: None,
+                azure_openai: None,
+                profiles: BTreeMap::new(),
+                routes: Vec::new(),
+                fallback: Vec::new(),
+                prices: Vec::new(),
+                cache: false,
+                replay: None,
             },
             settings: SettingsConfig {
                 auto_save: true,
                 timeout_secs: 120,
+                max_retries: default_max_retries(),
             },
         }
     }
 }
 
 impl Config {
-    pub fn load() -> Result<Self> {
+    /// Defaults, then the global config, then `<project_root>/.process/config.yaml`,
+    /// then `PROCESS_CLI__*` environment variables
+    pub fn load(project_root: &Path) -> Result<Self> {
         let builder = ConfigLoader::builder();
 
         // 1. Start with default values
@@ -64,7 +237,8 @@ impl Config {
         let mut builder = builder
             .set_default("ai.provider", defaults.ai.provider)?
             .set_default("settings.auto_save", defaults.settings.auto_save)?
-            .set_default("settings.timeout_secs", defaults.settings.timeout_secs)?;
+            .set_default("settings.timeout_secs", defaults.settings.timeout_secs)?
+            .set_default("settings.max_retries", defaults.settings.max_retries)?;
 
         // 2. Global Config: ~/.config/process-cli/config.yaml
         if let Some(home_dir) = dirs::home_dir() {
@@ -75,7 +249,7 @@ impl Config {
         }
 
         // 3. Project Config: .process/config.yaml
-        let project_path = PathBuf::from(".process/config.yaml");
+        let project_path = project_root.join(".process/config.yaml");
         if project_path.exists() {
              builder = builder.add_source(File::from(project_path).required(false));
         }
@@ -111,12 +285,14 @@ mod tests {
         assert!(config.ai.ollama.is_none());
         assert!(config.settings.auto_save);
         assert_eq!(config.settings.timeout_secs, 120);
+        assert_eq!(config.settings.max_retries, 3);
     }
 
     #[test]
     fn test_load_returns_defaults_when_no_files() {
         // When no config files exist, should use defaults
-        let config = Config::load().unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        let config = Config::load(tmp.path()).unwrap();
         assert_eq!(config.ai.provider, "auto");
         assert!(config.settings.auto_save);
     }
@@ -128,9 +304,76 @@ mod tests {
             model: Some("gpt-4o".to_string()),
             base_url: Some("https://api.example.com".to_string()),
             max_tokens: Some(8192),
+            ..Default::default()
         };
         assert_eq!(pc.api_key.unwrap(), "test-key");
         assert_eq!(pc.max_tokens.unwrap(), 8192);
     }
+
+    #[test]
+    fn test_price_for_prefers_longest_prefix() {
+        let price = |model: &str, input| ModelPrice { model: model.to_string(), input, output: 15.0 };
+        let mut ai = Config::default().ai;
+        ai.prices = vec![price("claude", 1.0), price("claude-sonnet-4-5", 3.0)];
+
+        assert_eq!(ai.price_for("claude-sonnet-4-5-20250929").unwrap().input, 3.0);
+        assert_eq!(ai.price_for("claude-haiku-4-5").unwrap().input, 1.0);
+        assert!(ai.price_for("gpt-4o").is_none());
+        assert_eq!(price("m", 3.0).cost(1_000_000, 200_000), 6.0);
+    }
+
+    #[test]
+    fn test_load_reads_price_table() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+        std::fs::write(
+            tmp.path().join(".process/config.yaml"),
+            "ai:\n  prices:\n    - { model: gpt-4.1, input: 2.0, output: 8.0 }\n",
+        )
+        .unwrap();
+        let config = Config::load(tmp.path()).unwrap();
+        assert_eq!(config.ai.price_for("gpt-4.1-mini").unwrap().output, 8.0);
+    }
+
+    #[test]
+    fn test_route_takes_each_setting_from_first_matching_rule() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+        std::fs::write(
+            tmp.path().join(".process/config.yaml"),
+            "ai:\n  routes:\n    - { match: review.security, provider: claude, model: claude-opus-4-1 }\n    - { match: adopt.infer_conventions, provider: ollama }\n    - { match: \"branch.*\", max_tokens: 2048, temperature: 0.2 }\n",
+        )
+        .unwrap();
+        let ai = Config::load(tmp.path()).unwrap().ai;
+
+        let security = ai.route("branch.review", Some("review.security"));
+        assert_eq!(security.provider.as_deref(), Some("claude"));
+        assert_eq!(security.model.as_deref(), Some("claude-opus-4-1"));
+        assert_eq!((security.max_tokens, security.temperature), (Some(2048), Some(0.2)));
+
+        let general = ai.route("branch.review", Some("review.general"));
+        assert_eq!((general.provider, general.max_tokens), (None, Some(2048)));
+        assert_eq!(ai.route("adopt.infer_conventions", Some("adopt_infer_conventions")).provider.as_deref(), Some("ollama"));
+        assert_eq!(ai.route("diverge.generate", Some("diverge")), Route::default());
+    }
+
+    #[test]
+    fn test_load_reads_profiles() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+        std::fs::write(
+            tmp.path().join(".process/config.yaml"),
+            "ai:\n  profiles:\n    local-vllm:\n      kind: openai_compatible\n      base_url: http://localhost:8000/v1\n      model: qwen\n      timeout_secs: 600\n    openrouter:\n      kind: openai_compatible\n      base_url: https://openrouter.ai/api/v1\n      api_key_env: OPENROUTER_API_KEY\n      headers:\n        X-Title: process-cli\n",
+        )
+        .unwrap();
+        let config = Config::load(tmp.path()).unwrap();
+        let vllm = &config.ai.profiles["local-vllm"];
+        assert_eq!(vllm.kind, ProfileKind::OpenaiCompatible);
+        assert!(vllm.api_key_env.is_none());
+        assert_eq!(vllm.provider_config().timeout_secs, Some(600));
+        let openrouter = &config.ai.profiles["openrouter"];
+        assert_eq!(openrouter.api_key_env.as_deref(), Some("OPENROUTER_API_KEY"));
+        assert_eq!(openrouter.headers.len(), 1);
+    }
 }
 
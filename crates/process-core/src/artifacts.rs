@@ -0,0 +1,332 @@
+//! Artifact registry: the one place that knows where each artifact lives
+//! under `.process/`. Passes and commands both resolve paths here.
+//!
+//! Earlier versions disagreed about some locations (`converge_summary.yaml`
+//! vs `rules.yaml`, flat `branches/<name>-review.yaml` files); `migrate`
+//! moves such files to the canonical layout.
+
+use crate::manifest::Manifest;
+use crate::pass::ArtifactKind;
+use crate::phase::Phase;
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Directory holding all process artifacts, relative to the project root
+pub const PROCESS_DIR: &str = ".process";
+
+/// Artifacts with a fixed location, in the order phases produce them
+pub const PROJECT_ARTIFACTS: [ArtifactKind; 8] = [
+    ArtifactKind::Seed,
+    ArtifactKind::Proposals,
+    ArtifactKind::Rules,
+    ArtifactKind::Skeleton,
+    ArtifactKind::DecisionLog,
+    ArtifactKind::Learnings,
+    ArtifactKind::Friction,
+    ArtifactKind::Postmortem,
+];
+
+/// The nearest directory at or above `start` that contains `.process/`,
+/// the way git finds `.git/`
+pub fn find_project_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(PROCESS_DIR).is_dir())
+        .map(Path::to_path_buf)
+}
+
+/// Path of an artifact relative to `.process/`
+pub fn relative_path(kind: &ArtifactKind) -> String {
+    match kind {
+        ArtifactKind::Seed => "seed.yaml".to_string(),
+        ArtifactKind::Proposals => "diverge_summary.yaml".to_string(),
+        ArtifactKind::Rules => "rules.yaml".to_string(),
+        ArtifactKind::Skeleton => "skeleton.yaml".to_string(),
+        ArtifactKind::DecisionLog => "decisions_log.yaml".to_string(),
+        ArtifactKind::Learnings => "learnings.yaml".to_string(),
+        ArtifactKind::Friction => "friction.yaml".to_string(),
+        ArtifactKind::BranchHypothesis(name) => format!("branches/{}/hypothesis.yaml", name),
+        ArtifactKind::BranchReview(name) => format!("branches/{}/review.yaml", name),
+        ArtifactKind::BranchAbuse(name) => format!("branches/{}/abuse.yaml", name),
+        ArtifactKind::BranchImplementation(name) => format!("branches/{}/implement.yaml", name),
+        ArtifactKind::Postmortem => "postmortem.yaml".to_string(),
+        ArtifactKind::Custom(name) => format!("{}.yaml", name),
+    }
+}
+
+/// The phase whose work produces an artifact; cross-phase records
+/// (decision log, learnings) and custom artifacts belong to none
+pub fn phase_of(kind: &ArtifactKind) -> Option<Phase> {
+    match kind {
+        ArtifactKind::Seed => Some(Phase::Seed),
+        ArtifactKind::Proposals => Some(Phase::Diverge),
+        ArtifactKind::Rules => Some(Phase::Converge),
+        ArtifactKind::Skeleton => Some(Phase::Skeleton),
+        ArtifactKind::BranchHypothesis(_)
+        | ArtifactKind::BranchReview(_)
+        | ArtifactKind::BranchAbuse(_)
+        | ArtifactKind::BranchImplementation(_)
+        | ArtifactKind::Friction => Some(Phase::Branching),
+        ArtifactKind::Postmortem => Some(Phase::Postmortem),
+        ArtifactKind::DecisionLog | ArtifactKind::Learnings | ArtifactKind::Custom(_) => None,
+    }
+}
+
+/// Path of an artifact under a project root
+pub fn path(project_root: &Path, kind: &ArtifactKind) -> PathBuf {
+    project_root.join(PROCESS_DIR).join(relative_path(kind))
+}
+
+/// Write a file under `.process/` atomically: the content goes to a
+/// temporary file in the same directory, which is then renamed over `path`.
+/// Readers see the old or the new version, never a partial write.
+pub fn write(path: &Path, content: impl AsRef<[u8]>) -> Result<()> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    std::fs::create_dir_all(dir)?;
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = dir.join(format!(".{}.{}-{}.tmp", name, std::process::id(), COUNTER.fetch_add(1, Ordering::Relaxed)));
+
+    let result = (|| {
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(content.as_ref())?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, path)
+    })();
+    if result.is_err() {
+        std::fs::remove_file(&tmp).ok();
+    }
+    result.with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Directory holding one subdirectory per branch
+pub fn branches_dir(project_root: &Path) -> PathBuf {
+    project_root.join(PROCESS_DIR).join("branches")
+}
+
+/// Names of all branches with a hypothesis, sorted
+pub fn branch_names(project_root: &Path) -> Result<Vec<String>> {
+    let dir = branches_dir(project_root);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut names = Vec::new();
+    for entry in std::fs::read_dir(&dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path(project_root, &ArtifactKind::BranchHypothesis(name.clone())).exists() {
+            names.push(name);
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// Files moved (or left alone) by `migrate`, as `(from, to)` pairs
+#[derive(Debug, Default)]
+pub struct Migration {
+    pub moved: Vec<(PathBuf, PathBuf)>,
+    /// Both the legacy and the canonical file exist; the legacy one is kept
+    /// for the user to merge by hand
+    pub conflicts: Vec<(PathBuf, PathBuf)>,
+}
+
+impl Migration {
+    pub fn is_empty(&self) -> bool {
+        self.moved.is_empty() && self.conflicts.is_empty()
+    }
+}
+
+/// Legacy top-level filenames and the artifact that now owns them
+const LEGACY_FILES: [(&str, ArtifactKind); 3] = [
+    ("converge_summary.yaml", ArtifactKind::Rules),
+    ("proposals.yaml", ArtifactKind::Proposals),
+    ("decision_log.yaml", ArtifactKind::DecisionLog),
+];
+
+/// The branch artifact a flat `branches/<file>.yaml` used to hold. A
+/// `-review`/`-abuse`/`-implement` suffix only names a companion file when
+/// the branch it strips down to has a hypothesis, either still flat or
+/// already migrated; otherwise the suffix is part of the branch name.
+fn legacy_branch_file(project_root: &Path, stem: &str) -> ArtifactKind {
+    let branch = |suffix: &str| {
+        stem.strip_suffix(suffix)
+            .filter(|name| {
+                branches_dir(project_root).join(format!("{}.yaml", name)).is_file()
+                    || path(project_root, &ArtifactKind::BranchHypothesis(name.to_string())).is_file()
+            })
+            .map(str::to_string)
+    };
+    if let Some(name) = branch("-review") {
+        ArtifactKind::BranchReview(name)
+    } else if let Some(name) = branch("-abuse") {
+        ArtifactKind::BranchAbuse(name)
+    } else if let Some(name) = branch("-implement") {
+        ArtifactKind::BranchImplementation(name)
+    } else {
+        ArtifactKind::BranchHypothesis(stem.to_string())
+    }
+}
+
+/// Move artifacts from older layouts to their canonical paths and update
+/// the manifest to match. Safe to run repeatedly.
+pub fn migrate(project_root: &Path) -> Result<Migration> {
+    let process_dir = project_root.join(PROCESS_DIR);
+    let mut moves: Vec<(PathBuf, ArtifactKind)> = LEGACY_FILES
+        .iter()
+        .map(|(file, kind)| (process_dir.join(file), kind.clone()))
+        .collect();
+
+    let branches = branches_dir(project_root);
+    if branches.is_dir() {
+        for entry in std::fs::read_dir(&branches)? {
+            let file = entry?.path();
+            if file.is_file() && file.extension().is_some_and(|e| e == "yaml") {
+                let stem = file.file_stem().unwrap_or_default().to_string_lossy().to_string();
+                moves.push((file, legacy_branch_file(project_root, &stem)));
+            }
+        }
+    }
+
+    let mut migration = Migration::default();
+    let mut renamed = Vec::new();
+    for (from, kind) in moves {
+        if !from.exists() {
+            continue;
+        }
+        let to = path(project_root, &kind);
+        if to.exists() {
+            migration.conflicts.push((from, to));
+            continue;
+        }
+        if let Some(parent) = to.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::rename(&from, &to)
+            .with_context(|| format!("Failed to move {} to {}", from.display(), to.display()))?;
+
+        let old_relative = from.strip_prefix(&process_dir).unwrap_or(&from).to_string_lossy().replace('\\', "/");
+        renamed.push((old_relative, kind));
+        migration.moved.push((from, to));
+    }
+
+    if !renamed.is_empty() && process_dir.join("manifest.yaml").exists() {
+        let mut manifest = Manifest::load(project_root)?;
+        for record in manifest.artifacts.values_mut() {
+            if let Some((_, kind)) = renamed.iter().find(|(old, _)| *old == record.path) {
+                record.path = relative_path(kind);
+            }
+        }
+        manifest.save(project_root)?;
+    }
+
+    Ok(migration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_artifact_kind_has_a_distinct_path() {
+        let mut kinds = PROJECT_ARTIFACTS.to_vec();
+        kinds.extend([
+            ArtifactKind::BranchHypothesis("b".into()),
+            ArtifactKind::BranchReview("b".into()),
+            ArtifactKind::BranchAbuse("b".into()),
+            ArtifactKind::BranchImplementation("b".into()),
+            ArtifactKind::Custom("gap-report".into()),
+        ]);
+        let mut paths: Vec<String> = kinds.iter().map(relative_path).collect();
+        paths.sort();
+        paths.dedup();
+        assert_eq!(paths.len(), kinds.len());
+    }
+
+    #[test]
+    fn test_find_project_root_searches_upward() {
+        let tmp = tempfile::tempdir().unwrap();
+        let nested = tmp.path().join("src/deep/er");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_project_root(&nested), None);
+
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+        assert_eq!(find_project_root(&nested).as_deref(), Some(tmp.path()));
+        assert_eq!(find_project_root(tmp.path()).as_deref(), Some(tmp.path()));
+    }
+
+    #[test]
+    fn test_write_replaces_file_without_leaving_temp_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join(".process/branches/cache/review.yaml");
+        write(&file, "v1").unwrap();
+        write(&file, "v2").unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v2");
+        assert_eq!(std::fs::read_dir(file.parent().unwrap()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_migrate_moves_legacy_layout() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join(".process");
+        std::fs::create_dir_all(dir.join("branches")).unwrap();
+        std::fs::write(dir.join("converge_summary.yaml"), "rules").unwrap();
+        std::fs::write(dir.join("branches/cache.yaml"), "hypothesis").unwrap();
+        std::fs::write(dir.join("branches/cache-review.yaml"), "review").unwrap();
+        std::fs::write(dir.join("branches/cache-abuse.yaml"), "abuse").unwrap();
+        let mut manifest = Manifest::default();
+        manifest.record_artifact("rules", "converge.analyze", "converge_summary.yaml", "rules", Default::default());
+        manifest.save(tmp.path()).unwrap();
+
+        let migration = migrate(tmp.path()).unwrap();
+        assert_eq!(migration.moved.len(), 4);
+        assert!(migration.conflicts.is_empty());
+
+        assert_eq!(std::fs::read_to_string(dir.join("rules.yaml")).unwrap(), "rules");
+        assert!(!dir.join("converge_summary.yaml").exists());
+        assert_eq!(std::fs::read_to_string(dir.join("branches/cache/review.yaml")).unwrap(), "review");
+        assert_eq!(branch_names(tmp.path()).unwrap(), ["cache"]);
+        assert_eq!(Manifest::load(tmp.path()).unwrap().artifacts["rules"].path, "rules.yaml");
+
+        // Running again is a no-op
+        assert!(migrate(tmp.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_migrate_keeps_legacy_file_on_conflict() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join(".process");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("converge_summary.yaml"), "old").unwrap();
+        std::fs::write(dir.join("rules.yaml"), "new").unwrap();
+
+        let migration = migrate(tmp.path()).unwrap();
+        assert_eq!(migration.conflicts.len(), 1);
+        assert_eq!(std::fs::read_to_string(dir.join("rules.yaml")).unwrap(), "new");
+        assert!(dir.join("converge_summary.yaml").exists());
+    }
+
+    #[test]
+    fn test_migrate_keeps_suffix_without_matching_hypothesis() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join(".process");
+        std::fs::create_dir_all(dir.join("branches")).unwrap();
+        std::fs::write(dir.join("branches/code-review.yaml"), "hypothesis").unwrap();
+
+        let migration = migrate(tmp.path()).unwrap();
+        assert_eq!(migration.moved.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("branches/code-review/hypothesis.yaml")).unwrap(),
+            "hypothesis"
+        );
+        assert!(!dir.join("branches/code").exists());
+        assert_eq!(branch_names(tmp.path()).unwrap(), ["code-review"]);
+    }
+}
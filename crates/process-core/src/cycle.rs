@@ -0,0 +1,395 @@
+//! Decision cycles. A project runs through the phase track more than once:
+//! the greenfield cycle that created it, evolution cycles for major
+//! redesigns and a final sunset cycle. Starting a cycle archives the
+//! current one's artifacts and seeds the next from its postmortem, so each
+//! cycle starts from what the last one learned.
+
+use crate::artifacts::{self, PROCESS_DIR};
+use crate::manifest::{CycleRecord, Manifest};
+use crate::pass::ArtifactKind;
+use crate::phase::Phase;
+use crate::schema::Seed;
+use crate::state::ProcessState;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// What a decision cycle is for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CycleType {
+    /// Building the project from scratch
+    #[default]
+    Greenfield,
+    /// A major change or redesign of an existing project
+    Evolution,
+    /// Migration, knowledge archiving and retirement
+    Sunset,
+}
+
+impl fmt::Display for CycleType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CycleType::Greenfield => write!(f, "greenfield"),
+            CycleType::Evolution => write!(f, "evolution"),
+            CycleType::Sunset => write!(f, "sunset"),
+        }
+    }
+}
+
+impl FromStr for CycleType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "greenfield" => Ok(CycleType::Greenfield),
+            "evolution" => Ok(CycleType::Evolution),
+            "sunset" => Ok(CycleType::Sunset),
+            other => Err(anyhow!("Unknown cycle type '{}' (expected greenfield, evolution or sunset)", other)),
+        }
+    }
+}
+
+/// Id of the cycle a project starts in
+pub const INITIAL_CYCLE: &str = "v1-initial";
+
+/// Artifacts that belong to one cycle and move to its archive
+const CYCLE_ARTIFACTS: [ArtifactKind; 6] = [
+    ArtifactKind::Seed,
+    ArtifactKind::Proposals,
+    ArtifactKind::Rules,
+    ArtifactKind::Skeleton,
+    ArtifactKind::Friction,
+    ArtifactKind::Postmortem,
+];
+
+/// Artifacts shared by all cycles; the archive gets a copy
+const SHARED_ARTIFACTS: [ArtifactKind; 2] = [ArtifactKind::DecisionLog, ArtifactKind::Learnings];
+
+/// Directory a completed cycle's artifacts are archived to
+pub fn archive_dir(project_root: &Path, cycle_id: &str) -> PathBuf {
+    project_root.join(PROCESS_DIR).join("archive").join(cycle_id)
+}
+
+/// The result of `start`
+#[derive(Debug)]
+pub struct Transition {
+    /// The cycle that was just completed and archived
+    pub previous: CycleRecord,
+    /// The cycle now active
+    pub next: CycleRecord,
+    /// Lessons carried from the previous postmortem into the new seed
+    pub lessons: usize,
+}
+
+/// Complete the active cycle and start a new one: archive the cycle's
+/// artifacts, write a seed pre-populated from its seed and postmortem,
+/// record the lineage in the manifest and reset `state` to the seed phase.
+/// The caller saves `state`.
+pub fn start(
+    project_root: &Path,
+    state: &mut ProcessState,
+    cycle_type: CycleType,
+    trigger: &str,
+    id: Option<&str>,
+) -> Result<Transition> {
+    let mut manifest = Manifest::load(project_root)?;
+    if manifest.cycles.is_empty() {
+        // Projects from before cycles were tracked: record the implicit first one
+        let started = manifest.artifacts.values().map(|r| r.last_updated.clone()).min();
+        manifest.cycles.push(CycleRecord {
+            id: state.cycle.clone(),
+            cycle_type: state.cycle_type,
+            started: started.unwrap_or_else(|| Utc::now().to_rfc3339()),
+            completed: None,
+            trigger: None,
+            parent: None,
+            artifacts: Vec::new(),
+        });
+    }
+
+    let id = match id {
+        Some(id) => id.to_string(),
+        None => format!("v{}-{}", manifest.cycles.len() + 1, cycle_type),
+    };
+    if id.is_empty() || id.contains(['/', '\\']) || id.starts_with('.') {
+        bail!("Invalid cycle id '{}'", id);
+    }
+    if manifest.cycles.iter().any(|c| c.id == id) {
+        bail!("Cycle '{}' already exists", id);
+    }
+
+    let previous_id = state.cycle.clone();
+    let archive = archive_dir(project_root, &previous_id);
+    if archive.exists() {
+        bail!("{} already exists; refusing to overwrite an archived cycle", archive.display());
+    }
+
+    let seed = std::fs::read_to_string(artifacts::path(project_root, &ArtifactKind::Seed)).ok();
+    let postmortem = std::fs::read_to_string(artifacts::path(project_root, &ArtifactKind::Postmortem)).ok();
+
+    let archived = archive_artifacts(project_root, &archive)?;
+    for name in &archived {
+        manifest.artifacts.remove(name);
+    }
+
+    let lessons = postmortem.as_deref().map(postmortem_lessons).unwrap_or_default();
+    let next_seed = next_seed(seed.as_deref(), &lessons, &previous_id, &id, cycle_type, trigger)?;
+    artifacts::write(&artifacts::path(project_root, &ArtifactKind::Seed), next_seed)
+        .context("Failed to write the new cycle's seed")?;
+
+    let now = Utc::now();
+    let previous = manifest
+        .cycles
+        .iter_mut()
+        .rev()
+        .find(|c| c.id == previous_id && c.completed.is_none())
+        .ok_or_else(|| anyhow!("Active cycle '{}' is not recorded in the manifest", previous_id))?;
+    previous.completed = Some(now.to_rfc3339());
+    previous.artifacts = archived;
+    let previous = previous.clone();
+
+    let next = CycleRecord {
+        id: id.clone(),
+        cycle_type,
+        started: now.to_rfc3339(),
+        completed: None,
+        trigger: Some(trigger.to_string()),
+        parent: Some(previous_id),
+        artifacts: Vec::new(),
+    };
+    manifest.cycles.push(next.clone());
+    manifest.save(project_root)?;
+
+    state.current_phase = Phase::Seed;
+    state.cycle = id;
+    state.cycle_type = cycle_type;
+    state.last_updated = now;
+
+    Ok(Transition { previous, next, lessons: lessons.len() })
+}
+
+/// Move the cycle's artifacts (and branches) into `archive` and copy the
+/// shared ones; returns the names of the archived artifacts
+fn archive_artifacts(project_root: &Path, archive: &Path) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    let mut moved = CYCLE_ARTIFACTS.to_vec();
+    for branch in artifacts::branch_names(project_root)? {
+        moved.extend([
+            ArtifactKind::BranchHypothesis(branch.clone()),
+            ArtifactKind::BranchReview(branch.clone()),
+            ArtifactKind::BranchAbuse(branch.clone()),
+            ArtifactKind::BranchImplementation(branch),
+        ]);
+    }
+
+    for kind in &moved {
+        let from = artifacts::path(project_root, kind);
+        if !from.exists() {
+            continue;
+        }
+        let to = archive.join(artifacts::relative_path(kind));
+        if let Some(parent) = to.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::rename(&from, &to)
+            .with_context(|| format!("Failed to archive {} to {}", from.display(), to.display()))?;
+        names.push(kind.to_string());
+    }
+
+    let branches = artifacts::branches_dir(project_root);
+    if branches.is_dir() {
+        // Whatever else lived in the branch directories goes along
+        let to = archive.join("branches");
+        std::fs::create_dir_all(&to)?;
+        for entry in std::fs::read_dir(&branches)? {
+            let entry = entry?;
+            let target = to.join(entry.file_name());
+            if target.exists() {
+                for inner in std::fs::read_dir(entry.path())? {
+                    let inner = inner?;
+                    std::fs::rename(inner.path(), target.join(inner.file_name()))?;
+                }
+                std::fs::remove_dir(entry.path())?;
+            } else {
+                std::fs::rename(entry.path(), target)?;
+            }
+        }
+    }
+
+    for kind in &SHARED_ARTIFACTS {
+        let from = artifacts::path(project_root, kind);
+        if from.exists() {
+            std::fs::create_dir_all(archive)?;
+            std::fs::copy(&from, archive.join(artifacts::relative_path(kind)))?;
+        }
+    }
+
+    Ok(names)
+}
+
+/// Actionable lessons from a postmortem, one line each
+fn postmortem_lessons(postmortem: &str) -> Vec<String> {
+    let Ok(doc) = serde_yaml::from_str::<serde_yaml::Value>(postmortem) else {
+        return Vec::new();
+    };
+    let items = |key: &str| doc.get(key).and_then(|v| v.as_sequence()).cloned().unwrap_or_default();
+    let field = |item: &serde_yaml::Value, key: &str| item.get(key).and_then(|v| v.as_str()).map(str::to_string);
+
+    let mut lessons = Vec::new();
+    for item in items("learnings_summary") {
+        match (field(&item, "lesson"), field(&item, "actionable")) {
+            (Some(lesson), Some(action)) => lessons.push(format!("{} → {}", lesson, action)),
+            (Some(lesson), None) => lessons.push(lesson),
+            _ => {}
+        }
+    }
+    for item in items("rules_that_should_exist_earlier") {
+        if let Some(rule) = field(&item, "rule") {
+            lessons.push(format!("Establish early: {}", rule));
+        }
+    }
+    for item in items("rejected_approaches_review") {
+        if field(&item, "retrospective_verdict").as_deref() == Some("should_reconsider") {
+            if let Some(approach) = field(&item, "approach") {
+                lessons.push(format!("Reconsider previously rejected approach: {}", approach));
+            }
+        }
+    }
+    lessons
+}
+
+/// The `cycle` section of a seed started from a previous cycle
+#[derive(Serialize)]
+struct SeedCycle<'a> {
+    #[serde(rename = "type")]
+    cycle_type: CycleType,
+    id: &'a str,
+    parent: &'a str,
+    trigger: &'a str,
+    lessons_from_parent: &'a [String],
+}
+
+#[derive(Serialize)]
+struct NextSeed<'a> {
+    #[serde(flatten)]
+    seed: Seed,
+    cycle: SeedCycle<'a>,
+}
+
+/// Seed for a new cycle: the previous seed's answers as a starting point,
+/// plus the trigger and the previous postmortem's lessons
+fn next_seed(
+    previous: Option<&str>,
+    lessons: &[String],
+    parent: &str,
+    id: &str,
+    cycle_type: CycleType,
+    trigger: &str,
+) -> Result<String> {
+    let seed = previous
+        .and_then(|content| serde_yaml::from_str::<Seed>(content).ok())
+        .unwrap_or_else(|| Seed {
+            idea: trigger.to_string(),
+            target_user: "谁会用这个？具体场景是什么？".to_string(),
+            constraints: vec!["硬约束1".to_string()],
+            non_goals: vec!["明确不做的事1".to_string()],
+            success_criteria: vec!["可验证的成功标准1".to_string()],
+            reversibility_budget: "medium".to_string(),
+        });
+
+    let body = serde_yaml::to_string(&NextSeed {
+        seed,
+        cycle: SeedCycle { cycle_type, id, parent, trigger, lessons_from_parent: lessons },
+    })?;
+    Ok(format!(
+        "# .process/seed.yaml — {} cycle '{}', started from '{}'\n\
+         # Pre-filled from the previous seed and postmortem: revisit every field\n\
+         # against the trigger before running diverge.\n{}",
+        cycle_type, id, parent, body
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema;
+
+    const POSTMORTEM: &str = "learnings_summary:\n  - category: Technical\n    lesson: sessions do not scale\n    actionable: use stateless tokens\nrules_that_should_exist_earlier:\n  - rule: load test before launch\nrejected_approaches_review:\n  - approach: JWT\n    retrospective_verdict: should_reconsider\n  - approach: SOAP\n    retrospective_verdict: rejection_correct\n";
+
+    #[test]
+    fn test_cycle_type_round_trips() {
+        for t in [CycleType::Greenfield, CycleType::Evolution, CycleType::Sunset] {
+            assert_eq!(t.to_string().parse::<CycleType>().unwrap(), t);
+        }
+        assert!("rewrite".parse::<CycleType>().is_err());
+    }
+
+    #[test]
+    fn test_postmortem_lessons() {
+        assert_eq!(postmortem_lessons(POSTMORTEM), [
+            "sessions do not scale → use stateless tokens",
+            "Establish early: load test before launch",
+            "Reconsider previously rejected approach: JWT",
+        ]);
+        assert!(postmortem_lessons("not: [valid").is_empty());
+    }
+
+    #[test]
+    fn test_start_archives_cycle_and_seeds_the_next() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let dir = root.join(".process");
+        std::fs::create_dir_all(dir.join("branches/cache")).unwrap();
+        let seed = "idea: app\ntarget_user: devs\nconstraints: [offline]\nnon_goals: [n]\nsuccess_criteria: [s]\nreversibility_budget: low\n";
+        std::fs::write(dir.join("seed.yaml"), seed).unwrap();
+        std::fs::write(dir.join("postmortem.yaml"), POSTMORTEM).unwrap();
+        std::fs::write(dir.join("decisions_log.yaml"), "decisions: []\n").unwrap();
+        std::fs::write(dir.join("branches/cache/hypothesis.yaml"), "h").unwrap();
+        let mut manifest = Manifest::default();
+        manifest.record_artifact("postmortem", "postmortem.generate", "postmortem.yaml", POSTMORTEM, Default::default());
+        manifest.save(root).unwrap();
+
+        let mut state = ProcessState::default();
+        state.set_phase(Phase::Done);
+        let transition = start(root, &mut state, CycleType::Evolution, "sessions overloaded", Some("v2-auth")).unwrap();
+
+        assert_eq!(transition.previous.id, INITIAL_CYCLE);
+        assert_eq!(transition.next.parent.as_deref(), Some(INITIAL_CYCLE));
+        assert_eq!(transition.lessons, 3);
+        assert_eq!(state.current_phase, Phase::Seed);
+        assert_eq!(state.cycle, "v2-auth");
+        assert_eq!(state.cycle_type, CycleType::Evolution);
+
+        // Cycle artifacts moved, shared ones copied
+        let archive = archive_dir(root, INITIAL_CYCLE);
+        assert!(archive.join("postmortem.yaml").exists());
+        assert!(!dir.join("postmortem.yaml").exists());
+        assert!(archive.join("branches/cache/hypothesis.yaml").exists());
+        assert!(!dir.join("branches/cache").exists());
+        assert!(archive.join("decisions_log.yaml").exists());
+        assert!(dir.join("decisions_log.yaml").exists());
+
+        // The new seed is valid and carries the lessons
+        let next = std::fs::read_to_string(dir.join("seed.yaml")).unwrap();
+        schema::validate(&ArtifactKind::Seed, "seed.yaml", &next).unwrap();
+        assert!(next.contains("reversibility_budget: low"));
+        assert!(next.contains("use stateless tokens"));
+        assert!(next.contains("sessions overloaded"));
+
+        let manifest = Manifest::load(root).unwrap();
+        assert_eq!(manifest.cycles.len(), 2);
+        assert!(manifest.cycles[0].completed.is_some());
+        assert!(manifest.cycles[0].artifacts.contains(&"postmortem".to_string()));
+        assert!(!manifest.artifacts.contains_key("postmortem"));
+
+        // The same id cannot be reused
+        assert!(start(root, &mut state, CycleType::Sunset, "retire", Some("v2-auth")).is_err());
+        let third = start(root, &mut state, CycleType::Sunset, "retire", None).unwrap();
+        assert_eq!(third.next.id, "v3-sunset");
+        assert_eq!(third.previous.id, "v2-auth");
+    }
+}
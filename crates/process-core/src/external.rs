@@ -0,0 +1,461 @@
+//! Script passes ("Layer 2"): `process-pass-*` executables found in
+//! `.process/passes/` or on `PATH`, spoken to over stdin/stdout.
+//!
+//! Each message is one line of JSON-RPC 2.0. Every call starts a fresh
+//! process and sends it a single request:
+//!
+//! - `describe` → `{name, description, requires, optional_requires, produces, kind}`
+//!   where artifacts use their manifest names ("seed", "custom.lint-report")
+//!   and `kind` is `sync`, `ai` or `interactive`.
+//! - `run` with `{project_root, artifacts, overrides}` → `{artifacts}`, the
+//!   declared products by name. Before answering, the script may send
+//!   `complete` requests (`{prompt, max_tokens?, model?}`, answered with
+//!   `{content}`) and `log` notifications (`{message}`).
+//!
+//! Stderr is passed through to the terminal.
+
+use crate::pass::{ArtifactKind, Pass, PassContext, PassKind};
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use process_ai::provider::{CompletionRequest, Message};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+/// File name prefix that marks an executable as a pass
+pub const SCRIPT_PREFIX: &str = "process-pass-";
+
+/// Id of the host's request; callbacks from the script use their own ids
+const REQUEST_ID: u64 = 1;
+
+/// What a script reports about itself in answer to `describe`
+#[derive(Debug, Deserialize)]
+struct PassDescription {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    requires: Vec<String>,
+    #[serde(default)]
+    optional_requires: Vec<String>,
+    #[serde(default)]
+    produces: Vec<String>,
+    #[serde(default = "default_kind")]
+    kind: String,
+}
+
+fn default_kind() -> String {
+    "sync".to_string()
+}
+
+/// A pass implemented by an external executable
+#[derive(Debug, Clone)]
+pub struct ExternalPass {
+    path: PathBuf,
+    name: String,
+    description: String,
+    requires: Vec<ArtifactKind>,
+    optional_requires: Vec<ArtifactKind>,
+    produces: Vec<ArtifactKind>,
+    kind: PassKind,
+}
+
+impl ExternalPass {
+    /// Ask the executable to describe itself
+    pub fn describe(path: &Path) -> Result<Self> {
+        let mut child = std::process::Command::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("Failed to start {}", path.display()))?;
+
+        let request = json!({"jsonrpc": "2.0", "id": REQUEST_ID, "method": "describe"});
+        if let Some(mut stdin) = child.stdin.take() {
+            writeln!(stdin, "{}", request)?;
+        }
+        let output = child.wait_with_output()?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let result = stdout
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .find_map(|line| parse_response(line).transpose())
+            .ok_or_else(|| anyhow!("{} did not answer 'describe' ({})", path.display(), output.status))?
+            .with_context(|| format!("{} failed to describe itself", path.display()))?;
+
+        let description: PassDescription = serde_json::from_value(result)
+            .with_context(|| format!("Invalid 'describe' result from {}", path.display()))?;
+        Self::from_description(path, description)
+    }
+
+    fn from_description(path: &Path, d: PassDescription) -> Result<Self> {
+        let parse_all = |names: &[String]| -> Result<Vec<ArtifactKind>> {
+            names.iter().map(|n| n.parse()).collect()
+        };
+        let kind = match d.kind.as_str() {
+            "sync" => PassKind::Sync,
+            "ai" | "ai_assisted" => PassKind::AiAssisted,
+            "interactive" => PassKind::Interactive,
+            other => bail!("Unknown pass kind '{}' (expected sync, ai or interactive)", other),
+        };
+        if d.name.trim().is_empty() {
+            bail!("{} described a pass with an empty name", path.display());
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            requires: parse_all(&d.requires)?,
+            optional_requires: parse_all(&d.optional_requires)?,
+            produces: parse_all(&d.produces)?,
+            name: d.name,
+            description: d.description,
+            kind,
+        })
+    }
+
+    /// The executable behind this pass
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// `process-pass-*` executables, project scripts first, then `PATH` in
+/// order. A name found twice keeps its first location, as a shell would.
+pub fn find_scripts(project_root: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![project_root.join(".process/passes")];
+    if let Some(path) = std::env::var_os("PATH") {
+        dirs.extend(std::env::split_paths(&path));
+    }
+
+    let mut seen = HashSet::new();
+    let mut scripts = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        let mut found: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with(SCRIPT_PREFIX))
+            })
+            .filter(|p| is_executable(p))
+            .collect();
+        found.sort();
+        for path in found {
+            if seen.insert(path.file_name().map(|n| n.to_os_string())) {
+                scripts.push(path);
+            }
+        }
+    }
+    scripts
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// `Some(result)` for the answer to our request, `None` for anything else
+fn parse_response(line: &str) -> Result<Option<Value>> {
+    let message: Value = serde_json::from_str(line)
+        .with_context(|| format!("Invalid JSON-RPC message: {}", line))?;
+    if message.get("method").is_some() || message.get("id") != Some(&json!(REQUEST_ID)) {
+        return Ok(None);
+    }
+    if let Some(error) = message.get("error") {
+        let text = error.get("message").and_then(Value::as_str).unwrap_or("unknown error");
+        bail!("{}", text);
+    }
+    Ok(Some(message.get("result").cloned().unwrap_or(Value::Null)))
+}
+
+#[async_trait]
+impl Pass for ExternalPass {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        self.requires.clone()
+    }
+
+    fn optional_requires(&self) -> Vec<ArtifactKind> {
+        self.optional_requires.clone()
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        self.produces.clone()
+    }
+
+    fn kind(&self) -> PassKind {
+        self.kind.clone()
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        let mut child = tokio::process::Command::new(&self.path)
+            .current_dir(ctx.project_root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to start {}", self.path.display()))?;
+        let mut stdin = child.stdin.take().context("script stdin unavailable")?;
+        let mut lines = BufReader::new(child.stdout.take().context("script stdout unavailable")?).lines();
+
+        let artifacts: BTreeMap<String, &str> = ctx.artifacts.iter()
+            .map(|(kind, content)| (kind.to_string(), content.as_str()))
+            .collect();
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": REQUEST_ID,
+            "method": "run",
+            "params": {
+                "project_root": ctx.project_root,
+                "artifacts": artifacts,
+                "overrides": ctx.overrides,
+            },
+        });
+        stdin.write_all(format!("{}\n", request).as_bytes()).await?;
+
+        let mut result = None;
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(r) = parse_response(&line)
+                .with_context(|| format!("Script {} returned an error", self.path.display()))?
+            {
+                result = Some(r);
+                break;
+            }
+
+            let message: Value = serde_json::from_str(&line)?;
+            let method = message.get("method").and_then(Value::as_str).unwrap_or_default();
+            let params = message.get("params").cloned().unwrap_or(Value::Null);
+            match (method, message.get("id")) {
+                ("log", _) => {
+                    let text = params.get("message").and_then(Value::as_str).unwrap_or_default();
+                    println!("    [{}] {}", self.name, text);
+                }
+                ("complete", Some(id)) => {
+                    let reply = match complete(ctx, params).await {
+                        Ok(content) => json!({"jsonrpc": "2.0", "id": id, "result": {"content": content}}),
+                        Err(e) => json!({"jsonrpc": "2.0", "id": id, "error": {"code": -32000, "message": e.to_string()}}),
+                    };
+                    stdin.write_all(format!("{}\n", reply).as_bytes()).await?;
+                }
+                (other, Some(id)) => {
+                    let reply = json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": {"code": -32601, "message": format!("Method not found: {}", other)},
+                    });
+                    stdin.write_all(format!("{}\n", reply).as_bytes()).await?;
+                }
+                // Unknown notifications are ignored
+                (_, None) => {}
+            }
+        }
+        drop(stdin);
+
+        let status = child.wait().await?;
+        let result = result.ok_or_else(|| {
+            anyhow!("Script {} exited without a result ({})", self.path.display(), status)
+        })?;
+
+        let produced: BTreeMap<String, String> = serde_json::from_value(
+            result.get("artifacts").cloned().unwrap_or_else(|| json!({})),
+        )
+        .context("'run' result must contain an 'artifacts' map of name → content")?;
+
+        for (name, content) in produced {
+            let kind: ArtifactKind = name.parse()?;
+            if !self.produces.contains(&kind) {
+                bail!("Pass '{}' returned artifact '{}' it does not declare in 'produces'", self.name, name);
+            }
+            ctx.save_artifact(&kind, &content)?;
+            println!("✔ Saved {}", ctx.artifact_filename(&kind));
+        }
+
+        Ok(())
+    }
+}
+
+/// Answer a script's `complete` callback with the pass's AI provider.
+/// Scripts send either a `prompt` or a `messages` conversation, plus an
+/// optional `system` prompt; a `model` or `max_tokens` they send wins over
+/// the pass's route.
+async fn complete(ctx: &PassContext<'_>, params: Value) -> Result<String> {
+    #[derive(Deserialize)]
+    struct Params {
+        prompt: Option<String>,
+        #[serde(default)]
+        messages: Vec<Message>,
+        system: Option<String>,
+        max_tokens: Option<usize>,
+        model: Option<String>,
+    }
+    let params: Params = serde_json::from_value(params).context("Invalid 'complete' params")?;
+
+    let mut messages = params.messages;
+    messages.extend(params.prompt.map(Message::user));
+    if messages.is_empty() {
+        bail!("Invalid 'complete' params: expected 'prompt' or 'messages'");
+    }
+
+    let route = ctx.route(None);
+    let provider = ctx.provider().await?;
+    let response = provider.complete(&CompletionRequest {
+        system: params.system,
+        messages,
+        max_tokens: params.max_tokens.or(route.max_tokens).or(Some(4096)),
+        model: params.model.or(route.model),
+        temperature: route.temperature,
+        response_schema: None,
+        template: None,
+    }).await?;
+    Ok(response.content)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::pass::PassServices;
+    use process_ai::provider::{AiProvider, CompletionResponse};
+    use process_ai::registry::AiRegistry;
+    use std::os::unix::fs::PermissionsExt;
+    use std::sync::Arc;
+
+    /// A shell stand-in for a Python pass: describes itself, asks the host
+    /// for one completion, then returns a report built from the answer
+    const FAKE_SCRIPT: &str = r#"#!/bin/sh
+read request
+case "$request" in
+  *'"describe"'*)
+    echo '{"jsonrpc":"2.0","id":1,"result":{"name":"lint.report","description":"Fake lint","requires":["seed"],"produces":["custom.lint-report"],"kind":"ai"}}'
+    ;;
+  *'"run"'*)
+    case "$request" in
+      *'idea: lint me'*) ;;
+      *) echo '{"jsonrpc":"2.0","id":1,"error":{"code":1,"message":"seed not passed"}}'; exit 0 ;;
+    esac
+    echo '{"jsonrpc":"2.0","method":"log","params":{"message":"asking the host"}}'
+    echo '{"jsonrpc":"2.0","id":"c1","method":"complete","params":{"prompt":"hello"}}'
+    read reply
+    case "$reply" in
+      *'echo: hello'*) printf '%s\n' '{"jsonrpc":"2.0","id":1,"result":{"artifacts":{"custom.lint-report":"ok: true\n"}}}' ;;
+      *) echo '{"jsonrpc":"2.0","id":1,"error":{"code":1,"message":"bad completion"}}' ;;
+    esac
+    ;;
+esac
+"#;
+
+    struct EchoProvider;
+
+    #[async_trait]
+    impl AiProvider for EchoProvider {
+        fn name(&self) -> &'static str { "echo" }
+        fn priority(&self) -> u8 { 0 }
+        async fn is_available(&self) -> bool { true }
+        async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+            Ok(CompletionResponse { content: format!("echo: {}", request.transcript()), usage: None, provider: None, model: None, cached: false })
+        }
+    }
+
+    fn write_script(dir: &Path, name: &str, body: &str) -> PathBuf {
+        std::fs::create_dir_all(dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, body).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_find_scripts_in_project_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join(".process/passes");
+        write_script(&dir, "process-pass-lint", FAKE_SCRIPT);
+        write_script(&dir, "unrelated", FAKE_SCRIPT);
+        std::fs::write(dir.join("process-pass-notexec"), FAKE_SCRIPT).unwrap();
+
+        let found = find_scripts(tmp.path());
+        assert!(found.contains(&dir.join("process-pass-lint")));
+        assert!(!found.iter().any(|p| p.starts_with(&dir) && !p.ends_with("process-pass-lint")));
+    }
+
+    #[test]
+    fn test_describe_parses_script_metadata() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = write_script(tmp.path(), "process-pass-lint", FAKE_SCRIPT);
+
+        let pass = ExternalPass::describe(&path).unwrap();
+        assert_eq!(pass.name(), "lint.report");
+        assert_eq!(pass.requires(), vec![ArtifactKind::Seed]);
+        assert_eq!(pass.produces(), vec![ArtifactKind::Custom("lint-report".to_string())]);
+        assert_eq!(pass.kind(), PassKind::AiAssisted);
+    }
+
+    #[test]
+    fn test_describe_rejects_unknown_artifact() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = write_script(tmp.path(), "process-pass-bad", "#!/bin/sh\nread r\necho '{\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{\"name\":\"bad\",\"produces\":[\"report\"]}}'\n");
+        assert!(ExternalPass::describe(&path).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_exchanges_artifacts_and_completions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = write_script(tmp.path(), "process-pass-lint", FAKE_SCRIPT);
+        let pass = ExternalPass::describe(&path).unwrap();
+
+        let root = tmp.path().join("project");
+        std::fs::create_dir_all(root.join(".process")).unwrap();
+        std::fs::write(
+            root.join(".process/seed.yaml"),
+            "idea: lint me\ntarget_user: devs\nconstraints: [c]\nnon_goals: [n]\nsuccess_criteria: [s]\nreversibility_budget: low\n",
+        ).unwrap();
+
+        let mut ai = AiRegistry::new();
+        ai.register(EchoProvider);
+        let mut config = process_config::config::Config::default();
+        config.ai.provider = "echo".to_string();
+        let services = PassServices { config: Arc::new(config), ai: Arc::new(ai), ..PassServices::default() };
+
+        let mut ctx = PassContext::with_services(&root, &services);
+        ctx.load_artifact(&ArtifactKind::Seed).unwrap();
+        pass.run(&mut ctx).await.unwrap();
+
+        let report = std::fs::read_to_string(root.join(".process/lint-report.yaml")).unwrap();
+        assert_eq!(report, "ok: true\n");
+    }
+
+    #[tokio::test]
+    async fn test_run_surfaces_script_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = write_script(tmp.path(), "process-pass-lint", FAKE_SCRIPT);
+        let pass = ExternalPass::describe(&path).unwrap();
+
+        // No seed loaded: the script answers with a JSON-RPC error
+        let mut ctx = PassContext::new(tmp.path());
+        let err = pass.run(&mut ctx).await.unwrap_err();
+        assert!(format!("{:#}", err).contains("seed not passed"));
+    }
+}
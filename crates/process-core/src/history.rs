@@ -0,0 +1,179 @@
+//! Artifact history: every version of an artifact a pass writes is kept
+//! under `.process/history/<artifact>/<n>.yaml` and numbered in the
+//! manifest, so a decision stays traceable to the exact inputs it was made
+//! from even after the pass is re-run.
+
+use crate::artifacts::{self, PROCESS_DIR};
+use crate::manifest::{hash_content, ArtifactVersion, Manifest};
+use crate::pass::ArtifactKind;
+use crate::schema;
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+
+/// Directory holding one subdirectory per artifact, under `.process/`
+pub const HISTORY_DIR: &str = "history";
+
+/// Recorded as `produced_by` for content written outside any pass
+pub const MANUAL: &str = "manual";
+
+/// Path of one saved version of an artifact
+pub fn version_path(project_root: &Path, artifact: &str, version: u32) -> PathBuf {
+    project_root
+        .join(PROCESS_DIR)
+        .join(HISTORY_DIR)
+        .join(artifact)
+        .join(format!("{}.yaml", version))
+}
+
+/// Saved versions of an artifact, oldest first
+pub fn versions<'m>(manifest: &'m Manifest, artifact: &str) -> &'m [ArtifactVersion] {
+    manifest.history.get(artifact).map(Vec::as_slice).unwrap_or_default()
+}
+
+/// Save `content` as the next version of an artifact. Content identical to
+/// the latest version is not saved again; returns the new version number.
+pub fn record(
+    project_root: &Path,
+    manifest: &mut Manifest,
+    artifact: &str,
+    content: &str,
+    produced_by: &str,
+    provider: Option<&str>,
+) -> Result<Option<u32>> {
+    let content_hash = hash_content(content);
+    let history = manifest.history.entry(artifact.to_string()).or_default();
+    if history.last().is_some_and(|v| v.content_hash == content_hash) {
+        return Ok(None);
+    }
+
+    let version = history.last().map_or(1, |v| v.version + 1);
+    artifacts::write(&version_path(project_root, artifact, version), content)?;
+
+    history.push(ArtifactVersion {
+        version,
+        timestamp: Utc::now().to_rfc3339(),
+        produced_by: produced_by.to_string(),
+        provider: provider.map(str::to_string),
+        content_hash,
+    });
+    Ok(Some(version))
+}
+
+/// Save the artifact as it is on disk now, if that content is not already
+/// the latest version — typically right before a pass overwrites it.
+/// Content the manifest attributes to a pass keeps that attribution;
+/// anything else was edited by hand.
+pub fn snapshot(project_root: &Path, manifest: &mut Manifest, kind: &ArtifactKind) -> Result<Option<u32>> {
+    let Ok(content) = std::fs::read_to_string(artifacts::path(project_root, kind)) else {
+        return Ok(None);
+    };
+    let name = kind.to_string();
+    let produced_by = manifest
+        .artifacts
+        .get(&name)
+        .filter(|record| record.content_hash == hash_content(&content))
+        .map_or(MANUAL.to_string(), |record| record.produced_by.clone());
+    record(project_root, manifest, &name, &content, &produced_by, None)
+}
+
+/// Content of one saved version
+pub fn read(project_root: &Path, manifest: &Manifest, artifact: &str, version: u32) -> Result<String> {
+    if !versions(manifest, artifact).iter().any(|v| v.version == version) {
+        return Err(anyhow!("'{}' has no version {} (see `process artifact history {}`)", artifact, version, artifact));
+    }
+    let path = version_path(project_root, artifact, version);
+    std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+/// Write a saved version back as the current artifact. The restore is
+/// itself recorded as a new version, so history only ever grows.
+pub fn restore(project_root: &Path, manifest: &mut Manifest, kind: &ArtifactKind, version: u32) -> Result<Option<u32>> {
+    let name = kind.to_string();
+    let content = read(project_root, manifest, &name, version)?;
+    schema::validate(kind, &artifacts::relative_path(kind), &content).map_err(|e| anyhow::Error::new(*e))?;
+
+    snapshot(project_root, manifest, kind)?;
+    artifacts::write(&artifacts::path(project_root, kind), &content)?;
+
+    if let Some(record) = manifest.artifacts.get_mut(&name) {
+        record.content_hash = hash_content(&content);
+        record.last_updated = Utc::now().to_rfc3339();
+    }
+    record(project_root, manifest, &name, &content, &format!("restore@{}", version), None)
+}
+
+/// Parse `<artifact>[@<version>]`, e.g. `proposals@2`
+pub fn parse_spec(spec: &str) -> Result<(ArtifactKind, Option<u32>)> {
+    let (name, version) = match spec.rsplit_once('@') {
+        Some((name, version)) => {
+            let version = version
+                .parse()
+                .map_err(|_| anyhow!("Invalid version '{}' in '{}' (expected <artifact>@<n>)", version, spec))?;
+            (name, Some(version))
+        }
+        None => (spec, None),
+    };
+    Ok((name.parse()?, version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_numbers_versions_and_skips_duplicates() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut manifest = Manifest::default();
+
+        assert_eq!(record(tmp.path(), &mut manifest, "proposals", "a", "diverge.generate", Some("claude")).unwrap(), Some(1));
+        assert_eq!(record(tmp.path(), &mut manifest, "proposals", "a", "diverge.generate", Some("claude")).unwrap(), None);
+        assert_eq!(record(tmp.path(), &mut manifest, "proposals", "b", "diverge.generate", None).unwrap(), Some(2));
+
+        let history = versions(&manifest, "proposals");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].provider.as_deref(), Some("claude"));
+        assert_eq!(read(tmp.path(), &manifest, "proposals", 1).unwrap(), "a");
+        assert!(read(tmp.path(), &manifest, "proposals", 3).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_attributes_hand_edits_to_manual() {
+        let tmp = tempfile::tempdir().unwrap();
+        let kind = ArtifactKind::Custom("notes".into());
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+        std::fs::write(artifacts::path(tmp.path(), &kind), "generated").unwrap();
+
+        let mut manifest = Manifest::default();
+        manifest.record_artifact("custom.notes", "notes.generate", "notes.yaml", "generated", Default::default());
+        assert_eq!(snapshot(tmp.path(), &mut manifest, &kind).unwrap(), Some(1));
+
+        std::fs::write(artifacts::path(tmp.path(), &kind), "edited").unwrap();
+        assert_eq!(snapshot(tmp.path(), &mut manifest, &kind).unwrap(), Some(2));
+
+        let history = versions(&manifest, "custom.notes");
+        assert_eq!(history[0].produced_by, "notes.generate");
+        assert_eq!(history[1].produced_by, MANUAL);
+    }
+
+    #[test]
+    fn test_restore_writes_old_content_as_new_version() {
+        let tmp = tempfile::tempdir().unwrap();
+        let kind = ArtifactKind::Custom("notes".into());
+        let mut manifest = Manifest::default();
+        record(tmp.path(), &mut manifest, "custom.notes", "v1", "p", None).unwrap();
+        record(tmp.path(), &mut manifest, "custom.notes", "v2", "p", None).unwrap();
+        std::fs::write(artifacts::path(tmp.path(), &kind), "v2").unwrap();
+
+        assert_eq!(restore(tmp.path(), &mut manifest, &kind, 1).unwrap(), Some(3));
+        assert_eq!(std::fs::read_to_string(artifacts::path(tmp.path(), &kind)).unwrap(), "v1");
+        assert_eq!(versions(&manifest, "custom.notes")[2].produced_by, "restore@1");
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        assert_eq!(parse_spec("proposals@2").unwrap(), (ArtifactKind::Proposals, Some(2)));
+        assert_eq!(parse_spec("branch.cache.review").unwrap(), (ArtifactKind::BranchReview("cache".into()), None));
+        assert!(parse_spec("proposals@latest").is_err());
+    }
+}
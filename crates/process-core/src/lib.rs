@@ -3,3 +3,11 @@ pub mod state;
 pub mod pass;
 pub mod manifest;
 pub mod pass_manager;
+pub mod pipeline;
+pub mod external;
+pub mod schema;
+pub mod artifacts;
+pub mod history;
+pub mod cycle;
+pub mod lock;
+pub mod usage;
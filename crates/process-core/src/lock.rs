@@ -0,0 +1,93 @@
+//! Advisory lock on `.process/`, so two commands (two terminals, or a git
+//! hook and a human) never interleave their read-modify-write cycles.
+//!
+//! The lock is an OS file lock on `.process/.lock`, released when the
+//! `ProcessLock` is dropped or the process exits. The file also records who
+//! holds it, for the error the next command shows.
+
+use crate::artifacts::PROCESS_DIR;
+use anyhow::{bail, Context, Result};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+
+/// Lock file name under `.process/`
+pub const LOCK_FILE: &str = ".lock";
+
+/// Exclusive hold on a project's `.process/` directory
+#[derive(Debug)]
+pub struct ProcessLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl ProcessLock {
+    /// Take the lock without waiting. `holder` describes this process (for
+    /// example the command line) to anyone who finds the lock taken.
+    pub fn acquire(project_root: &Path, holder: &str) -> Result<Self> {
+        let path = project_root.join(PROCESS_DIR).join(LOCK_FILE);
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let mut owner = String::new();
+                file.read_to_string(&mut owner).ok();
+                let owner = owner.trim();
+                let owner = if owner.is_empty() { "another process" } else { owner };
+                bail!(
+                    "{} is locked by {}. Wait for it to finish, then retry \
+                     (the lock is released automatically when that process exits).",
+                    project_root.join(PROCESS_DIR).display(),
+                    owner
+                );
+            }
+            Err(TryLockError::Error(e)) => {
+                return Err(e).with_context(|| format!("Failed to lock {}", path.display()));
+            }
+        }
+
+        file.set_len(0)?;
+        file.rewind()?;
+        writeln!(file, "pid {} ({})", std::process::id(), holder)?;
+        Ok(Self { file, path })
+    }
+
+    /// Path of the lock file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ProcessLock {
+    fn drop(&mut self) {
+        // Leave the file for the next holder; just forget who we were
+        self.file.set_len(0).ok();
+        self.file.unlock().ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_second_holder_gets_clear_error_until_release() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+
+        let lock = ProcessLock::acquire(tmp.path(), "process pipeline run default").unwrap();
+        let err = ProcessLock::acquire(tmp.path(), "process learn").unwrap_err().to_string();
+        assert!(err.contains("is locked by pid"), "{}", err);
+        assert!(err.contains("process pipeline run default"), "{}", err);
+
+        drop(lock);
+        ProcessLock::acquire(tmp.path(), "process learn").unwrap();
+    }
+}
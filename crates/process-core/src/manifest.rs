@@ -1,6 +1,11 @@
+use crate::artifacts;
+use crate::cycle::CycleType;
+use crate::pass::ArtifactKind;
+use crate::phase::Phase;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 use chrono::Utc;
 
@@ -15,6 +20,62 @@ pub struct ArtifactRecord {
     pub content_hash: String,
     /// File path relative to .process/
     pub path: String,
+    /// SHA-256 of each input artifact (by name) when this one was produced
+    #[serde(default)]
+    pub inputs: BTreeMap<String, String>,
+    /// AI provider that answered the pass (after any fallback), if it used one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Set when a reopened phase invalidated this artifact; cleared when a
+    /// pass produces it again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub superseded: Option<Supersession>,
+}
+
+/// Why an artifact no longer reflects the current decisions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Supersession {
+    /// The phase that was reopened
+    pub reopened: Phase,
+    pub reason: String,
+    pub timestamp: String,
+}
+
+/// One saved version of an artifact, kept under `.process/history/`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactVersion {
+    /// 1-based, increasing with every save
+    pub version: u32,
+    /// When this version was written
+    pub timestamp: String,
+    /// Pass that wrote it ("manual" for edits made outside any pass)
+    pub produced_by: String,
+    /// AI provider the pass used, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// SHA-256 hash of the content
+    pub content_hash: String,
+}
+
+/// One decision cycle in the project's lineage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleRecord {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub cycle_type: CycleType,
+    pub started: String,
+    /// Set when the next cycle starts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed: Option<String>,
+    /// Why the cycle was started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<String>,
+    /// The cycle this one grew out of
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// Artifacts archived under `.process/archive/<id>/` when it completed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<String>,
 }
 
 /// The manifest tracks all artifacts and their provenance
@@ -24,13 +85,19 @@ pub struct Manifest {
     pub version: u32,
     /// Map of artifact name → record
     pub artifacts: HashMap<String, ArtifactRecord>,
+    /// Map of artifact name → saved versions, oldest first
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub history: BTreeMap<String, Vec<ArtifactVersion>>,
+    /// Decision cycles, oldest first; the last one is active
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cycles: Vec<CycleRecord>,
 }
 
 impl Manifest {
     pub fn load(project_root: &Path) -> Result<Self> {
         let path = project_root.join(".process/manifest.yaml");
         if !path.exists() {
-            return Ok(Self { version: 1, artifacts: HashMap::new() });
+            return Ok(Self { version: 1, ..Default::default() });
         }
         let content = std::fs::read_to_string(&path)?;
         let manifest: Manifest = serde_yaml::from_str(&content)?;
@@ -40,33 +107,120 @@ impl Manifest {
     pub fn save(&self, project_root: &Path) -> Result<()> {
         let path = project_root.join(".process/manifest.yaml");
         let content = serde_yaml::to_string(self)?;
-        std::fs::write(&path, content)?;
+        artifacts::write(&path, content)?;
         Ok(())
     }
 
-    /// Record that a pass produced an artifact
-    pub fn record_artifact(&mut self, artifact_name: &str, pass_name: &str, file_path: &str, content: &str) {
-        let hash = format!("{:x}", md5_hash(content));
+    /// Record that a pass produced an artifact from the given input hashes
+    pub fn record_artifact(
+        &mut self,
+        artifact_name: &str,
+        pass_name: &str,
+        file_path: &str,
+        content: &str,
+        inputs: BTreeMap<String, String>,
+    ) {
         self.artifacts.insert(artifact_name.to_string(), ArtifactRecord {
             produced_by: pass_name.to_string(),
             last_updated: Utc::now().to_rfc3339(),
-            content_hash: hash,
+            content_hash: hash_content(content),
             path: file_path.to_string(),
+            inputs,
+            provider: None,
+            superseded: None,
         });
     }
 
-    /// Check if an artifact is stale (its dependencies changed since it was produced)
-    pub fn is_fresh(&self, artifact_name: &str, _dependency_names: &[&str]) -> bool {
-        // Simple freshness: artifact exists in manifest
-        self.artifacts.contains_key(artifact_name)
+    /// Mark every recorded artifact produced in `reopened` or a later phase
+    /// as superseded; returns their names
+    pub fn supersede(&mut self, reopened: Phase, reason: &str) -> Vec<String> {
+        let timestamp = Utc::now().to_rfc3339();
+        let mut names: Vec<String> = self
+            .artifacts
+            .iter_mut()
+            .filter(|(name, _)| {
+                name.parse::<ArtifactKind>()
+                    .ok()
+                    .and_then(|kind| artifacts::phase_of(&kind))
+                    .is_some_and(|phase| phase >= reopened)
+            })
+            .map(|(name, record)| {
+                record.superseded = Some(Supersession {
+                    reopened,
+                    reason: reason.to_string(),
+                    timestamp: timestamp.clone(),
+                });
+                name.clone()
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Check if an artifact is fresh: it was recorded from exactly these
+    /// input hashes (an input added, removed or edited makes it stale) and
+    /// no reopened phase has superseded it
+    pub fn is_fresh(&self, artifact_name: &str, current_inputs: &BTreeMap<String, String>) -> bool {
+        self.artifacts
+            .get(artifact_name)
+            .is_some_and(|record| &record.inputs == current_inputs && record.superseded.is_none())
     }
 }
 
-/// Simple hash function (not cryptographic, just for change detection)
-fn md5_hash(content: &str) -> u64 {
-    let mut hash: u64 = 5381;
-    for byte in content.bytes() {
-        hash = hash.wrapping_mul(33).wrapping_add(byte as u64);
+/// Hex-encoded SHA-256 of an artifact's content
+pub fn hash_content(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), hash_content(v))).collect()
+    }
+
+    #[test]
+    fn test_hash_content_is_sha256() {
+        assert_eq!(
+            hash_content("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_is_fresh_compares_input_hashes() {
+        let mut manifest = Manifest::default();
+        manifest.record_artifact("rules", "converge.analyze", "rules.yaml", "rules", inputs(&[("seed", "v1")]));
+
+        assert!(manifest.is_fresh("rules", &inputs(&[("seed", "v1")])));
+        assert!(!manifest.is_fresh("rules", &inputs(&[("seed", "v2")])));
+        assert!(!manifest.is_fresh("rules", &inputs(&[("seed", "v1"), ("proposals", "p")])));
+        assert!(!manifest.is_fresh("skeleton", &BTreeMap::new()));
+    }
+
+    #[test]
+    fn test_supersede_marks_reopened_phase_and_later() {
+        let mut manifest = Manifest::default();
+        for (name, file) in [("proposals", "diverge_summary.yaml"), ("rules", "rules.yaml"), ("skeleton", "skeleton.yaml"), ("branch.cache.review", "branches/cache/review.yaml"), ("learnings", "learnings.yaml")] {
+            manifest.record_artifact(name, "p", file, name, BTreeMap::new());
+        }
+
+        let superseded = manifest.supersede(Phase::Converge, "wrong database");
+        assert_eq!(superseded, ["branch.cache.review", "rules", "skeleton"]);
+        assert!(manifest.is_fresh("proposals", &BTreeMap::new()));
+        assert!(!manifest.is_fresh("rules", &BTreeMap::new()));
+        assert_eq!(manifest.artifacts["rules"].superseded.as_ref().unwrap().reason, "wrong database");
+
+        // Producing it again clears the mark
+        manifest.record_artifact("rules", "p", "rules.yaml", "rules v2", BTreeMap::new());
+        assert!(manifest.is_fresh("rules", &BTreeMap::new()));
+    }
+
+    #[test]
+    fn test_old_manifest_without_inputs_loads() {
+        let yaml = "version: 1\nartifacts:\n  seed:\n    produced_by: seed.init\n    last_updated: now\n    content_hash: abc\n    path: seed.yaml\n";
+        let manifest: Manifest = serde_yaml::from_str(yaml).unwrap();
+        assert!(manifest.artifacts["seed"].inputs.is_empty());
     }
-    hash
 }
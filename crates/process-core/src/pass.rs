@@ -1,7 +1,17 @@
+use crate::state::ProcessState;
+use crate::usage::{self, UsageScope};
+use crate::{artifacts, schema};
 use anyhow::Result;
+use async_trait::async_trait;
+use process_ai::cache::ResponseCache;
+use process_ai::prompts::PromptEngine;
+use process_ai::provider::{AiProvider, ChunkSink, CompletionRequest, CompletionResponse};
+use process_ai::registry::AiRegistry;
+use process_config::config::{Config, Route};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 /// Kinds of artifacts produced/consumed by passes
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -15,6 +25,8 @@ pub enum ArtifactKind {
     Friction,
     BranchHypothesis(String),
     BranchReview(String),
+    BranchAbuse(String),
+    BranchImplementation(String),
     Postmortem,
     Custom(String),
 }
@@ -31,12 +43,49 @@ impl std::fmt::Display for ArtifactKind {
             ArtifactKind::Friction => write!(f, "friction"),
             ArtifactKind::BranchHypothesis(name) => write!(f, "branch.{}.hypothesis", name),
             ArtifactKind::BranchReview(name) => write!(f, "branch.{}.review", name),
+            ArtifactKind::BranchAbuse(name) => write!(f, "branch.{}.abuse", name),
+            ArtifactKind::BranchImplementation(name) => write!(f, "branch.{}.implement", name),
             ArtifactKind::Postmortem => write!(f, "postmortem"),
             ArtifactKind::Custom(name) => write!(f, "custom.{}", name),
         }
     }
 }
 
+impl std::str::FromStr for ArtifactKind {
+    type Err = anyhow::Error;
+
+    /// Parse the name produced by `Display` (e.g. "seed", "custom.gap-report")
+    fn from_str(s: &str) -> Result<Self> {
+        let kind = match s {
+            "seed" => ArtifactKind::Seed,
+            "proposals" => ArtifactKind::Proposals,
+            "rules" => ArtifactKind::Rules,
+            "skeleton" => ArtifactKind::Skeleton,
+            "decision_log" => ArtifactKind::DecisionLog,
+            "learnings" => ArtifactKind::Learnings,
+            "friction" => ArtifactKind::Friction,
+            "postmortem" => ArtifactKind::Postmortem,
+            _ => {
+                if let Some(name) = s.strip_prefix("custom.").filter(|n| !n.is_empty()) {
+                    ArtifactKind::Custom(name.to_string())
+                } else if let Some((branch, part)) = s.strip_prefix("branch.").and_then(|r| r.rsplit_once('.')) {
+                    let branch = branch.to_string();
+                    match part {
+                        "hypothesis" => ArtifactKind::BranchHypothesis(branch),
+                        "review" => ArtifactKind::BranchReview(branch),
+                        "abuse" => ArtifactKind::BranchAbuse(branch),
+                        "implement" => ArtifactKind::BranchImplementation(branch),
+                        _ => anyhow::bail!("Unknown branch artifact '{}'", s),
+                    }
+                } else {
+                    anyhow::bail!("Unknown artifact '{}' (custom artifacts are named 'custom.<name>')", s);
+                }
+            }
+        };
+        Ok(kind)
+    }
+}
+
 /// The kind of pass execution
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PassKind {
@@ -48,70 +97,246 @@ pub enum PassKind {
     Interactive,
 }
 
+/// Services shared by every pass in a run
+#[derive(Clone)]
+pub struct PassServices {
+    pub config: Arc<Config>,
+    pub ai: Arc<AiRegistry>,
+    pub prompts: Arc<PromptEngine>,
+}
+
+impl PassServices {
+    /// Built-in providers for the given configuration (replay fixtures
+    /// resolved under the project), with prompt lookup honouring the
+    /// project's `.process/prompts/` overrides
+    pub fn from_config(project_root: &Path, config: Config) -> Self {
+        Self {
+            ai: Arc::new(AiRegistry::for_project(project_root, &config)),
+            prompts: Arc::new(PromptEngine::for_project(project_root, &config.ai.provider)),
+            config: Arc::new(config),
+        }
+    }
+
+    /// Services for a project: its configuration and prompt overrides
+    pub fn load(project_root: &Path) -> Result<Self> {
+        Ok(Self::from_config(project_root, Config::load(project_root)?))
+    }
+}
+
+impl Default for PassServices {
+    /// Default configuration with no AI providers registered
+    fn default() -> Self {
+        Self {
+            config: Arc::new(Config::default()),
+            ai: Arc::new(AiRegistry::new()),
+            prompts: Arc::new(PromptEngine::new("auto")),
+        }
+    }
+}
+
+/// Per-pass settings a pipeline can override
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PassOverrides {
+    /// AI provider name, instead of `ai.provider`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Model passed to the provider
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Completion token limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<usize>,
+}
+
+/// Passes the provider through, remembering who answered each completion
+struct RecordingProvider {
+    inner: Arc<dyn AiProvider>,
+    used: Arc<Mutex<Option<String>>>,
+}
+
+impl RecordingProvider {
+    fn note(&self, response: &CompletionResponse) {
+        let name = response.provider.clone().unwrap_or_else(|| self.inner.name().to_string());
+        *self.used.lock().unwrap() = Some(name);
+    }
+}
+
+#[async_trait]
+impl AiProvider for RecordingProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn label(&self) -> String {
+        self.inner.label()
+    }
+
+    fn primary(&self) -> String {
+        self.inner.primary()
+    }
+
+    fn model(&self) -> Option<String> {
+        self.inner.model()
+    }
+
+    async fn is_available(&self) -> bool {
+        self.inner.is_available().await
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+        let response = self.inner.complete(request).await?;
+        self.note(&response);
+        Ok(response)
+    }
+
+    async fn complete_stream(&self, request: &CompletionRequest, on_chunk: ChunkSink<'_>) -> Result<CompletionResponse> {
+        let response = self.inner.complete_stream(request, on_chunk).await?;
+        self.note(&response);
+        Ok(response)
+    }
+}
+
 /// Context provided to each pass during execution
 pub struct PassContext<'a> {
     /// Project root directory
     pub project_root: &'a Path,
     /// Read an artifact file as a string
     pub artifacts: HashMap<ArtifactKind, String>,
+    /// Loaded configuration
+    pub config: Arc<Config>,
+    /// Prompt template engine
+    pub prompts: Arc<PromptEngine>,
+    /// AI provider registry
+    pub ai: Arc<AiRegistry>,
+    /// Pipeline overrides for this pass
+    pub overrides: PassOverrides,
+    /// Name of the running pass, for the usage ledger
+    pub pass_name: String,
+    /// Provider that answered this pass's last completion, for history
+    used_provider: Arc<Mutex<Option<String>>>,
 }
 
 impl<'a> PassContext<'a> {
     pub fn new(project_root: &'a Path) -> Self {
+        Self::with_services(project_root, &PassServices::default())
+    }
+
+    pub fn with_services(project_root: &'a Path, services: &PassServices) -> Self {
         Self {
             project_root,
             artifacts: HashMap::new(),
+            config: Arc::clone(&services.config),
+            prompts: Arc::clone(&services.prompts),
+            ai: Arc::clone(&services.ai),
+            overrides: PassOverrides::default(),
+            pass_name: String::new(),
+            used_provider: Arc::new(Mutex::new(None)),
         }
     }
 
-    /// Load an artifact from .process/ directory
+    /// Provider, model and limits for a completion by this pass rendered
+    /// from `template`: the pipeline overrides where set, else `ai.routes`
+    pub fn route(&self, template: Option<&str>) -> Route {
+        let route = self.config.ai.route(&self.pass_name, template);
+        Route {
+            provider: self.overrides.provider.clone().or(route.provider),
+            model: self.overrides.model.clone().or(route.model),
+            max_tokens: self.overrides.max_tokens.or(route.max_tokens),
+            temperature: route.temperature,
+        }
+    }
+
+    /// The AI provider for this pass: the pipeline override or route if
+    /// any, otherwise `ai.provider` from the configuration. Completions
+    /// through it note which provider answered, fallbacks included, are
+    /// served from the response cache when it is on, and are added to the
+    /// project's usage ledger.
+    pub async fn provider(&self) -> Result<Arc<dyn AiProvider>> {
+        self.provider_for(None).await
+    }
+
+    /// `provider`, routed for completions rendered from `template`
+    pub async fn provider_for(&self, template: Option<&str>) -> Result<Arc<dyn AiProvider>> {
+        let name = self.route(template).provider.unwrap_or_else(|| self.config.ai.provider.clone());
+        let mut provider = self.ai.get_provider(&name).await?;
+        // Outside a project there is no cache or ledger
+        if let Ok(state) = ProcessState::load(self.project_root) {
+            provider = ResponseCache::for_project(self.project_root, self.config.ai.cache).wrap(provider);
+            let scope = UsageScope { phase: state.current_phase, pass: self.pass_name.clone(), branch: None };
+            provider = usage::meter(provider, self.project_root, &self.config.ai, scope);
+        }
+        Ok(Arc::new(RecordingProvider {
+            inner: provider,
+            used: Arc::clone(&self.used_provider),
+        }))
+    }
+
+    /// The provider that produced this pass's output, if it used one
+    pub fn used_provider(&self) -> Option<String> {
+        self.used_provider.lock().unwrap().clone()
+    }
+
+    /// Load an artifact from .process/ directory, checked against its schema
     pub fn load_artifact(&mut self, kind: &ArtifactKind) -> Result<String> {
-        let filename = self.artifact_filename(kind);
-        let path = self.project_root.join(".process").join(&filename);
+        let path = self.artifact_path(kind);
         let content = std::fs::read_to_string(&path)
             .map_err(|_| anyhow::anyhow!("Artifact '{}' not found at {}", kind, path.display()))?;
+        schema::validate(kind, &self.artifact_filename(kind), &content).map_err(|e| anyhow::Error::new(*e))?;
         self.artifacts.insert(kind.clone(), content.clone());
         Ok(content)
     }
 
-    /// Save an artifact to .process/ directory
+    /// Get an artifact that was loaded (or saved) during this run
+    pub fn artifact(&self, kind: &ArtifactKind) -> Option<&str> {
+        self.artifacts.get(kind).map(|s| s.as_str())
+    }
+
+    /// Absolute path of an artifact file
+    pub fn artifact_path(&self, kind: &ArtifactKind) -> PathBuf {
+        artifacts::path(self.project_root, kind)
+    }
+
+    /// Save an artifact to .process/ directory. Content that fails the
+    /// artifact's schema is not saved; it is kept next to it as
+    /// `<file>.rejected` for inspection instead.
     pub fn save_artifact(&mut self, kind: &ArtifactKind, content: &str) -> Result<()> {
-        let filename = self.artifact_filename(kind);
-        let path = self.project_root.join(".process").join(&filename);
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)?;
+        let path = self.artifact_path(kind);
+        if let Err(err) = schema::validate(kind, &self.artifact_filename(kind), content) {
+            let rejected = path.with_extension("yaml.rejected");
+            artifacts::write(&rejected, content)?;
+            return Err(anyhow::Error::new(*err)
+                .context(format!("Refusing to save invalid '{}' (kept at {})", kind, rejected.display())));
         }
-        std::fs::write(&path, content)?;
+        artifacts::write(&path, content)?;
         self.artifacts.insert(kind.clone(), content.to_string());
         Ok(())
     }
 
-    /// Map ArtifactKind to filename
-    fn artifact_filename(&self, kind: &ArtifactKind) -> String {
-        match kind {
-            ArtifactKind::Seed => "seed.yaml".to_string(),
-            ArtifactKind::Proposals => "proposals.yaml".to_string(),
-            ArtifactKind::Rules => "rules.yaml".to_string(),
-            ArtifactKind::Skeleton => "skeleton.yaml".to_string(),
-            ArtifactKind::DecisionLog => "decision_log.yaml".to_string(),
-            ArtifactKind::Learnings => "learnings.yaml".to_string(),
-            ArtifactKind::Friction => "friction.yaml".to_string(),
-            ArtifactKind::BranchHypothesis(name) => format!("branches/{}/hypothesis.yaml", name),
-            ArtifactKind::BranchReview(name) => format!("branches/{}/review.yaml", name),
-            ArtifactKind::Postmortem => "postmortem.yaml".to_string(),
-            ArtifactKind::Custom(name) => format!("{}.yaml", name),
-        }
+    /// Filename relative to .process/, as assigned by the artifact registry
+    pub fn artifact_filename(&self, kind: &ArtifactKind) -> String {
+        artifacts::relative_path(kind)
     }
 }
 
 /// The core Pass trait — all process steps implement this
+#[async_trait]
 pub trait Pass: Send + Sync {
     /// Unique name (e.g., "diverge.generate")
-    fn name(&self) -> &'static str;
+    fn name(&self) -> &str;
 
     /// What this pass needs to run
     fn requires(&self) -> Vec<ArtifactKind>;
 
+    /// Artifacts this pass reads when present, but can run without.
+    /// They still order the pass after their producers.
+    fn optional_requires(&self) -> Vec<ArtifactKind> {
+        Vec::new()
+    }
+
     /// What this pass produces
     fn produces(&self) -> Vec<ArtifactKind>;
 
@@ -119,8 +344,54 @@ pub trait Pass: Send + Sync {
     fn kind(&self) -> PassKind;
 
     /// Human-readable description
-    fn description(&self) -> &'static str;
+    fn description(&self) -> &str;
 
     /// Execute the pass
-    fn run(&self, ctx: &mut PassContext) -> Result<()>;
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_artifact_kind_round_trips_through_name() {
+        for kind in [
+            ArtifactKind::Seed,
+            ArtifactKind::DecisionLog,
+            ArtifactKind::BranchHypothesis("cache".to_string()),
+            ArtifactKind::BranchReview("cache".to_string()),
+            ArtifactKind::BranchImplementation("feature.x".to_string()),
+            ArtifactKind::Custom("gap-report".to_string()),
+        ] {
+            assert_eq!(kind.to_string().parse::<ArtifactKind>().unwrap(), kind);
+        }
+        assert!("nonsense".parse::<ArtifactKind>().is_err());
+        assert!("custom.".parse::<ArtifactKind>().is_err());
+    }
+
+    #[test]
+    fn test_pipeline_overrides_win_over_routes() {
+        let mut config = Config::default();
+        config.ai.routes = vec![process_config::config::RouteRule {
+            pattern: "diverge.*".into(),
+            provider: Some("ollama".into()),
+            model: Some("llama3".into()),
+            max_tokens: Some(1024),
+            temperature: Some(0.7),
+        }];
+        let services = PassServices { config: Arc::new(config), ..PassServices::default() };
+        let mut ctx = PassContext::with_services(Path::new("."), &services);
+        ctx.pass_name = "diverge.generate".into();
+        ctx.overrides.model = Some("llama3.1:70b".into());
+
+        let route = ctx.route(Some("diverge"));
+        assert_eq!(route.provider.as_deref(), Some("ollama"));
+        assert_eq!(route.model.as_deref(), Some("llama3.1:70b"));
+        assert_eq!((route.max_tokens, route.temperature), (Some(1024), Some(0.7)));
+
+        ctx.pass_name = "converge.analyze".into();
+        assert_eq!(ctx.route(Some("converge")).model.as_deref(), Some("llama3.1:70b"));
+        assert!(ctx.route(Some("converge")).provider.is_none());
+    }
 }
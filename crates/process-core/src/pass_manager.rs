@@ -1,40 +1,125 @@
-use crate::pass::{Pass, PassContext};
-use crate::manifest::Manifest;
+use crate::pass::{Pass, PassContext, PassKind, PassOverrides, PassServices};
+use crate::pipeline::Pipeline;
+use crate::history;
+use crate::manifest::{hash_content, Manifest};
 use anyhow::{Result, anyhow};
-use std::collections::HashMap;
-use std::path::Path;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::task::JoinSet;
+
+/// How `run_all` schedules passes
+#[derive(Debug, Clone, Copy)]
+pub struct RunOptions {
+    /// Skip passes whose inputs are unchanged since their last run
+    pub incremental: bool,
+    /// Maximum passes in flight at once; interactive passes always run alone
+    pub jobs: usize,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self { incremental: true, jobs: 1 }
+    }
+}
+
+/// One row of `PassManager::plan`: what a pass reads and writes and whether
+/// an incremental `run_all` would execute it
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanEntry {
+    pub name: String,
+    pub kind: PassKind,
+    pub requires: Vec<String>,
+    pub optional_requires: Vec<String>,
+    pub produces: Vec<String>,
+    /// Passes that produce this pass's inputs
+    pub depends_on: Vec<String>,
+    /// Whether an incremental run would execute this pass
+    pub will_run: bool,
+    /// Why it runs or is skipped
+    pub reason: String,
+}
 
 /// The PassManager registers, resolves dependencies, and executes passes
 pub struct PassManager {
-    passes: HashMap<String, Box<dyn Pass>>,
+    passes: BTreeMap<String, Arc<dyn Pass>>,
     execution_order: Vec<String>,
+    services: PassServices,
+    /// Held while a finished pass records its outputs, so concurrent
+    /// passes never overwrite each other's manifest entries
+    manifest_lock: Arc<Mutex<()>>,
+    /// Pipeline overrides by pass name
+    overrides: HashMap<String, PassOverrides>,
+}
+
+impl Default for PassManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PassManager {
     pub fn new() -> Self {
+        Self::with_services(PassServices::default())
+    }
+
+    /// Manager whose passes share the given config, AI registry and prompts
+    pub fn with_services(services: PassServices) -> Self {
         Self {
-            passes: HashMap::new(),
+            passes: BTreeMap::new(),
             execution_order: Vec::new(),
+            services,
+            manifest_lock: Arc::new(Mutex::new(())),
+            overrides: HashMap::new(),
         }
     }
 
-    /// Register a pass
+    /// Register a pass (replaces any pass with the same name)
     pub fn register<P: Pass + 'static>(&mut self, pass: P) {
         let name = pass.name().to_string();
-        self.passes.insert(name, Box::new(pass));
+        self.passes.insert(name, Arc::new(pass));
+    }
+
+    /// Look up a registered pass by name
+    pub fn get(&self, name: &str) -> Option<&dyn Pass> {
+        self.passes.get(name).map(|p| p.as_ref())
+    }
+
+    /// Override provider/model/max_tokens for one pass
+    pub fn set_overrides(&mut self, name: &str, overrides: PassOverrides) {
+        self.overrides.insert(name.to_string(), overrides);
     }
 
     /// List all registered passes
     pub fn list_passes(&self) -> Vec<(&str, &str)> {
-        let mut passes: Vec<_> = self.passes.values()
+        self.passes.values()
             .map(|p| (p.name(), p.description()))
-            .collect();
-        passes.sort_by_key(|(name, _)| *name);
-        passes
+            .collect()
+    }
+
+    /// The order computed by the last `resolve_order` call
+    pub fn execution_order(&self) -> &[String] {
+        &self.execution_order
     }
 
-    /// Resolve execution order using topological sort
+    /// Resolve execution order using topological sort. Each artifact must
+    /// have a single producer: the manifest, incremental runs and the
+    /// scheduler all key on it.
     pub fn resolve_order(&mut self) -> Result<()> {
+        let mut producers: HashMap<String, &str> = HashMap::new();
+        for (name, pass) in &self.passes {
+            for prod in pass.produces() {
+                if let Some(other) = producers.insert(prod.to_string(), name) {
+                    return Err(anyhow!(
+                        "Artifact '{}' is produced by both '{}' and '{}'",
+                        prod, other, name
+                    ));
+                }
+            }
+        }
+
         let mut order = Vec::new();
         let mut visited = HashMap::new();
 
@@ -61,17 +146,9 @@ impl PassManager {
 
         visited.insert(name.to_string(), true); // Mark in progress
 
-        // Visit dependencies
-        if let Some(pass) = self.passes.get(name) {
-            let requires = pass.requires();
-            for req in &requires {
-                // Find which pass produces this artifact
-                for (pname, p) in &self.passes {
-                    if p.produces().contains(req) && pname != name {
-                        self.visit(pname, visited, order)?;
-                    }
-                }
-            }
+        // Visit dependencies (required and optional inputs both order the pass)
+        for dep in self.dependencies(name) {
+            self.visit(&dep, visited, order)?;
         }
 
         visited.insert(name.to_string(), false); // Mark done
@@ -81,54 +158,227 @@ impl PassManager {
         Ok(())
     }
 
+    /// Passes producing any of this pass's required or optional inputs
+    pub fn dependencies(&self, name: &str) -> Vec<String> {
+        let Some(pass) = self.passes.get(name) else {
+            return Vec::new();
+        };
+        let mut inputs = pass.requires();
+        inputs.extend(pass.optional_requires());
+
+        self.passes.iter()
+            .filter(|(pname, p)| {
+                pname.as_str() != name && p.produces().iter().any(|prod| inputs.contains(prod))
+            })
+            .map(|(pname, _)| pname.clone())
+            .collect()
+    }
+
+    /// Dry run of an incremental `run_all`: execution order, artifacts read
+    /// and written, and which passes would run. A pass whose upstream runs is
+    /// reported as running too, since its inputs are about to change.
+    pub fn plan(&mut self, project_root: &Path) -> Result<Vec<PlanEntry>> {
+        self.resolve_order()?;
+        let manifest = Manifest::load(project_root)?;
+
+        let mut running: HashSet<String> = HashSet::new();
+        let mut entries = Vec::new();
+
+        for name in &self.execution_order {
+            let pass = &self.passes[name];
+            let depends_on = self.dependencies(name);
+
+            let upstream: Vec<&String> = depends_on.iter().filter(|d| running.contains(*d)).collect();
+            let (will_run, reason) = if pass.produces().is_empty() {
+                (true, "always runs (produces nothing)".to_string())
+            } else if !upstream.is_empty() {
+                let names: Vec<&str> = upstream.iter().map(|s| s.as_str()).collect();
+                (true, format!("upstream re-runs: {}", names.join(", ")))
+            } else if self.is_up_to_date(name, project_root, &manifest) {
+                (false, "up to date".to_string())
+            } else if pass.produces().iter().any(|p| !manifest.artifacts.contains_key(&p.to_string())) {
+                (true, "never run".to_string())
+            } else if let Some(s) = pass.produces().iter()
+                .find_map(|p| manifest.artifacts[&p.to_string()].superseded.as_ref())
+            {
+                (true, format!("superseded: {} reopened", s.reopened.name()))
+            } else {
+                (true, "inputs changed".to_string())
+            };
+
+            if will_run {
+                running.insert(name.clone());
+            }
+
+            entries.push(PlanEntry {
+                name: name.clone(),
+                kind: pass.kind(),
+                requires: pass.requires().iter().map(|k| k.to_string()).collect(),
+                optional_requires: pass.optional_requires().iter().map(|k| k.to_string()).collect(),
+                produces: pass.produces().iter().map(|k| k.to_string()).collect(),
+                depends_on,
+                will_run,
+                reason,
+            });
+        }
+
+        Ok(entries)
+    }
+
     /// Run a single pass by name
-    pub fn run_pass(&self, name: &str, project_root: &Path) -> Result<()> {
+    pub async fn run_pass(&self, name: &str, project_root: &Path) -> Result<()> {
         let pass = self.passes.get(name)
             .ok_or_else(|| anyhow!("Pass '{}' not found", name))?;
 
-        let mut ctx = PassContext::new(project_root);
-        let mut manifest = Manifest::load(project_root)?;
+        let overrides = self.overrides.get(name).cloned().unwrap_or_default();
+        execute_pass(pass.as_ref(), project_root, &self.services, overrides, &self.manifest_lock).await
+    }
 
-        // Load required artifacts
-        for req in pass.requires() {
-            if let Err(_) = ctx.load_artifact(&req) {
-                return Err(anyhow!(
-                    "Pass '{}' requires artifact '{}' which is not available. Run prerequisite passes first.",
-                    name, req
-                ));
-            }
+    /// Hashes of a pass's inputs as they are on disk now (missing optional
+    /// inputs are simply absent)
+    fn current_inputs(&self, pass: &dyn Pass, project_root: &Path) -> BTreeMap<String, String> {
+        let ctx = PassContext::with_services(project_root, &self.services);
+        pass.requires().into_iter()
+            .chain(pass.optional_requires())
+            .filter_map(|kind| {
+                let content = std::fs::read_to_string(ctx.artifact_path(&kind)).ok()?;
+                Some((kind.to_string(), hash_content(&content)))
+            })
+            .collect()
+    }
+
+    /// A pass is up to date when every artifact it produces is on disk and
+    /// was recorded by it from the inputs as they are now. Passes that
+    /// produce nothing (validators) are never up to date.
+    pub fn is_up_to_date(&self, name: &str, project_root: &Path, manifest: &Manifest) -> bool {
+        let Some(pass) = self.passes.get(name) else {
+            return false;
+        };
+        let produces = pass.produces();
+        if produces.is_empty() {
+            return false;
         }
 
-        // Execute
-        pass.run(&mut ctx)?;
+        let inputs = self.current_inputs(pass.as_ref(), project_root);
+        let ctx = PassContext::with_services(project_root, &self.services);
+        produces.iter().all(|prod| {
+            let name_matches = manifest.artifacts.get(&prod.to_string())
+                .is_some_and(|r| r.produced_by == name);
+            name_matches
+                && ctx.artifact_path(prod).exists()
+                && manifest.is_fresh(&prod.to_string(), &inputs)
+        })
+    }
 
-        // Record produced artifacts in manifest
-        for prod in pass.produces() {
-            if let Some(content) = ctx.artifacts.get(&prod) {
-                let filename = format!("{}.yaml", prod);
-                manifest.record_artifact(&prod.to_string(), name, &filename, content);
+    /// Run all passes in dependency order. When `incremental`, passes whose
+    /// inputs are unchanged since they last ran are skipped; anything
+    /// downstream of an edited artifact sees a new input hash and re-runs.
+    ///
+    /// Up to `jobs` passes whose dependencies have finished run at once.
+    /// An interactive pass waits for the others to drain and then runs
+    /// alone, so prompts never interleave with other output. On failure no
+    /// new passes start; those in flight finish before the error returns.
+    pub async fn run_all(&mut self, project_root: &Path, options: RunOptions) -> Result<()> {
+        self.resolve_order()?;
+        let all = self.execution_order.clone();
+        self.run_selected(&all, project_root, options).await
+    }
+
+    /// Run a pipeline's passes with its per-pass overrides. Only the listed
+    /// passes run; inputs from passes outside the pipeline must already exist.
+    pub async fn run_pipeline(&mut self, pipeline: &Pipeline, project_root: &Path, options: RunOptions) -> Result<()> {
+        for step in &pipeline.passes {
+            let name = step.pass_name();
+            if !self.passes.contains_key(name) {
+                return Err(anyhow!("Pipeline '{}' lists unknown pass '{}'", pipeline.name, name));
             }
+            self.set_overrides(name, step.overrides());
         }
 
-        manifest.save(project_root)?;
-        Ok(())
+        self.resolve_order()?;
+        self.run_selected(&pipeline.pass_names(), project_root, options).await
     }
 
-    /// Run all passes in dependency order
-    pub fn run_all(&mut self, project_root: &Path) -> Result<()> {
-        self.resolve_order()?;
+    /// Schedule the given passes by the resolved order; dependencies on
+    /// passes outside the selection are treated as satisfied. Callers
+    /// resolve the order first, which guarantees no two passes write the
+    /// same artifact, so passes without a dependency between them never
+    /// race on an output.
+    async fn run_selected(&self, selected: &[String], project_root: &Path, options: RunOptions) -> Result<()> {
+        let jobs = options.jobs.max(1);
+        let mut pending: Vec<String> = self.execution_order.iter()
+            .filter(|name| selected.contains(name))
+            .cloned()
+            .collect();
+        let mut finished: HashSet<String> = HashSet::new();
+        let mut running: JoinSet<(String, Result<()>)> = JoinSet::new();
+        let mut exclusive = false;
+        let mut failure: Option<anyhow::Error> = None;
 
-        let order = self.execution_order.clone();
-        for name in &order {
-            println!("  ▶ Running pass: {}", name);
-            self.run_pass(name, project_root)?;
+        loop {
+            // Start every ready pass the job limit allows
+            while failure.is_none() && !exclusive && running.len() < jobs {
+                let Some(idx) = pending.iter().position(|name| {
+                    self.dependencies(name).iter()
+                        .all(|d| finished.contains(d) || !selected.contains(d))
+                }) else {
+                    break;
+                };
+
+                let name = pending[idx].clone();
+                let pass = Arc::clone(&self.passes[&name]);
+                let interactive = pass.kind() == PassKind::Interactive;
+                if interactive && !running.is_empty() {
+                    break; // wait for the others to drain
+                }
+                pending.remove(idx);
+
+                if options.incremental && self.is_up_to_date(&name, project_root, &Manifest::load(project_root)?) {
+                    println!("  ⏭ Skipping pass: {} (up to date)", name);
+                    finished.insert(name);
+                    continue;
+                }
+
+                println!("  ▶ Running pass: {}", name);
+                exclusive = interactive;
+                let root: PathBuf = project_root.to_path_buf();
+                let services = self.services.clone();
+                let lock = Arc::clone(&self.manifest_lock);
+                let overrides = self.overrides.get(&name).cloned().unwrap_or_default();
+                running.spawn(async move {
+                    let result = execute_pass(pass.as_ref(), &root, &services, overrides, &lock).await;
+                    (name, result)
+                });
+            }
+
+            let Some(joined) = running.join_next().await else {
+                break;
+            };
+            exclusive = false;
+            match joined {
+                Ok((name, Ok(()))) => {
+                    finished.insert(name);
+                }
+                Ok((name, Err(e))) => {
+                    failure.get_or_insert(e.context(format!("Pass '{}' failed", name)));
+                }
+                Err(e) => {
+                    failure.get_or_insert(anyhow!("Pass task panicked: {}", e));
+                }
+            }
         }
 
-        Ok(())
+        match failure {
+            Some(e) => Err(e),
+            None if !pending.is_empty() => Err(anyhow!(
+                "Passes could not be scheduled: {}", pending.join(", ")
+            )),
+            None => Ok(()),
+        }
     }
 
     /// Run only the passes for a specific phase prefix (e.g., "diverge")
-    pub fn run_phase(&mut self, phase: &str, project_root: &Path) -> Result<()> {
+    pub async fn run_phase(&mut self, phase: &str, project_root: &Path) -> Result<()> {
         self.resolve_order()?;
 
         let matching: Vec<String> = self.execution_order.iter()
@@ -142,9 +392,664 @@ impl PassManager {
 
         for name in &matching {
             println!("  ▶ Running pass: {}", name);
-            self.run_pass(name, project_root)?;
+            self.run_pass(name, project_root).await?;
         }
 
         Ok(())
     }
 }
+
+/// Load a pass's inputs, run it and record what it produced
+async fn execute_pass(
+    pass: &dyn Pass,
+    project_root: &Path,
+    services: &PassServices,
+    overrides: PassOverrides,
+    manifest_lock: &Mutex<()>,
+) -> Result<()> {
+    let mut ctx = PassContext::with_services(project_root, services);
+    ctx.overrides = overrides;
+    ctx.pass_name = pass.name().to_string();
+
+    // Load required artifacts; present but invalid ones report their schema errors
+    for req in pass.requires() {
+        if !ctx.artifact_path(&req).exists() {
+            return Err(anyhow!(
+                "Pass '{}' requires artifact '{}' which is not available. Run prerequisite passes first.",
+                pass.name(), req
+            ));
+        }
+        ctx.load_artifact(&req)?;
+    }
+
+    // Optional artifacts are loaded when present
+    for opt in pass.optional_requires() {
+        if ctx.artifact_path(&opt).exists() {
+            ctx.load_artifact(&opt)?;
+        }
+    }
+
+    let inputs: BTreeMap<String, String> = ctx.artifacts.iter()
+        .map(|(kind, content)| (kind.to_string(), hash_content(content)))
+        .collect();
+
+    // Keep what is on disk now (including hand edits) before the pass
+    // overwrites it
+    {
+        let _guard = manifest_lock.lock().await;
+        let mut manifest = Manifest::load(project_root)?;
+        let mut saved = false;
+        for prod in pass.produces() {
+            saved |= history::snapshot(project_root, &mut manifest, &prod)?.is_some();
+        }
+        if saved {
+            manifest.save(project_root)?;
+        }
+    }
+
+    // Execute
+    pass.run(&mut ctx).await?;
+
+    // Record produced artifacts in manifest and history. Reload under the
+    // lock: passes running alongside may have recorded theirs meanwhile.
+    let _guard = manifest_lock.lock().await;
+    let mut manifest = Manifest::load(project_root)?;
+    let provider = ctx.used_provider();
+    for prod in pass.produces() {
+        if let Some(content) = ctx.artifacts.get(&prod) {
+            let filename = ctx.artifact_filename(&prod);
+            manifest.record_artifact(&prod.to_string(), pass.name(), &filename, content, inputs.clone());
+            if let Some(record) = manifest.artifacts.get_mut(&prod.to_string()) {
+                record.provider = provider.clone();
+            }
+            history::record(project_root, &mut manifest, &prod.to_string(), content, pass.name(), provider.as_deref())?;
+        }
+    }
+
+    manifest.save(project_root)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass::{ArtifactKind, PassKind};
+    use async_trait::async_trait;
+    use process_ai::provider::{AiProvider, CompletionRequest, CompletionResponse};
+    use process_ai::registry::AiRegistry;
+    use process_config::config::Config;
+    use std::sync::Arc;
+
+    /// Minimal content satisfying `kind`'s schema, tagged with a comment
+    fn fixture(kind: &ArtifactKind, tag: &str) -> String {
+        let body = match kind {
+            ArtifactKind::Seed => "idea: test\ntarget_user: devs\nconstraints: [c]\nnon_goals: [n]\nsuccess_criteria: [s]\nreversibility_budget: medium\n",
+            ArtifactKind::Proposals => "proposals:\n  - {name: a, architecture: x, tradeoffs: [t], risks: [r]}\n  - {name: b, architecture: y, tradeoffs: [t], risks: [r]}\n",
+            ArtifactKind::Rules => "invariants:\n  - {id: INV-1, rule: r, rationale: why}\nconflict_resolution: {policy: human_final_say}\nselected_approach: {name: a, rationale: why}\n",
+            ArtifactKind::Skeleton => "files:\n  - path: README.md\n",
+            _ => "",
+        };
+        format!("# {}\n{}", tag, body)
+    }
+
+    /// The tag a fixture was written with
+    fn tag(content: &str) -> &str {
+        content.lines().next().unwrap_or_default().trim_start_matches("# ")
+    }
+
+    fn write_fixture(root: &Path, kind: &ArtifactKind, tag: &str) {
+        let filename = PassContext::new(root).artifact_filename(kind);
+        std::fs::write(root.join(".process").join(filename), fixture(kind, tag)).unwrap();
+    }
+
+    /// Test pass that writes a fixture tagged "<name>" into each artifact it produces
+    struct StubPass {
+        name: &'static str,
+        requires: Vec<ArtifactKind>,
+        optional: Vec<ArtifactKind>,
+        produces: Vec<ArtifactKind>,
+    }
+
+    impl StubPass {
+        fn new(name: &'static str, requires: Vec<ArtifactKind>, produces: Vec<ArtifactKind>) -> Self {
+            Self { name, requires, optional: Vec::new(), produces }
+        }
+    }
+
+    #[async_trait]
+    impl Pass for StubPass {
+        fn name(&self) -> &'static str { self.name }
+        fn requires(&self) -> Vec<ArtifactKind> { self.requires.clone() }
+        fn optional_requires(&self) -> Vec<ArtifactKind> { self.optional.clone() }
+        fn produces(&self) -> Vec<ArtifactKind> { self.produces.clone() }
+        fn kind(&self) -> PassKind { PassKind::Sync }
+        fn description(&self) -> &'static str { "stub" }
+
+        async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+            for kind in &self.produces {
+                ctx.save_artifact(kind, &fixture(kind, self.name))?;
+            }
+            Ok(())
+        }
+    }
+
+    fn position(manager: &PassManager, name: &str) -> usize {
+        manager.execution_order().iter().position(|n| n == name).unwrap()
+    }
+
+    #[test]
+    fn test_resolve_order_follows_artifacts() {
+        let mut manager = PassManager::new();
+        manager.register(StubPass::new("c.skeleton", vec![ArtifactKind::Rules], vec![ArtifactKind::Skeleton]));
+        manager.register(StubPass::new("b.rules", vec![ArtifactKind::Proposals], vec![ArtifactKind::Rules]));
+        manager.register(StubPass::new("a.proposals", vec![ArtifactKind::Seed], vec![ArtifactKind::Proposals]));
+
+        manager.resolve_order().unwrap();
+        assert_eq!(manager.execution_order(), ["a.proposals", "b.rules", "c.skeleton"]);
+    }
+
+    #[test]
+    fn test_optional_inputs_order_passes() {
+        let mut manager = PassManager::new();
+        let mut report = StubPass::new("a.report", vec![], vec![ArtifactKind::Custom("report".into())]);
+        report.optional = vec![ArtifactKind::Skeleton];
+        manager.register(report);
+        manager.register(StubPass::new("z.scan", vec![], vec![ArtifactKind::Skeleton]));
+
+        manager.resolve_order().unwrap();
+        assert!(position(&manager, "z.scan") < position(&manager, "a.report"));
+    }
+
+    #[test]
+    fn test_circular_dependency_errors() {
+        let mut manager = PassManager::new();
+        manager.register(StubPass::new("a", vec![ArtifactKind::Rules], vec![ArtifactKind::Proposals]));
+        manager.register(StubPass::new("b", vec![ArtifactKind::Proposals], vec![ArtifactKind::Rules]));
+
+        let err = manager.resolve_order().unwrap_err();
+        assert!(err.to_string().contains("Circular dependency"));
+    }
+
+    #[test]
+    fn test_duplicate_producers_error() {
+        let mut manager = PassManager::new();
+        manager.register(StubPass::new("a.rules", vec![], vec![ArtifactKind::Rules]));
+        manager.register(StubPass::new("b.rules", vec![ArtifactKind::Seed], vec![ArtifactKind::Rules]));
+
+        let err = manager.resolve_order().unwrap_err();
+        assert_eq!(err.to_string(), "Artifact 'rules' is produced by both 'a.rules' and 'b.rules'");
+    }
+
+    #[tokio::test]
+    async fn test_run_pass_missing_requirement_errors() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+
+        let mut manager = PassManager::new();
+        manager.register(StubPass::new("b.rules", vec![ArtifactKind::Proposals], vec![ArtifactKind::Rules]));
+
+        let err = manager.run_pass("b.rules", tmp.path()).await.unwrap_err();
+        assert!(err.to_string().contains("requires artifact 'proposals'"));
+    }
+
+    #[tokio::test]
+    async fn test_run_all_writes_artifacts_and_manifest() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+        write_fixture(tmp.path(), &ArtifactKind::Seed, "seed");
+
+        let mut manager = PassManager::new();
+        manager.register(StubPass::new("b.rules", vec![ArtifactKind::Proposals], vec![ArtifactKind::Rules]));
+        manager.register(StubPass::new("a.proposals", vec![ArtifactKind::Seed], vec![ArtifactKind::Proposals]));
+        manager.run_all(tmp.path(), RunOptions { incremental: false, jobs: 1 }).await.unwrap();
+
+        let rules = std::fs::read_to_string(tmp.path().join(".process/rules.yaml")).unwrap();
+        assert_eq!(tag(&rules), "b.rules");
+
+        let manifest = Manifest::load(tmp.path()).unwrap();
+        let record = manifest.artifacts.get("proposals").unwrap();
+        assert_eq!(record.produced_by, "a.proposals");
+        assert_eq!(record.path, "diverge_summary.yaml");
+    }
+
+    struct EchoProvider;
+
+    #[async_trait]
+    impl AiProvider for EchoProvider {
+        fn name(&self) -> &'static str { "echo" }
+        fn priority(&self) -> u8 { 50 }
+        async fn is_available(&self) -> bool { true }
+        async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+            Ok(CompletionResponse { content: format!("echo: {}", request.transcript()), usage: None, provider: None, model: None, cached: false })
+        }
+    }
+
+    /// AI pass that sends the seed to the configured provider and saves
+    /// the reply as `custom.echo`
+    struct EchoPass;
+
+    #[async_trait]
+    impl Pass for EchoPass {
+        fn name(&self) -> &'static str { "diverge.echo" }
+        fn requires(&self) -> Vec<ArtifactKind> { vec![ArtifactKind::Seed] }
+        fn produces(&self) -> Vec<ArtifactKind> { vec![ArtifactKind::Custom("echo".into())] }
+        fn kind(&self) -> PassKind { PassKind::AiAssisted }
+        fn description(&self) -> &'static str { "echo" }
+
+        async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+            let seed = ctx.artifact(&ArtifactKind::Seed).unwrap_or_default().to_string();
+            let provider = ctx.provider().await?;
+            let response = provider.complete(&CompletionRequest::new(seed)).await?;
+            ctx.save_artifact(&ArtifactKind::Custom("echo".into()), &response.content)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ai_pass_uses_configured_provider() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+        write_fixture(tmp.path(), &ArtifactKind::Seed, "seed");
+
+        let mut config = Config::default();
+        config.ai.provider = "echo".to_string();
+        let mut registry = AiRegistry::new();
+        registry.register(EchoProvider);
+        let services = PassServices {
+            ai: Arc::new(registry),
+            ..PassServices::from_config(tmp.path(), config)
+        };
+
+        let mut manager = PassManager::with_services(services);
+        manager.register(EchoPass);
+        manager.run_pass("diverge.echo", tmp.path()).await.unwrap();
+
+        let echo = std::fs::read_to_string(tmp.path().join(".process/echo.yaml")).unwrap();
+        assert_eq!(echo, format!("echo: {}", fixture(&ArtifactKind::Seed, "seed")));
+    }
+
+    #[tokio::test]
+    async fn test_reruns_keep_prior_versions_in_history() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+        write_fixture(tmp.path(), &ArtifactKind::Seed, "v1");
+
+        let mut config = Config::default();
+        config.ai.provider = "echo".to_string();
+        let mut registry = AiRegistry::new();
+        registry.register(EchoProvider);
+        let services = PassServices {
+            ai: Arc::new(registry),
+            ..PassServices::from_config(tmp.path(), config)
+        };
+        let mut manager = PassManager::with_services(services);
+        manager.register(EchoPass);
+
+        manager.run_pass("diverge.echo", tmp.path()).await.unwrap();
+        // Hand edit, then a re-run from a new seed: neither is lost
+        std::fs::write(tmp.path().join(".process/echo.yaml"), "edited").unwrap();
+        write_fixture(tmp.path(), &ArtifactKind::Seed, "v2");
+        manager.run_pass("diverge.echo", tmp.path()).await.unwrap();
+
+        let manifest = Manifest::load(tmp.path()).unwrap();
+        let versions = history::versions(&manifest, "custom.echo");
+        let by: Vec<&str> = versions.iter().map(|v| v.produced_by.as_str()).collect();
+        assert_eq!(by, ["diverge.echo", history::MANUAL, "diverge.echo"]);
+        assert_eq!(versions[0].provider.as_deref(), Some("echo"));
+        assert_eq!(versions[1].provider, None);
+        assert!(history::read(tmp.path(), &manifest, "custom.echo", 1).unwrap().contains("# v1"));
+        assert_eq!(history::read(tmp.path(), &manifest, "custom.echo", 2).unwrap(), "edited");
+    }
+
+    #[tokio::test]
+    async fn test_fallback_provider_is_recorded() {
+        struct DownProvider;
+
+        #[async_trait]
+        impl AiProvider for DownProvider {
+            fn name(&self) -> &'static str { "down" }
+            fn priority(&self) -> u8 { 90 }
+            async fn is_available(&self) -> bool { true }
+            async fn complete(&self, _request: &CompletionRequest) -> Result<CompletionResponse> {
+                Err(anyhow::anyhow!("connection refused"))
+            }
+        }
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+        write_fixture(tmp.path(), &ArtifactKind::Seed, "seed");
+
+        let mut config = Config::default();
+        config.ai.provider = "down".to_string();
+        let mut registry = AiRegistry::new();
+        registry.register(DownProvider);
+        registry.register(EchoProvider);
+        registry.set_fallback(vec!["echo".to_string()]);
+        let services = PassServices {
+            ai: Arc::new(registry),
+            ..PassServices::from_config(tmp.path(), config)
+        };
+        let mut manager = PassManager::with_services(services);
+        manager.register(EchoPass);
+        manager.run_pass("diverge.echo", tmp.path()).await.unwrap();
+
+        let manifest = Manifest::load(tmp.path()).unwrap();
+        assert_eq!(manifest.artifacts["custom.echo"].provider.as_deref(), Some("echo"));
+        assert_eq!(history::versions(&manifest, "custom.echo")[0].provider.as_deref(), Some("echo"));
+    }
+
+    /// Stub that counts its runs
+    struct CountingPass {
+        inner: StubPass,
+        runs: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Pass for CountingPass {
+        fn name(&self) -> &'static str { self.inner.name }
+        fn requires(&self) -> Vec<ArtifactKind> { self.inner.requires.clone() }
+        fn produces(&self) -> Vec<ArtifactKind> { self.inner.produces.clone() }
+        fn kind(&self) -> PassKind { PassKind::Sync }
+        fn description(&self) -> &'static str { "counting" }
+
+        async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+            self.runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            // Output depends on input so edits propagate downstream
+            let input = self.inner.requires.iter()
+                .filter_map(|k| ctx.artifact(k).map(tag))
+                .collect::<Vec<_>>()
+                .join("+");
+            for kind in &self.inner.produces {
+                ctx.save_artifact(kind, &fixture(kind, &format!("{}({})", self.inner.name, input)))?;
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_incremental_run_all_skips_unchanged_and_reruns_downstream() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+        write_fixture(tmp.path(), &ArtifactKind::Seed, "v1");
+
+        let proposals_runs = Arc::new(AtomicUsize::new(0));
+        let rules_runs = Arc::new(AtomicUsize::new(0));
+        let mut manager = PassManager::new();
+        manager.register(CountingPass {
+            inner: StubPass::new("a.proposals", vec![ArtifactKind::Seed], vec![ArtifactKind::Proposals]),
+            runs: Arc::clone(&proposals_runs),
+        });
+        manager.register(CountingPass {
+            inner: StubPass::new("b.rules", vec![ArtifactKind::Proposals], vec![ArtifactKind::Rules]),
+            runs: Arc::clone(&rules_runs),
+        });
+
+        manager.run_all(tmp.path(), RunOptions::default()).await.unwrap();
+        manager.run_all(tmp.path(), RunOptions::default()).await.unwrap();
+        assert_eq!(proposals_runs.load(Ordering::SeqCst), 1);
+        assert_eq!(rules_runs.load(Ordering::SeqCst), 1);
+
+        // Editing the seed re-runs everything downstream of it
+        write_fixture(tmp.path(), &ArtifactKind::Seed, "v2");
+        manager.run_all(tmp.path(), RunOptions::default()).await.unwrap();
+        assert_eq!(proposals_runs.load(Ordering::SeqCst), 2);
+        assert_eq!(rules_runs.load(Ordering::SeqCst), 2);
+        let rules = std::fs::read_to_string(tmp.path().join(".process/rules.yaml")).unwrap();
+        assert_eq!(tag(&rules), "b.rules(a.proposals(v2))");
+
+        // Editing a mid-pipeline artifact only re-runs its consumers
+        write_fixture(tmp.path(), &ArtifactKind::Proposals, "hand edited");
+        manager.run_all(tmp.path(), RunOptions::default()).await.unwrap();
+        assert_eq!(proposals_runs.load(Ordering::SeqCst), 2);
+        assert_eq!(rules_runs.load(Ordering::SeqCst), 3);
+
+        // Non-incremental runs ignore the manifest
+        manager.run_all(tmp.path(), RunOptions { incremental: false, jobs: 1 }).await.unwrap();
+        assert_eq!(proposals_runs.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_second_run_all_without_changes_runs_nothing() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+        write_fixture(tmp.path(), &ArtifactKind::Seed, "v1");
+
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut manager = PassManager::new();
+        for (name, requires, produces) in [
+            ("a.proposals", vec![ArtifactKind::Seed], ArtifactKind::Proposals),
+            ("b.rules", vec![ArtifactKind::Proposals], ArtifactKind::Rules),
+            ("c.skeleton", vec![ArtifactKind::Proposals, ArtifactKind::Rules], ArtifactKind::Skeleton),
+        ] {
+            manager.register(CountingPass {
+                inner: StubPass::new(name, requires, vec![produces]),
+                runs: Arc::clone(&runs),
+            });
+        }
+
+        manager.run_all(tmp.path(), RunOptions { incremental: true, jobs: 2 }).await.unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+
+        manager.run_all(tmp.path(), RunOptions { incremental: true, jobs: 2 }).await.unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+        assert!(manager.plan(tmp.path()).unwrap().iter().all(|e| !e.will_run));
+    }
+
+    #[tokio::test]
+    async fn test_plan_reports_stale_and_upstream_runs() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+        write_fixture(tmp.path(), &ArtifactKind::Seed, "v1");
+
+        let mut manager = PassManager::new();
+        manager.register(StubPass::new("a.proposals", vec![ArtifactKind::Seed], vec![ArtifactKind::Proposals]));
+        manager.register(StubPass::new("b.rules", vec![ArtifactKind::Proposals], vec![ArtifactKind::Rules]));
+        manager.register(StubPass::new("c.validate", vec![ArtifactKind::Rules], vec![]));
+
+        let plan = manager.plan(tmp.path()).unwrap();
+        assert!(plan.iter().all(|e| e.will_run));
+        assert_eq!(plan[0].reason, "never run");
+        assert_eq!(plan[1].depends_on, ["a.proposals"]);
+
+        manager.run_all(tmp.path(), RunOptions { incremental: false, jobs: 1 }).await.unwrap();
+        let plan = manager.plan(tmp.path()).unwrap();
+        assert!(!plan[0].will_run && !plan[1].will_run);
+        assert!(plan[2].will_run);
+
+        write_fixture(tmp.path(), &ArtifactKind::Seed, "v2");
+        let plan = manager.plan(tmp.path()).unwrap();
+        assert_eq!(plan[0].reason, "inputs changed");
+        assert_eq!(plan[1].reason, "upstream re-runs: a.proposals");
+
+        // Reopening converge supersedes rules even though its inputs match
+        manager.run_all(tmp.path(), RunOptions::default()).await.unwrap();
+        let mut manifest = Manifest::load(tmp.path()).unwrap();
+        manifest.supersede(crate::phase::Phase::Converge, "wrong call");
+        manifest.save(tmp.path()).unwrap();
+        let plan = manager.plan(tmp.path()).unwrap();
+        assert!(!plan[0].will_run);
+        assert_eq!(plan[1].reason, "superseded: converge reopened");
+    }
+
+    /// Pass that waits on a shared barrier, tracking peak concurrency
+    struct GatePass {
+        name: &'static str,
+        kind: PassKind,
+        produces: ArtifactKind,
+        barrier: Option<Arc<tokio::sync::Barrier>>,
+        active: Arc<std::sync::atomic::AtomicUsize>,
+        peak: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Pass for GatePass {
+        fn name(&self) -> &'static str { self.name }
+        fn requires(&self) -> Vec<ArtifactKind> { vec![] }
+        fn produces(&self) -> Vec<ArtifactKind> { vec![self.produces.clone()] }
+        fn kind(&self) -> PassKind { self.kind.clone() }
+        fn description(&self) -> &'static str { "gate" }
+
+        async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+            use std::sync::atomic::Ordering;
+            let now = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            if let Some(barrier) = &self.barrier {
+                barrier.wait().await;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            self.active.fetch_sub(1, Ordering::SeqCst);
+            ctx.save_artifact(&self.produces, &fixture(&self.produces, self.name))
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_run_all_runs_independent_passes_concurrently() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+
+        // Each pass waits for the other: only completes if both run at once
+        let barrier = Arc::new(tokio::sync::Barrier::new(2));
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let mut manager = PassManager::new();
+        for (name, produces) in [("scan.a", ArtifactKind::Skeleton), ("scan.b", ArtifactKind::Seed)] {
+            manager.register(GatePass {
+                name,
+                kind: PassKind::Sync,
+                produces,
+                barrier: Some(Arc::clone(&barrier)),
+                active: Arc::clone(&active),
+                peak: Arc::clone(&peak),
+            });
+        }
+
+        let run = manager.run_all(tmp.path(), RunOptions { incremental: false, jobs: 2 });
+        tokio::time::timeout(std::time::Duration::from_secs(5), run).await
+            .expect("independent passes did not run concurrently")
+            .unwrap();
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+
+        // Both outputs recorded despite finishing together
+        let manifest = Manifest::load(tmp.path()).unwrap();
+        assert_eq!(manifest.artifacts["skeleton"].produced_by, "scan.a");
+        assert_eq!(manifest.artifacts["seed"].produced_by, "scan.b");
+    }
+
+    #[tokio::test]
+    async fn test_run_all_refuses_overlapping_producers_before_running() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+
+        let mut manager = PassManager::new();
+        manager.register(StubPass::new("a.scan", vec![], vec![ArtifactKind::Skeleton]));
+        manager.register(StubPass::new("b.scan", vec![], vec![ArtifactKind::Seed, ArtifactKind::Skeleton]));
+
+        let err = manager.run_all(tmp.path(), RunOptions { incremental: false, jobs: 2 }).await.unwrap_err();
+        assert!(err.to_string().contains("produced by both 'a.scan' and 'b.scan'"));
+        assert!(!tmp.path().join(".process/skeleton.yaml").exists());
+        assert!(!tmp.path().join(".process/seed.yaml").exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_run_all_serializes_interactive_passes() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let mut manager = PassManager::new();
+        for (name, kind, produces) in [
+            ("a.scan", PassKind::Sync, ArtifactKind::Skeleton),
+            ("b.challenge", PassKind::Interactive, ArtifactKind::Custom("challenge".into())),
+            ("c.scan", PassKind::Sync, ArtifactKind::Seed),
+        ] {
+            manager.register(GatePass {
+                name,
+                kind,
+                produces,
+                barrier: None,
+                active: Arc::clone(&active),
+                peak: Arc::clone(&peak),
+            });
+        }
+
+        manager.run_all(tmp.path(), RunOptions { incremental: false, jobs: 4 }).await.unwrap();
+        // a.scan runs, then b.challenge alone, then c.scan
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+        assert_eq!(Manifest::load(tmp.path()).unwrap().artifacts.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_run_pipeline_runs_only_listed_passes_with_overrides() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+        write_fixture(tmp.path(), &ArtifactKind::Seed, "seed");
+        write_fixture(tmp.path(), &ArtifactKind::Rules, "rules");
+
+        let mut registry = AiRegistry::new();
+        registry.register(EchoProvider);
+        let services = PassServices {
+            ai: Arc::new(registry),
+            ..PassServices::default()
+        };
+        let mut manager = PassManager::with_services(services);
+        manager.register(EchoPass);
+        manager.register(StubPass::new("b.skeleton", vec![ArtifactKind::Rules], vec![ArtifactKind::Skeleton]));
+
+        // Config says "auto" with nothing available; the override picks echo
+        let pipeline = Pipeline::parse("name: t\npasses:\n  - pass: diverge.echo\n    provider: echo\n").unwrap();
+        manager.run_pipeline(&pipeline, tmp.path(), RunOptions::default()).await.unwrap();
+
+        assert!(tmp.path().join(".process/echo.yaml").exists());
+        assert!(!tmp.path().join(".process/skeleton.yaml").exists());
+
+        let bad = Pipeline::parse("name: bad\npasses:\n  - nope\n").unwrap();
+        let err = manager.run_pipeline(&bad, tmp.path(), RunOptions::default()).await.unwrap_err();
+        assert!(err.to_string().contains("unknown pass 'nope'"));
+    }
+
+    /// Pass whose output breaks the rules schema
+    struct BadRulesPass;
+
+    #[async_trait]
+    impl Pass for BadRulesPass {
+        fn name(&self) -> &'static str { "converge.bad" }
+        fn requires(&self) -> Vec<ArtifactKind> { vec![] }
+        fn produces(&self) -> Vec<ArtifactKind> { vec![ArtifactKind::Rules] }
+        fn kind(&self) -> PassKind { PassKind::Sync }
+        fn description(&self) -> &'static str { "bad" }
+
+        async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+            let bad = fixture(&ArtifactKind::Rules, "bad").replace("INV-1", "RULE-1");
+            ctx.save_artifact(&ArtifactKind::Rules, &bad)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_schema_violations_fail_at_the_pass_boundary() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".process")).unwrap();
+
+        let mut manager = PassManager::new();
+        manager.register(BadRulesPass);
+        let err = manager.run_pass("converge.bad", tmp.path()).await.unwrap_err();
+        assert!(format!("{:#}", err).contains("invariant ID 'RULE-1' must start with 'INV-'"));
+        assert!(!tmp.path().join(".process/rules.yaml").exists());
+        assert!(tmp.path().join(".process/rules.yaml.rejected").exists());
+        assert!(Manifest::load(tmp.path()).unwrap().artifacts.is_empty());
+
+        // A malformed input is reported as such, not as missing
+        std::fs::write(tmp.path().join(".process/rules.yaml"), "invariants: [").unwrap();
+        manager.register(StubPass::new("c.skeleton", vec![ArtifactKind::Rules], vec![ArtifactKind::Skeleton]));
+        let err = manager.run_pass("c.skeleton", tmp.path()).await.unwrap_err();
+        let schema_err = err.chain().find_map(|e| e.downcast_ref::<crate::schema::SchemaError>());
+        assert_eq!(schema_err.unwrap().file, "rules.yaml");
+    }
+}
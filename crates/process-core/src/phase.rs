@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Phase {
@@ -27,3 +28,52 @@ impl fmt::Display for Phase {
         }
     }
 }
+
+impl Phase {
+    /// All phases in order
+    pub const ALL: [Phase; 8] = [
+        Phase::Seed,
+        Phase::Diverge,
+        Phase::Converge,
+        Phase::Skeleton,
+        Phase::Branching,
+        Phase::Stabilize,
+        Phase::Postmortem,
+        Phase::Done,
+    ];
+
+    /// Lowercase name, as used on the command line and in decision logs
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Seed => "seed",
+            Phase::Diverge => "diverge",
+            Phase::Converge => "converge",
+            Phase::Skeleton => "skeleton",
+            Phase::Branching => "branching",
+            Phase::Stabilize => "stabilize",
+            Phase::Postmortem => "postmortem",
+            Phase::Done => "done",
+        }
+    }
+
+    /// The phase before this one (Seed has none and returns itself)
+    pub fn previous(self) -> Phase {
+        Phase::ALL[(self as usize).saturating_sub(1)]
+    }
+}
+
+impl FromStr for Phase {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let lower = s.to_lowercase();
+        Phase::ALL
+            .into_iter()
+            .find(|p| p.name() == lower)
+            .ok_or_else(|| anyhow::anyhow!(
+                "Unknown phase '{}' (expected one of: {})",
+                s,
+                Phase::ALL.map(Phase::name).join(", ")
+            ))
+    }
+}
@@ -0,0 +1,206 @@
+use crate::pass::PassOverrides;
+use anyhow::{anyhow, Context, Result};
+use include_dir::{include_dir, Dir};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+static BUILT_IN_PIPELINES: Dir = include_dir!("$CARGO_MANIFEST_DIR/../../templates/pipelines");
+
+/// A named, ordered selection of passes with optional per-pass overrides
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pipeline {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub passes: Vec<PipelineStep>,
+}
+
+/// A pipeline entry: either a bare pass name or a pass with overrides
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PipelineStep {
+    Name(String),
+    Configured {
+        pass: String,
+        #[serde(flatten)]
+        overrides: PassOverrides,
+    },
+}
+
+impl PipelineStep {
+    pub fn pass_name(&self) -> &str {
+        match self {
+            PipelineStep::Name(name) => name,
+            PipelineStep::Configured { pass, .. } => pass,
+        }
+    }
+
+    pub fn overrides(&self) -> PassOverrides {
+        match self {
+            PipelineStep::Name(_) => PassOverrides::default(),
+            PipelineStep::Configured { overrides, .. } => overrides.clone(),
+        }
+    }
+}
+
+/// Where a pipeline definition came from
+#[derive(Debug, Clone, PartialEq)]
+pub enum PipelineSource {
+    BuiltIn,
+    Project,
+    File,
+}
+
+impl Pipeline {
+    pub fn parse(content: &str) -> Result<Self> {
+        let pipeline: Pipeline = serde_yaml::from_str(content)
+            .context("Invalid pipeline YAML")?;
+        if pipeline.passes.is_empty() {
+            anyhow::bail!("Pipeline '{}' lists no passes", pipeline.name);
+        }
+        Ok(pipeline)
+    }
+
+    /// Resolve a pipeline by path (`*.yaml`/`*.yml`), then by name in
+    /// `.process/pipelines/`, then among the built-ins. A path is taken as
+    /// given when absolute or present relative to the working directory,
+    /// else relative to the project root.
+    pub fn load(project_root: &Path, name: &str) -> Result<(Self, PipelineSource)> {
+        let cwd = std::env::current_dir().context("Failed to read the working directory")?;
+        Self::resolve_from(&cwd, project_root, name)
+    }
+
+    /// `load` with the working directory passed in
+    fn resolve_from(cwd: &Path, project_root: &Path, name: &str) -> Result<(Self, PipelineSource)> {
+        if name.ends_with(".yaml") || name.ends_with(".yml") {
+            let given = cwd.join(name);
+            let path = if given.exists() { given } else { project_root.join(name) };
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read pipeline {}", path.display()))?;
+            return Ok((Self::parse(&content)?, PipelineSource::File));
+        }
+
+        let project_path = project_root.join(".process/pipelines").join(format!("{}.yaml", name));
+        if project_path.exists() {
+            let content = std::fs::read_to_string(&project_path)
+                .with_context(|| format!("Failed to read {}", project_path.display()))?;
+            let pipeline = Self::parse(&content)
+                .with_context(|| format!("In {}", project_path.display()))?;
+            return Ok((pipeline, PipelineSource::Project));
+        }
+
+        let file = BUILT_IN_PIPELINES
+            .get_file(format!("{}.yaml", name))
+            .ok_or_else(|| anyhow!("Pipeline '{}' not found in .process/pipelines/ or built-ins", name))?;
+        let content = file.contents_utf8().context("Built-in pipeline is not valid UTF-8")?;
+        Ok((Self::parse(content)?, PipelineSource::BuiltIn))
+    }
+
+    /// All pipelines available to a project; project files shadow built-ins
+    pub fn list(project_root: &Path) -> Result<Vec<(Self, PipelineSource)>> {
+        let mut names: Vec<String> = BUILT_IN_PIPELINES
+            .files()
+            .filter_map(|f| f.path().file_stem())
+            .map(|s| s.to_string_lossy().to_string())
+            .collect();
+
+        let project_dir = project_root.join(".process/pipelines");
+        if project_dir.is_dir() {
+            for entry in std::fs::read_dir(&project_dir)? {
+                let path = entry?.path();
+                if path.extension().is_some_and(|e| e == "yaml") {
+                    if let Some(stem) = path.file_stem() {
+                        names.push(stem.to_string_lossy().to_string());
+                    }
+                }
+            }
+        }
+
+        names.sort();
+        names.dedup();
+        names.iter().map(|name| Self::load(project_root, name)).collect()
+    }
+
+    pub fn pass_names(&self) -> Vec<String> {
+        self.passes.iter().map(|s| s.pass_name().to_string()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mixed_steps() {
+        let yaml = "name: custom\npasses:\n  - seed.validate\n  - pass: diverge.generate\n    provider: ollama\n    max_tokens: 1024\n";
+        let pipeline = Pipeline::parse(yaml).unwrap();
+        assert_eq!(pipeline.pass_names(), ["seed.validate", "diverge.generate"]);
+        assert_eq!(pipeline.passes[0].overrides(), PassOverrides::default());
+
+        let overrides = pipeline.passes[1].overrides();
+        assert_eq!(overrides.provider.as_deref(), Some("ollama"));
+        assert_eq!(overrides.max_tokens, Some(1024));
+        assert_eq!(overrides.model, None);
+    }
+
+    #[test]
+    fn test_empty_pipeline_errors() {
+        assert!(Pipeline::parse("name: empty\npasses: []\n").is_err());
+    }
+
+    #[test]
+    fn test_builtin_pipelines_load() {
+        let tmp = tempfile::tempdir().unwrap();
+        for name in ["default", "startup-fast", "adopt"] {
+            let (pipeline, source) = Pipeline::load(tmp.path(), name).unwrap();
+            assert_eq!(pipeline.name, name);
+            assert_eq!(source, PipelineSource::BuiltIn);
+        }
+        assert!(Pipeline::load(tmp.path(), "missing").is_err());
+    }
+
+    #[test]
+    fn test_project_pipeline_shadows_builtin() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join(".process/pipelines");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("adopt.yaml"), "name: adopt\npasses:\n  - adopt.scan_structure\n").unwrap();
+        std::fs::write(dir.join("mine.yaml"), "name: mine\npasses:\n  - seed.validate\n").unwrap();
+
+        let (pipeline, source) = Pipeline::load(tmp.path(), "adopt").unwrap();
+        assert_eq!(source, PipelineSource::Project);
+        assert_eq!(pipeline.pass_names(), ["adopt.scan_structure"]);
+
+        let names: Vec<String> = Pipeline::list(tmp.path()).unwrap()
+            .into_iter()
+            .map(|(p, _)| p.name)
+            .collect();
+        assert_eq!(names, ["adopt", "default", "mine", "startup-fast"]);
+    }
+
+    #[test]
+    fn test_pipeline_file_resolves_from_cwd_before_project_root() {
+        let project = tempfile::tempdir().unwrap();
+        let cwd = tempfile::tempdir().unwrap();
+        let write = |dir: &Path, file: &str, name: &str| {
+            let path = dir.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, format!("name: {}\npasses:\n  - seed.validate\n", name)).unwrap();
+        };
+        write(project.path(), "p.yaml", "in-root");
+        write(project.path(), "sub/p.yaml", "shadowed");
+        write(cwd.path(), "sub/p.yaml", "in-cwd");
+
+        // A file under the working directory wins over the root's
+        let (pipeline, source) = Pipeline::resolve_from(cwd.path(), project.path(), "sub/p.yaml").unwrap();
+        assert_eq!((pipeline.name.as_str(), source), ("in-cwd", PipelineSource::File));
+
+        let absolute = cwd.path().join("sub/p.yaml");
+        let (pipeline, _) = Pipeline::resolve_from(project.path(), project.path(), absolute.to_str().unwrap()).unwrap();
+        assert_eq!(pipeline.name, "in-cwd");
+
+        // Not under the working directory: falls back to the project root
+        let (pipeline, _) = Pipeline::resolve_from(cwd.path(), project.path(), "p.yaml").unwrap();
+        assert_eq!(pipeline.name, "in-root");
+    }
+}
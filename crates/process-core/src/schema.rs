@@ -0,0 +1,694 @@
+//! Typed schemas for the core artifacts.
+//!
+//! `PassContext` checks an artifact against its schema whenever it is
+//! loaded or saved, so malformed YAML (usually AI output) fails at the pass
+//! that wrote it. Failures are `SchemaError` diagnostics that point at the
+//! offending lines. Kinds without a schema (learnings, friction, branch and
+//! custom artifacts, ...) are stored as-is.
+
+use crate::pass::ArtifactKind;
+use miette::{Diagnostic, LabeledSpan, NamedSource, SourceSpan};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use thiserror::Error;
+
+// ── Schemas ──────────────────────────────────────────────────
+
+/// seed.yaml — the 6-field project spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Seed {
+    pub idea: String,
+    pub target_user: String,
+    pub constraints: Vec<String>,
+    pub non_goals: Vec<String>,
+    pub success_criteria: Vec<String>,
+    pub reversibility_budget: String,
+}
+
+/// Allowed values of `Seed::reversibility_budget`
+pub const REVERSIBILITY_BUDGETS: [&str; 3] = ["high", "medium", "low"];
+
+/// diverge_summary.yaml — competing proposals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proposals {
+    pub proposals: Vec<Proposal>,
+    #[serde(default)]
+    pub comparison_dimensions: Vec<ComparisonDimension>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proposal {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    pub architecture: String,
+    pub tradeoffs: Vec<String>,
+    pub risks: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraint_alignment: Option<serde_yaml::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonDimension {
+    pub dimension: String,
+    #[serde(default)]
+    pub ranking: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+/// rules.yaml — invariants and conventions extracted by converge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rules {
+    pub invariants: Vec<Invariant>,
+    #[serde(default)]
+    pub conventions: Vec<Convention>,
+    pub conflict_resolution: ConflictResolution,
+    #[serde(default)]
+    pub rejected_approaches: Vec<RejectedApproach>,
+    pub selected_approach: SelectedApproach,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Invariant {
+    pub id: String,
+    pub rule: String,
+    pub rationale: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Convention {
+    pub id: String,
+    pub rule: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictResolution {
+    pub policy: String,
+}
+
+/// Allowed values of `ConflictResolution::policy`
+pub const CONFLICT_POLICIES: [&str; 3] = ["human_final_say", "ai_decides", "majority_vote"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedApproach {
+    pub name: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectedApproach {
+    pub name: String,
+    pub rationale: String,
+}
+
+/// skeleton.yaml — files to scaffold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Skeleton {
+    pub files: Vec<SkeletonFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkeletonFile {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// decisions_log.yaml — recorded human (or reconstructed) decisions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionLog {
+    pub decisions: Vec<Decision>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Decision {
+    /// Kind of entry; absent for ordinary phase decisions, "reversal" for
+    /// a reopened phase
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub entry_type: Option<String>,
+    pub phase_transition: String,
+    pub decision: String,
+    pub reasoning: String,
+    #[serde(default)]
+    pub confidence: String,
+    #[serde(default)]
+    pub revisit_trigger: String,
+    #[serde(default)]
+    pub decided_by: String,
+    #[serde(default)]
+    pub timestamp: String,
+}
+
+// ── Rules beyond the types ───────────────────────────────────
+
+/// One step of a YAML path, used to point a diagnostic at a value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathSegment {
+    Key(&'static str),
+    Index(usize),
+}
+
+use PathSegment::{Index, Key};
+
+/// A rule broken by an otherwise well-typed artifact
+#[derive(Debug, Clone)]
+pub struct Violation {
+    pub path: Vec<PathSegment>,
+    pub message: String,
+}
+
+fn violation(path: &[PathSegment], message: impl Into<String>) -> Violation {
+    Violation { path: path.to_vec(), message: message.into() }
+}
+
+/// An artifact type with rules serde cannot express
+pub trait Schema: DeserializeOwned {
+    /// Fields every document must have, for the diagnostic's help text
+    const REQUIRED: &'static str;
+
+    /// Name of the schema in structured-output requests
+    const NAME: &'static str;
+
+    /// JSON Schema sent to providers so AI output arrives in this shape
+    fn json_schema() -> serde_json::Value;
+
+    fn check(&self) -> Vec<Violation>;
+}
+
+fn string_list() -> serde_json::Value {
+    json!({"type": "array", "items": {"type": "string"}})
+}
+
+impl Schema for Seed {
+    const REQUIRED: &'static str =
+        "idea, target_user, constraints, non_goals, success_criteria, reversibility_budget";
+    const NAME: &'static str = "seed";
+
+    fn json_schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "idea": {"type": "string"},
+                "target_user": {"type": "string"},
+                "constraints": string_list(),
+                "non_goals": string_list(),
+                "success_criteria": string_list(),
+                "reversibility_budget": {"type": "string", "enum": REVERSIBILITY_BUDGETS}
+            },
+            "required": ["idea", "target_user", "constraints", "non_goals", "success_criteria", "reversibility_budget"]
+        })
+    }
+
+    fn check(&self) -> Vec<Violation> {
+        let mut v = Vec::new();
+        if self.idea.trim().is_empty() {
+            v.push(violation(&[Key("idea")], "'idea' cannot be empty"));
+        }
+        if self.target_user.trim().is_empty() {
+            v.push(violation(&[Key("target_user")], "'target_user' cannot be empty"));
+        }
+        for (key, list) in [
+            ("constraints", &self.constraints),
+            ("non_goals", &self.non_goals),
+            ("success_criteria", &self.success_criteria),
+        ] {
+            if list.is_empty() {
+                v.push(violation(&[Key(key)], format!("'{}' must have at least one entry", key)));
+            }
+        }
+        if !REVERSIBILITY_BUDGETS.contains(&self.reversibility_budget.as_str()) {
+            v.push(violation(
+                &[Key("reversibility_budget")],
+                format!("invalid reversibility_budget '{}', expected one of {:?}", self.reversibility_budget, REVERSIBILITY_BUDGETS),
+            ));
+        }
+        v
+    }
+}
+
+impl Schema for Proposals {
+    const REQUIRED: &'static str = "proposals[].{name, architecture, tradeoffs, risks}";
+    const NAME: &'static str = "proposals";
+
+    fn json_schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "proposals": {
+                    "type": "array",
+                    "minItems": 2,
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": {"type": "string"},
+                            "summary": {"type": "string"},
+                            "architecture": {"type": "string"},
+                            "tradeoffs": string_list(),
+                            "risks": string_list(),
+                            "constraint_alignment": {"type": "object"}
+                        },
+                        "required": ["name", "architecture", "tradeoffs", "risks"]
+                    }
+                },
+                "comparison_dimensions": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "dimension": {"type": "string"},
+                            "ranking": string_list(),
+                            "notes": {"type": "string"}
+                        },
+                        "required": ["dimension"]
+                    }
+                }
+            },
+            "required": ["proposals"]
+        })
+    }
+
+    fn check(&self) -> Vec<Violation> {
+        let mut v = Vec::new();
+        if self.proposals.len() < 2 {
+            v.push(violation(
+                &[Key("proposals")],
+                format!("at least 2 proposals required, found {}", self.proposals.len()),
+            ));
+        }
+        for (i, p) in self.proposals.iter().enumerate() {
+            if p.name.trim().is_empty() {
+                v.push(violation(&[Key("proposals"), Index(i), Key("name")], "proposal name cannot be empty"));
+            }
+            if p.architecture.trim().is_empty() {
+                v.push(violation(
+                    &[Key("proposals"), Index(i), Key("architecture")],
+                    format!("proposal '{}' missing architecture description", p.name),
+                ));
+            }
+            if p.tradeoffs.is_empty() {
+                v.push(violation(
+                    &[Key("proposals"), Index(i), Key("tradeoffs")],
+                    format!("proposal '{}' must have at least one tradeoff", p.name),
+                ));
+            }
+            if p.risks.is_empty() {
+                v.push(violation(
+                    &[Key("proposals"), Index(i), Key("risks")],
+                    format!("proposal '{}' must have at least one risk", p.name),
+                ));
+            }
+        }
+        v
+    }
+}
+
+impl Schema for Rules {
+    const REQUIRED: &'static str = "invariants, conflict_resolution.policy, selected_approach";
+    const NAME: &'static str = "rules";
+
+    fn json_schema() -> serde_json::Value {
+        let named = |fields: [&str; 2]| json!({
+            "type": "object",
+            "properties": {fields[0]: {"type": "string"}, fields[1]: {"type": "string"}},
+            "required": fields
+        });
+        json!({
+            "type": "object",
+            "properties": {
+                "invariants": {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "id": {"type": "string", "pattern": "^INV-"},
+                            "rule": {"type": "string"},
+                            "rationale": {"type": "string"}
+                        },
+                        "required": ["id", "rule", "rationale"]
+                    }
+                },
+                "conventions": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "id": {"type": "string", "pattern": "^CONV-"},
+                            "rule": {"type": "string"}
+                        },
+                        "required": ["id", "rule"]
+                    }
+                },
+                "conflict_resolution": {
+                    "type": "object",
+                    "properties": {"policy": {"type": "string", "enum": CONFLICT_POLICIES}},
+                    "required": ["policy"]
+                },
+                "rejected_approaches": {"type": "array", "items": named(["name", "reason"])},
+                "selected_approach": named(["name", "rationale"])
+            },
+            "required": ["invariants", "conflict_resolution", "selected_approach"]
+        })
+    }
+
+    fn check(&self) -> Vec<Violation> {
+        let mut v = Vec::new();
+        if self.invariants.is_empty() {
+            v.push(violation(&[Key("invariants")], "at least 1 invariant is required"));
+        }
+        for (i, inv) in self.invariants.iter().enumerate() {
+            if !inv.id.starts_with("INV-") {
+                v.push(violation(
+                    &[Key("invariants"), Index(i), Key("id")],
+                    format!("invariant ID '{}' must start with 'INV-'", inv.id),
+                ));
+            }
+        }
+        for (i, conv) in self.conventions.iter().enumerate() {
+            if !conv.id.starts_with("CONV-") {
+                v.push(violation(
+                    &[Key("conventions"), Index(i), Key("id")],
+                    format!("convention ID '{}' must start with 'CONV-'", conv.id),
+                ));
+            }
+        }
+        if !CONFLICT_POLICIES.contains(&self.conflict_resolution.policy.as_str()) {
+            v.push(violation(
+                &[Key("conflict_resolution"), Key("policy")],
+                format!("invalid conflict resolution policy '{}', expected one of {:?}", self.conflict_resolution.policy, CONFLICT_POLICIES),
+            ));
+        }
+        if self.selected_approach.name.trim().is_empty() {
+            v.push(violation(&[Key("selected_approach"), Key("name")], "selected approach name cannot be empty"));
+        }
+        v
+    }
+}
+
+impl Schema for Skeleton {
+    const REQUIRED: &'static str = "files[].path";
+    const NAME: &'static str = "skeleton";
+
+    fn json_schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "files": {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "path": {"type": "string"},
+                            "description": {"type": "string"}
+                        },
+                        "required": ["path"]
+                    }
+                }
+            },
+            "required": ["files"]
+        })
+    }
+
+    fn check(&self) -> Vec<Violation> {
+        let mut v = Vec::new();
+        if self.files.is_empty() {
+            v.push(violation(&[Key("files")], "skeleton must contain at least one file"));
+        }
+        for (i, file) in self.files.iter().enumerate() {
+            let path = &file.path;
+            if path.is_empty() {
+                v.push(violation(&[Key("files"), Index(i), Key("path")], "file path cannot be empty"));
+            } else if path.starts_with('/') || path.contains("..") || path.contains('\\') || path.contains(':') {
+                v.push(violation(
+                    &[Key("files"), Index(i), Key("path")],
+                    format!("invalid file path '{}': must be relative and safe (no absolute paths, '..', backslashes, or colons)", path),
+                ));
+            }
+        }
+        v
+    }
+}
+
+impl Schema for DecisionLog {
+    const REQUIRED: &'static str = "decisions[].{phase_transition, decision, reasoning}";
+    const NAME: &'static str = "decision_log";
+
+    fn json_schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "decisions": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "type": {"type": "string"},
+                            "phase_transition": {"type": "string"},
+                            "decision": {"type": "string"},
+                            "reasoning": {"type": "string"},
+                            "confidence": {"type": "string"},
+                            "revisit_trigger": {"type": "string"},
+                            "decided_by": {"type": "string"},
+                            "timestamp": {"type": "string"}
+                        },
+                        "required": ["phase_transition", "decision", "reasoning"]
+                    }
+                }
+            },
+            "required": ["decisions"]
+        })
+    }
+
+    fn check(&self) -> Vec<Violation> {
+        self.decisions
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| d.decision.trim().is_empty())
+            .map(|(i, _)| violation(&[Key("decisions"), Index(i), Key("decision")], "decision cannot be empty"))
+            .collect()
+    }
+}
+
+// ── Validation ───────────────────────────────────────────────
+
+/// An artifact that failed its schema, with the offending lines labelled
+#[derive(Debug, Clone, Error, Diagnostic)]
+#[error("{file} does not match the {artifact} schema: {}", problems.join("; "))]
+#[diagnostic(code(process::artifact::schema))]
+pub struct SchemaError {
+    pub artifact: String,
+    pub file: String,
+    pub problems: Vec<String>,
+    #[source_code]
+    source_code: NamedSource<String>,
+    #[label(collection)]
+    labels: Vec<LabeledSpan>,
+    #[help]
+    help: Option<String>,
+}
+
+/// Check `content` against the schema of `kind`, if it has one.
+/// `file` names the document in diagnostics.
+pub fn validate(kind: &ArtifactKind, file: &str, content: &str) -> Result<(), Box<SchemaError>> {
+    match kind {
+        ArtifactKind::Seed => check::<Seed>(kind, file, content),
+        ArtifactKind::Proposals => check::<Proposals>(kind, file, content),
+        ArtifactKind::Rules => check::<Rules>(kind, file, content),
+        ArtifactKind::Skeleton => check::<Skeleton>(kind, file, content),
+        ArtifactKind::DecisionLog => check::<DecisionLog>(kind, file, content),
+        _ => Ok(()),
+    }
+}
+
+fn check<T: Schema>(kind: &ArtifactKind, file: &str, content: &str) -> Result<(), Box<SchemaError>> {
+    let (problems, labels) = match serde_yaml::from_str::<T>(content) {
+        Ok(doc) => {
+            let violations = doc.check();
+            if violations.is_empty() {
+                return Ok(());
+            }
+            let labels = violations
+                .iter()
+                .filter_map(|v| locate(content, &v.path).map(|span| LabeledSpan::new_with_span(Some(v.message.clone()), span)))
+                .collect();
+            (violations.into_iter().map(|v| v.message).collect(), labels)
+        }
+        Err(e) => {
+            let labels = e
+                .location()
+                .map(|loc| {
+                    let end = content[loc.index()..].find('\n').map_or(content.len(), |n| loc.index() + n);
+                    let span = SourceSpan::from(loc.index()..end.max(loc.index()));
+                    vec![LabeledSpan::new_with_span(Some("here".to_string()), span)]
+                })
+                .unwrap_or_default();
+            (vec![e.to_string()], labels)
+        }
+    };
+
+    Err(Box::new(SchemaError {
+        artifact: kind.to_string(),
+        file: file.to_string(),
+        problems,
+        source_code: NamedSource::new(file, content.to_string()),
+        labels,
+        help: Some(format!("{} requires: {}", file, T::REQUIRED)),
+    }))
+}
+
+/// A significant YAML line: where its key starts and whether it opens a
+/// sequence item (`- key: value`)
+struct Line<'a> {
+    offset: usize,
+    indent: usize,
+    dash: bool,
+    key_col: usize,
+    text: &'a str,
+}
+
+fn significant_lines(src: &str) -> Vec<Line<'_>> {
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for raw in src.split_inclusive('\n') {
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let body = line.trim_start();
+        let indent = line.len() - body.len();
+        if !body.is_empty() && !body.starts_with('#') {
+            let (dash, text) = match body.strip_prefix('-') {
+                Some(rest) if rest.is_empty() || rest.starts_with(' ') => (true, rest.trim_start()),
+                _ => (false, body),
+            };
+            lines.push(Line { offset, indent, dash, key_col: line.len() - text.len(), text });
+        }
+        offset += raw.len();
+    }
+    lines
+}
+
+/// Best-effort span for a YAML path in block-style YAML: the deepest
+/// segment that can be found, or `None` if not even the first one can
+pub fn locate(src: &str, path: &[PathSegment]) -> Option<SourceSpan> {
+    let lines = significant_lines(src);
+    let (mut start, mut end) = (0, lines.len());
+    let mut best = None;
+
+    for segment in path {
+        let first = lines.get(start).filter(|_| start < end)?;
+        match *segment {
+            Key(key) => {
+                let col = first.key_col;
+                let Some(i) = (start..end).find(|&i| {
+                    let l = &lines[i];
+                    l.key_col == col
+                        && [key.to_string(), format!("\"{}\"", key), format!("'{}'", key)]
+                            .iter()
+                            .any(|k| l.text.strip_prefix(k.as_str()).is_some_and(|r| r.trim_start().starts_with(':')))
+                }) else { break };
+                let l = &lines[i];
+                best = Some(SourceSpan::from((l.offset + l.key_col, l.text.len())));
+                let stop = (i + 1..end)
+                    .find(|&j| !(lines[j].indent > col || (lines[j].indent == col && lines[j].dash)))
+                    .unwrap_or(end);
+                (start, end) = (i + 1, stop);
+            }
+            Index(n) => {
+                let indent = first.indent;
+                let items: Vec<usize> = (start..end)
+                    .filter(|&i| lines[i].dash && lines[i].indent == indent)
+                    .collect();
+                let Some(&i) = items.get(n) else { break };
+                let l = &lines[i];
+                best = Some(SourceSpan::from((l.offset + l.indent, l.text.len() + l.key_col - l.indent)));
+                (start, end) = (i, items.get(n + 1).copied().unwrap_or(end));
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: &str = "idea: CLI\ntarget_user: devs\nconstraints: [rust]\nnon_goals: [gui]\nsuccess_criteria: [ships]\nreversibility_budget: medium\n";
+
+    fn span_text(src: &str, span: SourceSpan) -> &str {
+        &src[span.offset()..span.offset() + span.len()]
+    }
+
+    #[test]
+    fn test_valid_seed_passes() {
+        assert!(validate(&ArtifactKind::Seed, "seed.yaml", SEED).is_ok());
+    }
+
+    #[test]
+    fn test_json_schema_requires_what_serde_requires() {
+        let required = |schema: serde_json::Value| -> Vec<String> {
+            serde_json::from_value(schema["required"].clone()).unwrap()
+        };
+        assert_eq!(required(Seed::json_schema()).join(", "), Seed::REQUIRED);
+        assert_eq!(required(Skeleton::json_schema()), ["files"]);
+
+        // A JSON answer missing an optional field still saves cleanly as YAML
+        let proposals: Proposals = serde_json::from_value(json!({"proposals": [
+            {"name": "a", "architecture": "x", "tradeoffs": ["t"], "risks": ["r"]},
+            {"name": "b", "architecture": "y", "tradeoffs": ["t"], "risks": ["r"]}
+        ]})).unwrap();
+        let yaml = serde_yaml::to_string(&proposals).unwrap();
+        assert!(!yaml.contains("null"), "{}", yaml);
+        assert!(validate(&ArtifactKind::Proposals, "diverge_summary.yaml", &yaml).is_ok());
+    }
+
+    #[test]
+    fn test_kinds_without_schema_accept_anything() {
+        assert!(validate(&ArtifactKind::Custom("notes".into()), "notes.yaml", "not: [valid").is_ok());
+    }
+
+    #[test]
+    fn test_syntax_error_is_located() {
+        let src = "idea: CLI\ntarget_user: [unclosed\n";
+        let err = validate(&ArtifactKind::Seed, "seed.yaml", src).unwrap_err();
+        assert_eq!(err.labels.len(), 1);
+        assert!(err.help.as_deref().unwrap().contains("reversibility_budget"));
+    }
+
+    #[test]
+    fn test_rule_violations_point_at_their_lines() {
+        let src = SEED.replace("medium", "sideways");
+        let err = validate(&ArtifactKind::Seed, "seed.yaml", &src).unwrap_err();
+        assert_eq!(err.problems.len(), 1);
+        assert!(err.to_string().contains("invalid reversibility_budget 'sideways'"));
+        assert_eq!(span_text(&src, err.labels[0].inner().to_owned()), "reversibility_budget: sideways");
+    }
+
+    #[test]
+    fn test_locate_nested_sequence_items() {
+        let src = "\
+proposals:
+  - name: A
+    risks: [x]
+  - name: B
+    architecture: layered
+    risks: []
+comparison_dimensions: []
+";
+        let span = locate(src, &[Key("proposals"), Index(1), Key("risks")]).unwrap();
+        assert_eq!(span_text(src, span), "risks: []");
+        let span = locate(src, &[Key("proposals"), Index(1), Key("name")]).unwrap();
+        assert_eq!(span_text(src, span), "name: B");
+        // Missing segment falls back to the deepest match
+        let span = locate(src, &[Key("proposals"), Index(5)]).unwrap();
+        assert_eq!(span_text(src, span), "proposals:");
+    }
+
+    #[test]
+    fn test_locate_compact_sequences() {
+        let src = "files:\n- path: README.md\n- path: ../escape\ndone: true\n";
+        let span = locate(src, &[Key("files"), Index(1), Key("path")]).unwrap();
+        assert_eq!(span_text(src, span), "path: ../escape");
+        assert!(locate(src, &[Key("missing")]).is_none());
+    }
+}
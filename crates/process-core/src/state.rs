@@ -1,3 +1,5 @@
+use crate::artifacts;
+use crate::cycle::{CycleType, INITIAL_CYCLE};
 use crate::phase::Phase;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -10,6 +12,15 @@ pub struct ProcessState {
     pub current_phase: Phase,
     pub last_updated: DateTime<Utc>,
     pub metadata: std::collections::HashMap<String, String>,
+    /// Id of the active decision cycle
+    #[serde(default = "initial_cycle")]
+    pub cycle: String,
+    #[serde(default)]
+    pub cycle_type: CycleType,
+}
+
+fn initial_cycle() -> String {
+    INITIAL_CYCLE.to_string()
 }
 
 impl Default for ProcessState {
@@ -18,15 +29,20 @@ impl Default for ProcessState {
             current_phase: Phase::Seed,
             last_updated: Utc::now(),
             metadata: std::collections::HashMap::new(),
+            cycle: initial_cycle(),
+            cycle_type: CycleType::default(),
         }
     }
 }
 
 impl ProcessState {
-    pub fn load() -> Result<Self> {
-        let process_dir = Path::new(".process");
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let process_dir = project_root.join(artifacts::PROCESS_DIR);
         if !process_dir.exists() {
-            anyhow::bail!("Not a process project (no .process/ directory). Run 'process init' first.");
+            anyhow::bail!(
+                "Not a process project (no .process/ directory in {}). Run 'process init' first.",
+                project_root.display()
+            );
         }
         let path = process_dir.join(".state.yaml");
         if !path.exists() {
@@ -37,10 +53,10 @@ impl ProcessState {
         Ok(state)
     }
 
-    pub fn save(&self) -> Result<()> {
-        let path = Path::new(".process/.state.yaml");
+    pub fn save(&self, project_root: &Path) -> Result<()> {
+        let path = project_root.join(artifacts::PROCESS_DIR).join(".state.yaml");
         let content = serde_yaml::to_string(self).context("Failed to serialize state")?;
-        fs::write(path, content).context("Failed to write state file")?;
+        artifacts::write(&path, content).context("Failed to write state file")?;
         Ok(())
     }
 
@@ -53,6 +69,17 @@ impl ProcessState {
         Ok(())
     }
 
+    /// Step back so `phase` can be redone: the current phase becomes the one
+    /// before it. Only phases already reached can be reopened.
+    pub fn reopen(&mut self, phase: Phase) -> Result<()> {
+        if phase > self.current_phase {
+            anyhow::bail!("Cannot reopen {}: the process has only reached {}", phase, self.current_phase);
+        }
+        self.current_phase = phase.previous();
+        self.last_updated = Utc::now();
+        Ok(())
+    }
+
     pub fn set_phase(&mut self, phase: Phase) {
         if phase > self.current_phase {
             self.current_phase = phase;
@@ -124,6 +151,38 @@ mod tests {
         assert_eq!(state.last_updated, ts);
     }
 
+    // -- reopen tests --
+
+    #[test]
+    fn test_reopen_steps_back_before_the_phase() {
+        let mut state = ProcessState::default();
+        state.set_phase(Phase::Branching);
+
+        state.reopen(Phase::Converge).unwrap();
+        assert_eq!(state.current_phase, Phase::Diverge);
+        assert!(state.check_phase(Phase::Converge).is_err());
+
+        state.reopen(Phase::Seed).unwrap();
+        assert_eq!(state.current_phase, Phase::Seed);
+    }
+
+    #[test]
+    fn test_reopen_refuses_unreached_phase() {
+        let mut state = ProcessState::default();
+        state.set_phase(Phase::Diverge);
+        assert!(state.reopen(Phase::Skeleton).is_err());
+        assert_eq!(state.current_phase, Phase::Diverge);
+    }
+
+    #[test]
+    fn test_phase_parses_from_name() {
+        assert_eq!("converge".parse::<Phase>().unwrap(), Phase::Converge);
+        assert_eq!("Skeleton".parse::<Phase>().unwrap(), Phase::Skeleton);
+        assert!("review".parse::<Phase>().is_err());
+        assert_eq!(Phase::Diverge.previous(), Phase::Seed);
+        assert_eq!(Phase::Seed.previous(), Phase::Seed);
+    }
+
     // -- check_phase tests --
 
     #[test]
@@ -149,30 +208,30 @@ mod tests {
         assert!(msg.contains("not ready"));
     }
 
-    // -- load/save tests (combined to avoid set_current_dir race) --
+    // -- load/save tests --
 
     #[test]
     fn test_load_save_lifecycle() {
         let tmp = tempfile::tempdir().unwrap();
-        std::env::set_current_dir(tmp.path()).unwrap();
+        let root = tmp.path();
 
         // 1. No .process/ dir → error
-        let result = ProcessState::load();
+        let result = ProcessState::load(root);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Not a process project"));
 
         // 2. .process/ exists but no state file → default
-        std::fs::create_dir_all(".process").unwrap();
-        let state = ProcessState::load().unwrap();
+        std::fs::create_dir_all(root.join(".process")).unwrap();
+        let state = ProcessState::load(root).unwrap();
         assert_eq!(state.current_phase, Phase::Seed);
 
         // 3. Save and reload → roundtrip
         let mut state = ProcessState::default();
         state.set_phase(Phase::Skeleton);
         state.metadata.insert("key".to_string(), "value".to_string());
-        state.save().unwrap();
+        state.save(root).unwrap();
 
-        let loaded = ProcessState::load().unwrap();
+        let loaded = ProcessState::load(root).unwrap();
         assert_eq!(loaded.current_phase, Phase::Skeleton);
         assert_eq!(loaded.metadata.get("key").unwrap(), "value");
     }
@@ -188,6 +247,14 @@ mod tests {
 
     // -- Serialization tests --
 
+    #[test]
+    fn test_state_without_cycle_is_initial_greenfield() {
+        let yaml = "current_phase: Done\nlast_updated: 2024-01-15T00:00:00Z\nmetadata: {}\n";
+        let state: ProcessState = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(state.cycle, INITIAL_CYCLE);
+        assert_eq!(state.cycle_type, CycleType::Greenfield);
+    }
+
     #[test]
     fn test_phase_display() {
         assert_eq!(format!("{}", Phase::Seed), "0. Seed");
@@ -0,0 +1,265 @@
+//! Usage ledger: one entry per AI completion — who answered, how many
+//! tokens, how long it took and what it is estimated to have cost — kept in
+//! `.process/usage.yaml` so a team can budget AI spend per project.
+//!
+//! The file is a YAML list that is only ever appended to, so passes running
+//! in parallel add their entries without rewriting each other's.
+
+use crate::artifacts::PROCESS_DIR;
+use crate::phase::Phase;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use process_ai::notice::{self, Notice};
+use process_ai::provider::{AiProvider, ChunkSink, CompletionRequest, CompletionResponse};
+use process_config::config::AiConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Ledger file name under `.process/`
+pub const USAGE_FILE: &str = "usage.yaml";
+
+/// One completion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageEntry {
+    pub timestamp: String,
+    /// Project phase when the call was made
+    pub phase: Phase,
+    /// Pass (or command, e.g. `branch.review`) that made the call
+    pub pass: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Provider that answered, after any fallback
+    pub provider: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Zero when the provider does not report usage (CLI, manual)
+    pub prompt_tokens: usize,
+    pub completion_tokens: usize,
+    pub latency_ms: u64,
+    /// Estimated from `ai.prices`; absent when the model has no price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+}
+
+/// Where calls through a metered provider are attributed
+#[derive(Debug, Clone)]
+pub struct UsageScope {
+    pub phase: Phase,
+    pub pass: String,
+    pub branch: Option<String>,
+}
+
+pub fn ledger_path(project_root: &Path) -> PathBuf {
+    project_root.join(PROCESS_DIR).join(USAGE_FILE)
+}
+
+/// Add an entry to the end of the ledger
+pub fn append(project_root: &Path, entry: &UsageEntry) -> Result<()> {
+    let path = ledger_path(project_root);
+    // A one-item list serializes as "- ...", which extends the file's list.
+    // Written in one call so concurrent appends don't interleave.
+    let item = serde_yaml::to_string(&[entry])?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    file.write_all(item.as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Every recorded entry, oldest first
+pub fn load(project_root: &Path) -> Result<Vec<UsageEntry>> {
+    let path = ledger_path(project_root);
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Ok(Vec::new());
+    };
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_yaml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Sums over a group of entries
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsageTotals {
+    pub calls: usize,
+    pub prompt_tokens: usize,
+    pub completion_tokens: usize,
+    pub cost_usd: f64,
+    /// Calls with no price, so not in `cost_usd`
+    pub unpriced: usize,
+}
+
+impl UsageTotals {
+    pub fn add(&mut self, entry: &UsageEntry) {
+        self.calls += 1;
+        self.prompt_tokens += entry.prompt_tokens;
+        self.completion_tokens += entry.completion_tokens;
+        match entry.cost_usd {
+            Some(cost) => self.cost_usd += cost,
+            None => self.unpriced += 1,
+        }
+    }
+}
+
+/// Totals per key, e.g. `|e| e.provider.clone()`
+pub fn totals_by(entries: &[UsageEntry], key: impl Fn(&UsageEntry) -> String) -> BTreeMap<String, UsageTotals> {
+    let mut groups: BTreeMap<String, UsageTotals> = BTreeMap::new();
+    for entry in entries {
+        groups.entry(key(entry)).or_default().add(entry);
+    }
+    groups
+}
+
+/// Passes the provider through, adding an entry to the ledger for every
+/// completion that succeeds and was not served from the cache
+pub struct MeteredProvider {
+    inner: Arc<dyn AiProvider>,
+    project_root: PathBuf,
+    ai: AiConfig,
+    scope: UsageScope,
+}
+
+/// Wrap `provider` so its completions are recorded under `scope`
+pub fn meter(provider: Arc<dyn AiProvider>, project_root: &Path, ai: &AiConfig, scope: UsageScope) -> Arc<dyn AiProvider> {
+    Arc::new(MeteredProvider {
+        inner: provider,
+        project_root: project_root.to_path_buf(),
+        ai: ai.clone(),
+        scope,
+    })
+}
+
+impl MeteredProvider {
+    fn record(&self, response: &CompletionResponse, started: Instant) {
+        // Paid for when it was first fetched
+        if response.cached {
+            return;
+        }
+        let (prompt_tokens, completion_tokens) = response
+            .usage
+            .as_ref()
+            .map_or((0, 0), |u| (u.prompt_tokens, u.completion_tokens));
+        let cost_usd = response
+            .model
+            .as_deref()
+            .and_then(|model| self.ai.price_for(model))
+            .filter(|_| response.usage.is_some())
+            .map(|price| price.cost(prompt_tokens, completion_tokens));
+
+        let entry = UsageEntry {
+            timestamp: Utc::now().to_rfc3339(),
+            phase: self.scope.phase,
+            pass: self.scope.pass.clone(),
+            branch: self.scope.branch.clone(),
+            provider: response.provider.clone().unwrap_or_else(|| self.inner.name().to_string()),
+            model: response.model.clone(),
+            prompt_tokens,
+            completion_tokens,
+            latency_ms: started.elapsed().as_millis() as u64,
+            cost_usd,
+        };
+        // The answer is already paid for; losing its ledger line must not lose it too
+        if let Err(err) = append(&self.project_root, &entry) {
+            notice::emit(Notice::UsageNotRecorded { error: format!("{:#}", err) });
+        }
+    }
+}
+
+#[async_trait]
+impl AiProvider for MeteredProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn label(&self) -> String {
+        self.inner.label()
+    }
+
+    fn primary(&self) -> String {
+        self.inner.primary()
+    }
+
+    fn model(&self) -> Option<String> {
+        self.inner.model()
+    }
+
+    async fn is_available(&self) -> bool {
+        self.inner.is_available().await
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
+        let started = Instant::now();
+        let response = self.inner.complete(request).await?;
+        self.record(&response, started);
+        Ok(response)
+    }
+
+    async fn complete_stream(&self, request: &CompletionRequest, on_chunk: ChunkSink<'_>) -> Result<CompletionResponse> {
+        let started = Instant::now();
+        let response = self.inner.complete_stream(request, on_chunk).await?;
+        self.record(&response, started);
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use process_ai::provider::TokenUsage;
+    use process_config::config::{Config, ModelPrice};
+
+    struct Fixed;
+
+    #[async_trait]
+    impl AiProvider for Fixed {
+        fn name(&self) -> &'static str { "fixed" }
+        fn priority(&self) -> u8 { 0 }
+        async fn is_available(&self) -> bool { true }
+        async fn complete(&self, _request: &CompletionRequest) -> Result<CompletionResponse> {
+            Ok(CompletionResponse {
+                content: "ok".into(),
+                usage: Some(TokenUsage { prompt_tokens: 1000, completion_tokens: 500, total_tokens: 1500 }),
+                provider: None,
+                model: Some("test-model-2025".into()),
+                cached: false,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metered_calls_are_appended_and_priced() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(PROCESS_DIR)).unwrap();
+        let mut ai = Config::default().ai;
+        ai.prices = vec![ModelPrice { model: "test-model".into(), input: 3.0, output: 15.0 }];
+
+        for (pass, branch) in [("diverge.generate", None), ("branch.review", Some("cache"))] {
+            let scope = UsageScope { phase: Phase::Diverge, pass: pass.into(), branch: branch.map(Into::into) };
+            let provider = meter(Arc::new(Fixed), tmp.path(), &ai, scope);
+            provider.complete(&CompletionRequest::new("hi")).await.unwrap();
+        }
+
+        let entries = load(tmp.path()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].branch.as_deref(), Some("cache"));
+        assert_eq!(entries[0].provider, "fixed");
+        assert!((entries[0].cost_usd.unwrap() - 0.0105).abs() < 1e-9);
+
+        let by_pass = totals_by(&entries, |e| e.pass.clone());
+        assert_eq!(by_pass["branch.review"].calls, 1);
+        let by_provider = totals_by(&entries, |e| e.provider.clone());
+        assert_eq!(by_provider["fixed"].prompt_tokens, 2000);
+        assert_eq!(by_provider["fixed"].unpriced, 0);
+    }
+}
@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// A reviewer's overall judgement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Pass,
+    ConditionalPass,
+    Fail,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    High,
+    Medium,
+    Low,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewIssue {
+    pub severity: Severity,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+    /// `file:line`, when the issue points at code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_ref: Option<String>,
+}
+
+/// One role's review of a branch, as returned by the AI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewReport {
+    pub role: String,
+    pub verdict: Verdict,
+    #[serde(default)]
+    pub issues: Vec<ReviewIssue>,
+    pub summary: String,
+}
+
+impl ReviewReport {
+    /// JSON schema the AI's answer must match
+    pub fn json_schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "role": {"type": "string"},
+                "verdict": {"type": "string", "enum": ["pass", "conditional_pass", "fail"]},
+                "issues": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "severity": {"type": "string", "enum": ["high", "medium", "low"]},
+                            "description": {"type": "string"},
+                            "suggestion": {"type": "string"},
+                            "line_ref": {"type": "string"}
+                        },
+                        "required": ["severity", "description"]
+                    }
+                },
+                "summary": {"type": "string"}
+            },
+            "required": ["role", "verdict", "issues", "summary"]
+        })
+    }
+}
+
+/// The human's decision when reviewers disagree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictRuling {
+    pub ruling: String,
+    pub reasoning: String,
+    pub risk_accepted: String,
+}
+
+/// branches/<name>/review.yaml — every role's report and the combined verdict
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchReview {
+    pub reviews: Vec<ReviewReport>,
+    pub overall_verdict: Verdict,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub human_conflict_ruling: Option<ConflictRuling>,
+}
+
+impl BranchReview {
+    /// Fails if any role failed the branch
+    pub fn new(reviews: Vec<ReviewReport>) -> Self {
+        let overall_verdict = if reviews.iter().any(|r| r.verdict == Verdict::Fail) {
+            Verdict::Fail
+        } else {
+            Verdict::Pass
+        };
+        Self { reviews, overall_verdict, human_conflict_ruling: None }
+    }
+
+    /// Whether the roles reached different verdicts
+    pub fn has_conflicts(&self) -> bool {
+        self.reviews.windows(2).any(|w| w[0].verdict != w[1].verdict)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_enums_match_serde_names() {
+        let schema = ReviewReport::json_schema();
+        for verdict in schema["properties"]["verdict"]["enum"].as_array().unwrap() {
+            serde_json::from_value::<Verdict>(verdict.clone()).unwrap();
+        }
+        let severities = &schema["properties"]["issues"]["items"]["properties"]["severity"]["enum"];
+        for severity in severities.as_array().unwrap() {
+            serde_json::from_value::<Severity>(severity.clone()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_any_fail_fails_the_branch() {
+        let report = |verdict| ReviewReport { role: "r".into(), verdict, issues: vec![], summary: String::new() };
+
+        let review = BranchReview::new(vec![report(Verdict::Pass), report(Verdict::ConditionalPass)]);
+        assert_eq!(review.overall_verdict, Verdict::Pass);
+        assert!(review.has_conflicts());
+
+        let review = BranchReview::new(vec![report(Verdict::Fail), report(Verdict::Fail)]);
+        assert_eq!(review.overall_verdict, Verdict::Fail);
+        assert!(!review.has_conflicts());
+    }
+}
@@ -1,4 +1,7 @@
 use clap::{Parser, Subcommand, ValueEnum};
+use process_core::cycle::CycleType;
+use process_core::phase::Phase;
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "process")]
@@ -11,6 +14,18 @@ pub struct Cli {
     /// Verbose output
     #[arg(short, long, global = true)]
     pub verbose: bool,
+
+    /// Project root (default: the nearest directory upward containing .process/)
+    #[arg(long, global = true, value_name = "DIR")]
+    pub project_dir: Option<PathBuf>,
+
+    /// Don't read or store cached AI responses for this run
+    #[arg(long, global = true, conflicts_with = "refresh")]
+    pub no_cache: bool,
+
+    /// Call the AI again and replace cached responses
+    #[arg(long, global = true)]
+    pub refresh: bool,
 }
 
 #[derive(Subcommand)]
@@ -124,6 +139,33 @@ pub enum Commands {
     #[command(subcommand)]
     Pass(PassCommands),
 
+    /// Pipeline commands (named pass sequences)
+    #[command(subcommand)]
+    Pipeline(PipelineCommands),
+
+    /// Artifact version history
+    #[command(subcommand)]
+    Artifact(ArtifactCommands),
+
+    /// Phase control
+    #[command(subcommand)]
+    Phase(PhaseCommands),
+
+    /// Decision cycles (greenfield, evolution, sunset)
+    #[command(subcommand)]
+    Cycle(CycleCommands),
+
+    /// Cached AI responses
+    #[command(subcommand)]
+    Cache(CacheCommands),
+
+    /// AI token usage and estimated cost
+    Usage {
+        /// Show only one breakdown (default: all)
+        #[arg(long, value_enum)]
+        by: Option<UsageGroup>,
+    },
+
     /// Show categorized command guide
     Guide,
 
@@ -135,6 +177,21 @@ pub enum Commands {
     },
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+pub enum PlanFormat {
+    Table,
+    Dot,
+    Json,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+pub enum UsageGroup {
+    Phase,
+    Pass,
+    Branch,
+    Provider,
+}
+
 #[derive(Clone, ValueEnum)]
 pub enum ShellType {
     Bash,
@@ -150,7 +207,8 @@ pub enum AiConfigCommands {
     Test,
     /// Set the default AI provider
     SetProvider {
-        /// Provider name: claude, openai, ollama, claude-cli, manual, auto
+        /// Provider name: claude, openai, azure-openai, gemini, ollama, claude-cli, manual, replay, auto,
+        /// or an `ai.profiles` name
         name: String,
     },
 }
@@ -213,11 +271,14 @@ pub enum AdoptCommands {
     },
     /// Identify gaps and undocumented decisions (AI-assisted)
     GapAnalysis,
-    /// Run all adopt passes sequentially
+    /// Run all adopt passes in dependency order
     All {
         /// Maximum number of commits for git history scan
         #[arg(short, long, default_value = "200")]
         max_commits: usize,
+        /// Run up to N independent scans at once
+        #[arg(long, short = 'j', default_value_t = 1)]
+        jobs: usize,
     },
 }
 
@@ -249,6 +310,25 @@ pub enum CheckCommands {
     All,
 }
 
+impl Commands {
+    /// Commands that only read `.process/`; they run without taking the
+    /// project lock, so `status` works while a pipeline is running
+    pub fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            Commands::Status
+                | Commands::Guide
+                | Commands::Completions { .. }
+                | Commands::Check(_)
+                | Commands::Pass(PassCommands::List | PassCommands::Plan { .. })
+                | Commands::Pipeline(PipelineCommands::List)
+                | Commands::Artifact(ArtifactCommands::History { .. } | ArtifactCommands::Show { .. })
+                | Commands::Cycle(CycleCommands::List)
+                | Commands::Usage { .. }
+        )
+    }
+}
+
 #[derive(Subcommand)]
 pub enum PassCommands {
     /// Run a specific pass
@@ -258,6 +338,96 @@ pub enum PassCommands {
     },
     /// List all available passes
     List,
+    /// Show what run-all would do: order, artifacts, stale and interactive passes
+    Plan {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = PlanFormat::Table)]
+        format: PlanFormat,
+    },
     /// Run all passes in dependency order
-    RunAll,
+    RunAll {
+        /// Skip passes whose inputs are unchanged since their last run
+        /// (default; `--incremental=false` re-runs everything)
+        #[arg(long, default_value_t = true, num_args = 0..=1, default_missing_value = "true", action = clap::ArgAction::Set)]
+        incremental: bool,
+        /// Run up to N independent passes at once (interactive passes still run alone)
+        #[arg(long, short = 'j', default_value_t = 1)]
+        jobs: usize,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PipelineCommands {
+    /// Run a pipeline by name (.process/pipelines/<name>.yaml or built-in) or path
+    Run {
+        /// Pipeline name (e.g., "default", "startup-fast", "adopt") or a .yaml path
+        name: String,
+        /// Skip passes whose inputs are unchanged since their last run
+        #[arg(long, default_value_t = true, num_args = 0..=1, default_missing_value = "true", action = clap::ArgAction::Set)]
+        incremental: bool,
+        /// Run up to N independent passes at once (interactive passes still run alone)
+        #[arg(long, short = 'j', default_value_t = 1)]
+        jobs: usize,
+    },
+    /// List available pipelines
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum PhaseCommands {
+    /// Step back to redo a phase; later artifacts are marked superseded
+    Reopen {
+        /// Phase to redo (e.g., "converge")
+        phase: Phase,
+        /// Why the earlier decision no longer holds (recorded as a reversal)
+        #[arg(long)]
+        reason: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CycleCommands {
+    /// Archive the current cycle and start a new one from its postmortem
+    New {
+        /// Cycle type: greenfield, evolution or sunset
+        #[arg(long = "type")]
+        cycle_type: CycleType,
+        /// What prompted the new cycle
+        #[arg(long)]
+        trigger: String,
+        /// Cycle id (default: v<N>-<type>)
+        #[arg(long)]
+        id: Option<String>,
+    },
+    /// Show the project's cycle lineage
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum CacheCommands {
+    /// Delete cached AI responses
+    Prune {
+        /// Only those stored more than this many days ago (default: all)
+        #[arg(long, value_name = "DAYS")]
+        older_than: Option<u64>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ArtifactCommands {
+    /// List saved versions of an artifact
+    History {
+        /// Artifact name (e.g., "proposals", "rules", "branch.cache.review")
+        name: String,
+    },
+    /// Print an artifact, or one of its saved versions
+    Show {
+        /// Artifact name, optionally with a version (e.g., "proposals@2")
+        spec: String,
+    },
+    /// Make a saved version the current artifact again
+    Restore {
+        /// Artifact name and version (e.g., "proposals@2")
+        spec: String,
+    },
 }
@@ -3,15 +3,22 @@ use crate::cli::AiConfigCommands;
 use crate::utils;
 use process_config::config::Config;
 use process_ai::provider::CompletionRequest;
+use process_ai::registry::{AiRegistry, BUILTIN_PROVIDERS};
 use colored::Colorize;
+use process_core::artifacts;
+use std::path::Path;
 
-pub async fn execute(command: &AiConfigCommands) -> Result<()> {
+pub async fn execute(root: &Path, command: &AiConfigCommands) -> Result<()> {
     match command {
         AiConfigCommands::Show => {
-            let config = Config::load()?;
+            let config = Config::load(root)?;
             println!("━━━ AI Configuration ━━━");
             println!("{}: {}", "Provider".bold(), config.ai.provider);
+            if !config.ai.fallback.is_empty() {
+                println!("{}: {}", "Fallback".bold(), config.ai.fallback.join(" → "));
+            }
             println!("{}: {}", "Timeout".bold(), config.settings.timeout_secs);
+            println!("{}: {}", "Max Retries".bold(), config.settings.max_retries);
             
             if let Some(claude) = &config.ai.claude {
                 println!("\n[Claude]");
@@ -25,16 +32,56 @@ pub async fn execute(command: &AiConfigCommands) -> Result<()> {
                 if let Some(tokens) = &openai.max_tokens { println!("  Max Tokens: {}", tokens); }
                 if let Some(url) = &openai.base_url { println!("  Base URL: {}", url); }
             }
+            if let Some(gemini) = &config.ai.gemini {
+                println!("\n[Gemini]");
+                if let Some(model) = &gemini.model { println!("  Model: {}", model); }
+                if let Some(tokens) = &gemini.max_tokens { println!("  Max Tokens: {}", tokens); }
+                if let Some(url) = &gemini.base_url { println!("  Base URL: {}", url); }
+            }
+            if let Some(azure) = &config.ai.azure_openai {
+                println!("\n[Azure OpenAI]");
+                if let Some(url) = &azure.base_url { println!("  Endpoint: {}", url); }
+                if let Some(deployment) = &azure.model { println!("  Deployment: {}", deployment); }
+                if let Some(version) = &azure.api_version { println!("  API Version: {}", version); }
+                if let Some(tokens) = &azure.max_tokens { println!("  Max Tokens: {}", tokens); }
+            }
             if let Some(ollama) = &config.ai.ollama {
                 println!("\n[Ollama]");
                 if let Some(model) = &ollama.model { println!("  Model: {}", model); }
                 if let Some(url) = &ollama.base_url { println!("  Base URL: {}", url); }
             }
+            for (name, profile) in &config.ai.profiles {
+                println!("\n[Profile: {}]", name);
+                println!("  Base URL: {}", profile.base_url);
+                if let Some(model) = &profile.model { println!("  Model: {}", model); }
+                if let Some(var) = &profile.api_key_env { println!("  API Key: ${}", var); }
+                if !profile.headers.is_empty() {
+                    let names: Vec<&str> = profile.headers.keys().map(String::as_str).collect();
+                    println!("  Headers: {}", names.join(", "));
+                }
+            }
+            if !config.ai.routes.is_empty() {
+                println!("\n[Routes]");
+                for rule in &config.ai.routes {
+                    let mut target = Vec::new();
+                    if let Some(provider) = &rule.provider { target.push(provider.clone()); }
+                    if let Some(model) = &rule.model { target.push(model.clone()); }
+                    if let Some(tokens) = rule.max_tokens { target.push(format!("max_tokens {}", tokens)); }
+                    if let Some(temperature) = rule.temperature { target.push(format!("temperature {}", temperature)); }
+                    println!("  {} → {}", rule.pattern, target.join(", "));
+                }
+            }
+            if let Some(replay) = &config.ai.replay {
+                println!("\n[Replay]");
+                println!("  Fixtures: {}", replay.dir);
+                if replay.record { println!("  Recording: on"); }
+            }
 
             // Show available providers
-            let registry = utils::create_ai_registry(&config);
+            let registry = AiRegistry::for_project(root, &config);
             println!("\n{}", "Available Providers:".bold());
-            for name in ["claude", "openai", "ollama", "claude-cli", "manual"] {
+            let profiles = config.ai.profiles.keys().map(String::as_str).filter(|n| !BUILTIN_PROVIDERS.contains(n));
+            for name in BUILTIN_PROVIDERS.iter().copied().chain(profiles) {
                 let status = if registry.provider_exists(name) {
                     "✔".green().to_string()
                 } else {
@@ -45,23 +92,26 @@ pub async fn execute(command: &AiConfigCommands) -> Result<()> {
         },
         AiConfigCommands::Test => {
             println!("Testing AI Connection...");
-            let config = Config::load()?;
+            let config = Config::load(root)?;
 
-            let provider = utils::get_ai_provider(&config).await?;
+            let provider = utils::get_ai_provider(root, &config).await?;
 
-            println!("Selected Provider: {}", provider.name().cyan());
+            println!("Selected Provider: {}", provider.label().cyan());
             
             let response = provider.complete(&CompletionRequest {
-                prompt: "Hello, just say 'Connected'.".to_string(),
                 max_tokens: Some(10),
-                model: None,
+                ..CompletionRequest::new("Hello, just say 'Connected'.")
             }).await?;
             
             println!("Response: {}", response.content.green());
             println!("{}", "Connection Successful! ✔".green().bold());
         },
         AiConfigCommands::SetProvider { name } => {
-            let valid = ["auto", "claude", "openai", "ollama", "claude-cli", "manual"];
+            let config = Config::load(root)?;
+            let valid: Vec<&str> = std::iter::once("auto")
+                .chain(BUILTIN_PROVIDERS.iter().copied())
+                .chain(config.ai.profiles.keys().map(String::as_str))
+                .collect();
             if !valid.contains(&name.as_str()) {
                 return Err(anyhow!(
                     "Unknown provider '{}'. Valid options: {}",
@@ -69,12 +119,12 @@ pub async fn execute(command: &AiConfigCommands) -> Result<()> {
                 ));
             }
 
-            let config_path = std::path::Path::new(".process/config.yaml");
+            let config_path = root.join(artifacts::PROCESS_DIR).join("config.yaml");
             if !config_path.exists() {
                 return Err(anyhow!("No .process/config.yaml found. Run 'process init' first."));
             }
 
-            let content = std::fs::read_to_string(config_path)?;
+            let content = std::fs::read_to_string(&config_path)?;
             let mut doc: serde_yaml::Value = serde_yaml::from_str(&content)?;
             
             if let Some(ai) = doc.get_mut("ai") {
@@ -87,7 +137,7 @@ pub async fn execute(command: &AiConfigCommands) -> Result<()> {
             }
 
             let updated = serde_yaml::to_string(&doc)?;
-            std::fs::write(config_path, updated)?;
+            artifacts::write(&config_path, updated)?;
 
             println!("Default provider set to: {}", name.green().bold());
         },
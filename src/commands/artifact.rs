@@ -0,0 +1,64 @@
+use anyhow::{anyhow, Result};
+use crate::cli::ArtifactCommands;
+use colored::Colorize;
+use process_core::artifacts;
+use process_core::history;
+use process_core::manifest::Manifest;
+use std::path::Path;
+
+pub fn execute(root: &Path, command: &ArtifactCommands) -> Result<()> {
+    match command {
+        ArtifactCommands::History { name } => {
+            let (kind, _) = history::parse_spec(name)?;
+            let name = kind.to_string();
+            let manifest = Manifest::load(root)?;
+            let versions = history::versions(&manifest, &name);
+
+            println!("{}", format!("━━━ History: {} ━━━", name).bold());
+            if versions.is_empty() {
+                println!("  (no saved versions yet — history starts with the next pass that writes it)");
+                return Ok(());
+            }
+            for v in versions.iter().rev() {
+                let provider = v.provider.as_deref().map(|p| format!(" via {}", p)).unwrap_or_default();
+                println!(
+                    "  {} {}  {}{}  {}",
+                    format!("@{}", v.version).cyan(),
+                    v.timestamp.dimmed(),
+                    v.produced_by,
+                    provider,
+                    v.content_hash[..12].dimmed()
+                );
+            }
+        }
+        ArtifactCommands::Show { spec } => {
+            let (kind, version) = history::parse_spec(spec)?;
+            let content = match version {
+                Some(version) => history::read(root, &Manifest::load(root)?, &kind.to_string(), version)?,
+                None => {
+                    let path = artifacts::path(root, &kind);
+                    std::fs::read_to_string(&path)
+                        .map_err(|_| anyhow!("Artifact '{}' not found at {}", kind, path.display()))?
+                }
+            };
+            print!("{}", content);
+        }
+        ArtifactCommands::Restore { spec } => {
+            let (kind, version) = history::parse_spec(spec)?;
+            let version = version
+                .ok_or_else(|| anyhow!("Specify the version to restore, e.g. `process artifact restore {}@1`", kind))?;
+
+            let mut manifest = Manifest::load(root)?;
+            let saved = history::restore(root, &mut manifest, &kind, version)?;
+            manifest.save(root)?;
+
+            match saved {
+                Some(new) => println!("{} Restored {}@{} (saved as @{})", "✓".green(), kind, version, new),
+                None => println!("{} {} already matches @{}", "✓".green(), kind, version),
+            }
+            println!("  Downstream artifacts built from the replaced version are now stale; `process pass plan` shows what will re-run.");
+        }
+    }
+
+    Ok(())
+}
@@ -3,20 +3,21 @@ use colored::Colorize;
 use process_ai::provider::CompletionRequest;
 use process_config::config::Config;
 use process_core::{phase::Phase, state::ProcessState};
+use process_core::artifacts;
+use process_core::pass::ArtifactKind;
 use std::fs;
 use std::path::Path;
 
-use crate::utils::{get_ai_provider, strip_markdown_code_block};
-use crate::prompts::PromptEngine;
+use crate::utils::{complete_live, get_routed_branch_provider, strip_markdown_code_block};
+use process_ai::prompts::PromptEngine;
 
-pub async fn execute(name: &str) -> Result<()> {
+pub async fn execute(root: &Path, name: &str) -> Result<()> {
     println!("{}", "Branch Abuse — Adversarial Testing".bold().blue());
 
-    let state = ProcessState::load()?;
+    let state = ProcessState::load(root)?;
     state.check_phase(Phase::Branching)?;
 
-    let branch_path = Path::new(".process/branches")
-        .join(format!("{}.yaml", name));
+    let branch_path = artifacts::path(root, &ArtifactKind::BranchHypothesis(name.to_string()));
     if !branch_path.exists() {
         bail!("Branch '{}' not found at {}", name, branch_path.display());
     }
@@ -24,28 +25,28 @@ pub async fn execute(name: &str) -> Result<()> {
     let branch_content = fs::read_to_string(&branch_path)
         .context("Failed to read branch file")?;
 
-    let config = Config::load()?;
-    let engine = PromptEngine::new(&config.ai.provider);
+    let config = Config::load(root)?;
+    let engine = PromptEngine::for_project(root, &config.ai.provider);
     let mut ctx = tera::Context::new();
     ctx.insert("branch", &branch_content);
     let prompt = engine.render("branch_abuse", &ctx)?;
 
     println!("Calling AI for adversarial testing...");
-    let provider = get_ai_provider(&config).await?;
-    println!("Using Provider: {}", provider.name().cyan());
+    let (provider, route) = get_routed_branch_provider(root, &config, "branch.abuse", "branch_abuse", name).await?;
+    println!("Using Provider: {}", provider.label().cyan());
 
-    let response = provider.complete(&CompletionRequest {
-        prompt,
+    let request = CompletionRequest {
         max_tokens: Some(4096),
-        model: None,
-    }).await?;
+        template: Some("branch_abuse".to_string()),
+        ..CompletionRequest::new(prompt)
+    };
+    let response = complete_live(provider.as_ref(), &request.routed(&route)).await?;
 
     let cleaned = strip_markdown_code_block(&response.content);
 
     // Save abuse test results
-    let abuse_path = Path::new(".process/branches")
-        .join(format!("{}-abuse.yaml", name));
-    fs::write(&abuse_path, cleaned)
+    let abuse_path = artifacts::path(root, &ArtifactKind::BranchAbuse(name.to_string()));
+    artifacts::write(&abuse_path, cleaned)
         .context("Failed to write abuse test file")?;
     println!("{} Abuse tests saved to {}", "✔".green(), abuse_path.display());
 
@@ -54,7 +55,7 @@ pub async fn execute(name: &str) -> Result<()> {
         "status: \"reviewing\"",
         "status: \"abuse-testing\"",
     );
-    fs::write(&branch_path, &updated)
+    artifacts::write(&branch_path, &updated)
         .context("Failed to update branch status")?;
 
     println!("{} Branch '{}' status → abuse-testing", "✔".green(), name);
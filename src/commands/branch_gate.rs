@@ -1,17 +1,18 @@
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use process_core::{phase::Phase, state::ProcessState};
+use process_core::artifacts;
+use process_core::pass::ArtifactKind;
 use std::fs;
 use std::path::Path;
 
-pub fn execute(name: &str) -> Result<()> {
+pub fn execute(root: &Path, name: &str) -> Result<()> {
     println!("{}", "Branch Gate — Merge Checklist".bold().blue());
 
-    let state = ProcessState::load()?;
+    let state = ProcessState::load(root)?;
     state.check_phase(Phase::Branching)?;
 
-    let branch_path = Path::new(".process/branches")
-        .join(format!("{}.yaml", name));
+    let branch_path = artifacts::path(root, &ArtifactKind::BranchHypothesis(name.to_string()));
     if !branch_path.exists() {
         bail!("Branch '{}' not found at {}", name, branch_path.display());
     }
@@ -22,8 +23,7 @@ pub fn execute(name: &str) -> Result<()> {
     let mut all_passed = true;
 
     // Check 1: Review completed
-    let review_path = Path::new(".process/branches")
-        .join(format!("{}-review.yaml", name));
+    let review_path = artifacts::path(root, &ArtifactKind::BranchReview(name.to_string()));
     if review_path.exists() {
         println!("  {} Review process completed", "✓".green());
     } else {
@@ -33,8 +33,7 @@ pub fn execute(name: &str) -> Result<()> {
     }
 
     // Check 2: Abuse tests
-    let abuse_path = Path::new(".process/branches")
-        .join(format!("{}-abuse.yaml", name));
+    let abuse_path = artifacts::path(root, &ArtifactKind::BranchAbuse(name.to_string()));
     if abuse_path.exists() {
         let abuse_content = fs::read_to_string(&abuse_path)
             .context("Failed to read abuse file")?;
@@ -51,7 +50,7 @@ pub fn execute(name: &str) -> Result<()> {
     }
 
     // Check 3: Scope creep
-    check_scope_creep(&branch_content, name)?;
+    check_scope_creep(root, &branch_content)?;
 
     // Summary
     println!();
@@ -70,11 +69,12 @@ pub fn execute(name: &str) -> Result<()> {
     Ok(())
 }
 
-fn check_scope_creep(branch_content: &str, name: &str) -> Result<()> {
+fn check_scope_creep(root: &Path, branch_content: &str) -> Result<()> {
     // Parse files_not_to_touch from branch definition
     // Simple heuristic: look for files_not_to_touch entries
     let output = std::process::Command::new("git")
         .args(["diff", "--name-only", "HEAD~1"])
+        .current_dir(root)
         .output();
 
     match output {
@@ -99,6 +99,5 @@ fn check_scope_creep(branch_content: &str, name: &str) -> Result<()> {
         }
     }
 
-    let _ = name; // used in caller context
     Ok(())
 }
@@ -3,23 +3,25 @@ use colored::Colorize;
 use process_ai::provider::CompletionRequest;
 use process_config::config::Config;
 use process_core::{phase::Phase, state::ProcessState};
+use process_core::artifacts;
+use process_core::pass::ArtifactKind;
 use std::fs;
 use std::path::Path;
 
-use crate::prompts::PromptEngine;
-use crate::utils::{get_branch_ai_provider, strip_markdown_code_block};
+use process_ai::prompts::PromptEngine;
+use crate::utils::{complete_live, get_branch_ai_provider, wrap_branch_provider, strip_markdown_code_block};
 
-pub async fn execute(name: &str) -> Result<()> {
+pub async fn execute(root: &Path, name: &str) -> Result<()> {
     println!(
         "{}",
         "Branch Implement — AI-Assisted Implementation".bold().blue()
     );
 
-    let state = ProcessState::load()?;
+    let state = ProcessState::load(root)?;
     state.check_phase(Phase::Branching)?;
 
     // Check branch file exists and is in implementing status
-    let branch_path = Path::new(".process/branches").join(format!("{}.yaml", name));
+    let branch_path = artifacts::path(root, &ArtifactKind::BranchHypothesis(name.to_string()));
     if !branch_path.exists() {
         bail!(
             "Branch '{}' not found. Run 'process branch new {}' first.",
@@ -40,15 +42,17 @@ pub async fn execute(name: &str) -> Result<()> {
     }
 
     // Read project context
-    let seed = read_optional(".process/seed.yaml");
-    let rules = read_optional(".process/converge_summary.yaml");
-    let skeleton = read_optional(".process/skeleton.yaml");
+    let seed = read_optional(root, &ArtifactKind::Seed);
+    let rules = read_optional(root, &ArtifactKind::Rules);
+    let skeleton = read_optional(root, &ArtifactKind::Skeleton);
 
     // Load AI provider (branch-level override or global)
-    let config = Config::load()?;
-    let engine = PromptEngine::new(&config.ai.provider);
+    let config = Config::load(root)?;
+    let engine = PromptEngine::for_project(root, &config.ai.provider);
+    let mut route = config.ai.route("branch.implement", Some("branch.implement"));
     let (provider, provider_name) =
-        get_branch_ai_provider(&config, &branch_content).await?;
+        get_branch_ai_provider(root, &config, &branch_content, &mut route).await?;
+    let provider = wrap_branch_provider(provider, root, &config, "branch.implement", name);
 
     println!("Using Provider: {}", provider_name.cyan());
 
@@ -63,19 +67,18 @@ pub async fn execute(name: &str) -> Result<()> {
 
     println!("  {} Generating implementation plan...", "→".cyan());
 
-    let response = provider
-        .complete(&CompletionRequest {
-            prompt,
-            max_tokens: Some(4096),
-            model: None,
-        })
-        .await?;
+    let request = CompletionRequest {
+        max_tokens: Some(4096),
+        template: Some("branch.implement".to_string()),
+        ..CompletionRequest::new(prompt)
+    };
+    let response = complete_live(provider.as_ref(), &request.routed(&route)).await?;
 
     let cleaned = strip_markdown_code_block(&response.content);
 
     // Save implementation plan
-    let plan_path = Path::new(".process/branches").join(format!("{}-implement.yaml", name));
-    fs::write(&plan_path, cleaned).context("Failed to write implementation plan")?;
+    let plan_path = artifacts::path(root, &ArtifactKind::BranchImplementation(name.to_string()));
+    artifacts::write(&plan_path, cleaned).context("Failed to write implementation plan")?;
 
     println!(
         "{} Implementation plan saved to {}",
@@ -93,6 +96,7 @@ pub async fn execute(name: &str) -> Result<()> {
     Ok(())
 }
 
-fn read_optional(path: &str) -> String {
-    fs::read_to_string(path).unwrap_or_else(|_| format!("(no {} found)", path))
+fn read_optional(root: &Path, kind: &ArtifactKind) -> String {
+    fs::read_to_string(artifacts::path(root, kind))
+        .unwrap_or_else(|_| format!("(no {} found)", artifacts::relative_path(kind)))
 }
@@ -1,17 +1,18 @@
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use process_core::{phase::Phase, state::ProcessState};
+use process_core::artifacts;
+use process_core::pass::ArtifactKind;
 use std::fs;
 use std::path::Path;
 
-pub fn execute(name: &str) -> Result<()> {
+pub fn execute(root: &Path, name: &str) -> Result<()> {
     println!("{}", "Branch Merge — Mark as Merged".bold().blue());
 
-    let state = ProcessState::load()?;
+    let state = ProcessState::load(root)?;
     state.check_phase(Phase::Branching)?;
 
-    let branch_path = Path::new(".process/branches")
-        .join(format!("{}.yaml", name));
+    let branch_path = artifacts::path(root, &ArtifactKind::BranchHypothesis(name.to_string()));
     if !branch_path.exists() {
         bail!("Branch '{}' not found at {}", name, branch_path.display());
     }
@@ -25,7 +26,7 @@ pub fn execute(name: &str) -> Result<()> {
         .replace("status: \"reviewing\"", "status: \"merged\"")
         .replace("status: \"implementing\"", "status: \"merged\"");
 
-    fs::write(&branch_path, &updated)
+    artifacts::write(&branch_path, &updated)
         .context("Failed to update branch status")?;
 
     println!("{} Branch '{}' marked as merged", "✔".green(), name);
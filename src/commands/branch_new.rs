@@ -1,27 +1,28 @@
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use process_core::{phase::Phase, state::ProcessState};
+use process_core::artifacts;
+use process_core::pass::ArtifactKind;
 use std::fs;
 use std::path::Path;
 
-pub fn execute(name: &str) -> Result<()> {
+pub fn execute(root: &Path, name: &str) -> Result<()> {
     println!("{}", "Branch New — Creating Branch Hypothesis".bold().blue());
 
-    let mut state = ProcessState::load()?;
+    let mut state = ProcessState::load(root)?;
     state.check_phase(Phase::Skeleton)?;
 
-    // Ensure branches directory exists
-    let branches_dir = Path::new(".process/branches");
-    if !branches_dir.exists() {
-        fs::create_dir_all(branches_dir)
-            .context("Failed to create branches directory")?;
-    }
-
-    let branch_path = branches_dir.join(format!("{}.yaml", name));
+    let branch_path = artifacts::path(root, &ArtifactKind::BranchHypothesis(name.to_string()));
     if branch_path.exists() {
         bail!("Branch '{}' already exists at {}", name, branch_path.display());
     }
 
+    // Each branch gets its own directory for hypothesis, review, abuse and plan
+    if let Some(branch_dir) = branch_path.parent() {
+        fs::create_dir_all(branch_dir)
+            .context("Failed to create branch directory")?;
+    }
+
     let template = format!(
 r#"# Branch Hypothesis: {name}
 hypothesis: ""
@@ -61,12 +62,12 @@ status: "defined"
 #     base_url: "https://your-proxy.com"
 "#);
 
-    fs::write(&branch_path, &template)
+    artifacts::write(&branch_path, &template)
         .context("Failed to write branch file")?;
 
     // Advance to Branching phase
     state.set_phase(Phase::Branching);
-    state.save()?;
+    state.save(root)?;
 
     println!("{} Branch hypothesis created: {}", "✔".green(), branch_path.display());
     println!("\nNext steps:");
@@ -1,24 +1,27 @@
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use dialoguer::Input;
-use process_ai::provider::CompletionRequest;
+use process_ai::provider::{CompletionRequest, ResponseSchema};
 use process_config::config::Config;
 use process_core::{phase::Phase, state::ProcessState};
+use process_core::artifacts;
+use process_core::pass::ArtifactKind;
+use process_reviews::report::{BranchReview, ConflictRuling, ReviewReport, Verdict};
 use process_reviews::template::ReviewRegistry;
 use std::fs;
 use std::path::Path;
 
-use crate::prompts::PromptEngine;
-use crate::utils::{get_ai_provider, strip_markdown_code_block};
+use process_ai::prompts::PromptEngine;
+use crate::utils::{complete_typed_live, get_routed_branch_provider};
 
-pub async fn execute(name: &str, role_filter: Option<&str>) -> Result<()> {
+pub async fn execute(root: &Path, name: &str, role_filter: Option<&str>) -> Result<()> {
     println!("{}", "Branch Review — Multi-Role AI Review".bold().blue());
 
-    let state = ProcessState::load()?;
+    let state = ProcessState::load(root)?;
     state.check_phase(Phase::Branching)?;
 
     // Check branch file exists
-    let branch_path = Path::new(".process/branches").join(format!("{}.yaml", name));
+    let branch_path = artifacts::path(root, &ArtifactKind::BranchHypothesis(name.to_string()));
     if !branch_path.exists() {
         bail!("Branch '{}' not found at {}", name, branch_path.display());
     }
@@ -27,18 +30,15 @@ pub async fn execute(name: &str, role_filter: Option<&str>) -> Result<()> {
         fs::read_to_string(&branch_path).context("Failed to read branch file")?;
 
     // Read rules
-    let rules_path = Path::new(".process/converge_summary.yaml");
+    let rules_path = artifacts::path(root, &ArtifactKind::Rules);
     let rules_content = if rules_path.exists() {
-        fs::read_to_string(rules_path).context("Failed to read rules")?
+        fs::read_to_string(&rules_path).context("Failed to read rules")?
     } else {
         String::from("(no rules file found)")
     };
 
-    let config = Config::load()?;
-    let engine = PromptEngine::new(&config.ai.provider);
-    let provider = get_ai_provider(&config).await?;
-    println!("Using Provider: {}", provider.name().cyan());
-
+    let config = Config::load(root)?;
+    let engine = PromptEngine::for_project(root, &config.ai.provider);
     let registry = ReviewRegistry::default();
 
     // Determine which roles to run
@@ -66,14 +66,17 @@ pub async fn execute(name: &str, role_filter: Option<&str>) -> Result<()> {
             .join(", ")
     );
 
-    let mut all_reviews = String::from("reviews:\n");
-    let mut has_any_fail = false;
+    let mut reports = Vec::new();
 
     for tmpl in &templates {
+        // Each role can be routed to its own provider and model
+        let (provider, route) =
+            get_routed_branch_provider(root, &config, "branch.review", tmpl.prompt_template_name(), name).await?;
         println!(
-            "  {} Running {} review...",
+            "  {} Running {} review ({})...",
             "→".cyan(),
-            tmpl.role().bold()
+            tmpl.role().bold(),
+            provider.label().cyan()
         );
 
         // Build per-role prompt
@@ -82,59 +85,52 @@ pub async fn execute(name: &str, role_filter: Option<&str>) -> Result<()> {
         ctx.insert("branch", &branch_content);
         let prompt = engine.render(tmpl.prompt_template_name(), &ctx)?;
 
-        let response = provider
-            .complete(&CompletionRequest {
-                prompt,
-                max_tokens: Some(2048),
-                model: None,
-            })
-            .await?;
-
-        let cleaned = strip_markdown_code_block(&response.content);
-
-        // Detect verdict
-        let verdict = if cleaned.contains("verdict: \"fail\"") {
-            has_any_fail = true;
-            "FAIL".red().bold()
-        } else if cleaned.contains("verdict: \"conditional_pass\"") {
-            "CONDITIONAL".yellow().bold()
-        } else {
-            "PASS".green().bold()
+        let request = CompletionRequest {
+            max_tokens: Some(2048),
+            response_schema: Some(ResponseSchema {
+                name: "review_report".to_string(),
+                schema: ReviewReport::json_schema(),
+            }),
+            template: Some(tmpl.prompt_template_name().to_string()),
+            ..CompletionRequest::new(prompt)
+        };
+        let mut report: ReviewReport = complete_typed_live(provider.as_ref(), &request.routed(&route))
+            .await
+            .with_context(|| format!("{} review failed", tmpl.role()))?;
+        // The template decides the role, not the model
+        report.role = tmpl.role().to_string();
+
+        let verdict = match report.verdict {
+            Verdict::Fail => "FAIL".red().bold(),
+            Verdict::ConditionalPass => "CONDITIONAL".yellow().bold(),
+            Verdict::Pass => "PASS".green().bold(),
         };
-
         println!("    {} {} → {}", "✔".green(), tmpl.role(), verdict);
 
-        // Accumulate into combined YAML
-        all_reviews.push_str(&format!("  # --- {} ---\n", tmpl.role()));
-        for line in cleaned.lines() {
-            all_reviews.push_str(&format!("  {}\n", line));
-        }
-        all_reviews.push('\n');
+        reports.push(report);
     }
 
-    // Add overall verdict
-    let overall = if has_any_fail {
-        "fail"
+    let mut review = BranchReview::new(reports);
+
+    // Conflict detection and human ruling
+    if review.has_conflicts() {
+        review.human_conflict_ruling = Some(prompt_conflict_ruling()?);
     } else {
-        "pass"
-    };
-    all_reviews.push_str(&format!("overall_verdict: \"{}\"\n", overall));
+        println!("{} No inter-role conflicts detected", "✔".green());
+    }
 
     // Save combined review
-    let review_path = Path::new(".process/branches").join(format!("{}-review.yaml", name));
-    fs::write(&review_path, &all_reviews).context("Failed to write review file")?;
+    let review_path = artifacts::path(root, &ArtifactKind::BranchReview(name.to_string()));
+    artifacts::write(&review_path, serde_yaml::to_string(&review)?).context("Failed to write review file")?;
     println!(
         "\n{} Review saved to {}",
         "✔".green(),
         review_path.display()
     );
 
-    // Conflict detection and human ruling
-    prompt_conflict_ruling(&review_path)?;
-
     // Update branch status
     let updated = branch_content.replace("status: \"implementing\"", "status: \"reviewing\"");
-    fs::write(&branch_path, &updated).context("Failed to update branch status")?;
+    artifacts::write(&branch_path, &updated).context("Failed to update branch status")?;
 
     println!("{} Branch '{}' status → reviewing", "✔".green(), name);
     println!(
@@ -145,24 +141,7 @@ pub async fn execute(name: &str, role_filter: Option<&str>) -> Result<()> {
     Ok(())
 }
 
-fn prompt_conflict_ruling(review_path: &Path) -> Result<()> {
-    let content = fs::read_to_string(review_path)
-        .context("Failed to read review")?;
-
-    // Detect if any role gave a different verdict
-    let verdicts: Vec<&str> = content
-        .lines()
-        .filter(|l| l.contains("verdict:"))
-        .collect();
-
-    let has_conflicts = verdicts.len() > 1
-        && !verdicts.windows(2).all(|w| w[0] == w[1]);
-
-    if !has_conflicts {
-        println!("{} No inter-role conflicts detected", "✔".green());
-        return Ok(());
-    }
-
+fn prompt_conflict_ruling() -> Result<ConflictRuling> {
     println!("\n{}", "━━━ Role Conflict Resolution ━━━".bold().cyan());
     println!("Reviewers disagree. Please make a ruling:\n");
 
@@ -176,22 +155,12 @@ fn prompt_conflict_ruling(review_path: &Path) -> Result<()> {
         .interact_text()
         .context("Failed to read reasoning")?;
 
-    let risk: String = Input::new()
+    let risk_accepted: String = Input::new()
         .with_prompt("Risk accepted?")
         .default("N/A".to_string())
         .interact_text()
         .context("Failed to read risk")?;
 
-    let ruling_yaml = format!(
-        "\nhuman_conflict_ruling:\n  ruling: \"{}\"\n  reasoning: \"{}\"\n  risk_accepted: \"{}\"\n",
-        ruling, reasoning, risk
-    );
-
-    let mut full = content;
-    full.push_str(&ruling_yaml);
-    fs::write(review_path, full)
-        .context("Failed to update review with ruling")?;
-
     println!("{} Conflict ruling recorded", "✔".green());
-    Ok(())
+    Ok(ConflictRuling { ruling, reasoning, risk_accepted })
 }
@@ -1,17 +1,19 @@
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use process_core::{phase::Phase, state::ProcessState};
+use process_core::artifacts;
+use process_core::pass::ArtifactKind;
 use std::fs;
 use std::path::Path;
 
-pub fn execute(name: &str) -> Result<()> {
+pub fn execute(root: &Path, name: &str) -> Result<()> {
     println!("{}", "Branch Start — Validate & Create Git Branch".bold().blue());
 
-    let state = ProcessState::load()?;
+    let state = ProcessState::load(root)?;
     state.check_phase(Phase::Skeleton)?;
 
     // Check branch file exists
-    let branch_path = Path::new(".process/branches").join(format!("{}.yaml", name));
+    let branch_path = artifacts::path(root, &ArtifactKind::BranchHypothesis(name.to_string()));
     if !branch_path.exists() {
         bail!("Branch '{}' not found. Run 'process branch new {}' first.", name, name);
     }
@@ -26,13 +28,14 @@ pub fn execute(name: &str) -> Result<()> {
 
     // Update status: defined → implementing
     let updated = content.replace("status: \"defined\"", "status: \"implementing\"");
-    fs::write(&branch_path, &updated)
+    artifacts::write(&branch_path, &updated)
         .context("Failed to update branch status")?;
 
     // Create git branch
     let git_branch = format!("feature/{}", name);
     let output = std::process::Command::new("git")
         .args(["checkout", "-b", &git_branch])
+        .current_dir(root)
         .output()
         .context("Failed to run git checkout")?;
 
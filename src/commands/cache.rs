@@ -0,0 +1,22 @@
+use anyhow::Result;
+use crate::cli::CacheCommands;
+use colored::Colorize;
+use process_ai::cache;
+use std::path::Path;
+use std::time::Duration;
+
+pub fn execute(root: &Path, command: &CacheCommands) -> Result<()> {
+    match command {
+        CacheCommands::Prune { older_than } => {
+            let limit = older_than.map(|days| Duration::from_secs(days * 24 * 60 * 60));
+            let stats = cache::prune(root, limit)?;
+            println!(
+                "{} Removed {} cached response(s), {:.1} KiB",
+                "✔".green(),
+                stats.removed,
+                stats.bytes as f64 / 1024.0
+            );
+        }
+    }
+    Ok(())
+}
@@ -6,15 +6,14 @@ use process_checks::sensitive::SensitiveInfoCheck;
 use process_checks::todo::TodoCheck;
 use process_checks::lint::LintCheck;
 use process_checks::test::TestCheck;
+use std::path::Path;
 
-pub fn execute(command: &CheckCommands) -> Result<()> {
-    let cwd = std::env::current_dir()?;
-
+pub fn execute(root: &Path, command: &CheckCommands) -> Result<()> {
     match command {
-        CheckCommands::Sensitive => { run_check(&SensitiveInfoCheck, &cwd)?; Ok(()) }
-        CheckCommands::Todo => { run_check(&TodoCheck, &cwd)?; Ok(()) }
-        CheckCommands::Lint => { run_check(&LintCheck, &cwd)?; Ok(()) }
-        CheckCommands::Test => { run_check(&TestCheck, &cwd)?; Ok(()) }
+        CheckCommands::Sensitive => { run_check(&SensitiveInfoCheck, root)?; Ok(()) }
+        CheckCommands::Todo => { run_check(&TodoCheck, root)?; Ok(()) }
+        CheckCommands::Lint => { run_check(&LintCheck, root)?; Ok(()) }
+        CheckCommands::Test => { run_check(&TestCheck, root)?; Ok(()) }
         CheckCommands::All => {
             println!("{}", "Running all checks...".bold());
             let checks: Vec<Box<dyn Check>> = vec![
@@ -25,7 +24,7 @@ pub fn execute(command: &CheckCommands) -> Result<()> {
             ];
             let mut all_passed = true;
             for check in &checks {
-                let result = run_check(check.as_ref(), &cwd)?;
+                let result = run_check(check.as_ref(), root)?;
                 if !result.passed {
                     all_passed = false;
                 }
@@ -0,0 +1,69 @@
+use anyhow::Result;
+use crate::cli::CycleCommands;
+use colored::Colorize;
+use process_core::cycle;
+use process_core::manifest::Manifest;
+use process_core::phase::Phase;
+use process_core::state::ProcessState;
+use std::path::Path;
+
+pub fn execute(root: &Path, command: &CycleCommands) -> Result<()> {
+    match command {
+        CycleCommands::New { cycle_type, trigger, id } => {
+            let mut state = ProcessState::load(root)?;
+            if state.current_phase < Phase::Done {
+                println!(
+                    "{} Cycle '{}' is at {}, not Done; archiving it as is.",
+                    "⚠".yellow(), state.cycle, state.current_phase
+                );
+            }
+
+            let transition = cycle::start(root, &mut state, *cycle_type, trigger, id.as_deref())?;
+            state.save(root)?;
+
+            println!(
+                "{} Archived cycle {} to {} ({} artifacts)",
+                "✔".green(),
+                transition.previous.id.cyan(),
+                cycle::archive_dir(root, &transition.previous.id).display(),
+                transition.previous.artifacts.len()
+            );
+            println!(
+                "{} Started {} cycle {} (parent: {})",
+                "✔".green(),
+                transition.next.cycle_type,
+                transition.next.id.cyan().bold(),
+                transition.previous.id
+            );
+            println!(
+                "  seed.yaml pre-filled from the previous seed and {} postmortem lesson(s).",
+                transition.lessons
+            );
+            println!("\n{}", "Next: review .process/seed.yaml, then run `process seed-validate`.".dimmed());
+        }
+        CycleCommands::List => {
+            let manifest = Manifest::load(root)?;
+            println!("{}", "━━━ Cycles ━━━".bold());
+            if manifest.cycles.is_empty() {
+                let state = ProcessState::load(root)?;
+                println!("  {} ({}) — active", state.cycle.cyan(), state.cycle_type);
+                return Ok(());
+            }
+            for c in &manifest.cycles {
+                let status = match &c.completed {
+                    Some(completed) => format!("{} → {}", c.started, completed),
+                    None => format!("{} → active", c.started),
+                };
+                println!("  {} ({}) {}", c.id.cyan(), c.cycle_type, status.dimmed());
+                if let Some(parent) = &c.parent {
+                    println!("    parent: {}", parent);
+                }
+                if let Some(trigger) = &c.trigger {
+                    println!("    trigger: {}", trigger);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,28 +1,26 @@
 use anyhow::Result;
 use colored::Colorize;
 use process_core::{phase::Phase, state::ProcessState};
+use process_core::artifacts;
+use std::path::Path;
 
-pub fn execute() -> Result<()> {
+pub fn execute(root: &Path) -> Result<()> {
     println!("{}", "Phase 7: Done — Project Complete".bold().blue());
 
-    let mut state = ProcessState::load()?;
+    let mut state = ProcessState::load(root)?;
     state.check_phase(Phase::Postmortem)?;
 
     state.set_phase(Phase::Done);
-    state.save()?;
+    state.save(root)?;
 
     println!("{} Project marked as complete!", "✔".green());
     println!();
     println!("Final status: {}", state.current_phase);
     println!();
     println!("Artifacts in .process/:");
-    println!("  - seed.yaml");
-    println!("  - diverge_summary.yaml");
-    println!("  - converge_summary.yaml");
-    println!("  - skeleton.yaml");
-    println!("  - learnings.yaml");
-    println!("  - friction.yaml");
-    println!("  - postmortem.yaml");
+    for kind in &artifacts::PROJECT_ARTIFACTS {
+        println!("  - {}", artifacts::relative_path(kind));
+    }
 
     Ok(())
 }
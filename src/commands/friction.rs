@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
+use process_core::artifacts;
+use process_core::pass::ArtifactKind;
 use process_core::state::ProcessState;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -21,10 +23,10 @@ struct FrictionPoint {
     action: String,
 }
 
-pub fn execute(branch: &str, description: &str, severity: &str) -> Result<()> {
+pub fn execute(root: &Path, branch: &str, description: &str, severity: &str) -> Result<()> {
     println!("{}", "Recording Friction Point".bold().blue());
 
-    let _state = ProcessState::load()?;
+    let _state = ProcessState::load(root)?;
 
     // Validate severity
     let valid = ["high", "medium", "low"];
@@ -32,10 +34,10 @@ pub fn execute(branch: &str, description: &str, severity: &str) -> Result<()> {
         bail!("Invalid severity '{}'. Valid: {:?}", severity, valid);
     }
 
-    let path = Path::new(".process/friction.yaml");
+    let path = artifacts::path(root, &ArtifactKind::Friction);
 
     let mut file = if path.exists() {
-        let content = fs::read_to_string(path)
+        let content = fs::read_to_string(&path)
             .context("Failed to read friction.yaml")?;
         serde_yaml::from_str::<FrictionFile>(&content)
             .unwrap_or(FrictionFile { friction_points: vec![] })
@@ -53,7 +55,7 @@ pub fn execute(branch: &str, description: &str, severity: &str) -> Result<()> {
 
     let content = serde_yaml::to_string(&file)
         .context("Failed to serialize friction")?;
-    fs::write(path, content)
+    artifacts::write(&path, content)
         .context("Failed to write friction.yaml")?;
 
     println!("{} Friction point recorded ({} total)", "✔".green(), file.friction_points.len());
@@ -8,14 +8,12 @@ use process_generators::makefile::MakefileGenerator;
 use process_generators::ide::IdeGenerator;
 use std::path::Path;
 
-pub fn execute(command: &GenerateCommands) -> Result<()> {
-    let cwd = std::env::current_dir()?;
-
+pub fn execute(root: &Path, command: &GenerateCommands) -> Result<()> {
     match command {
-        GenerateCommands::GitHooks => run_generator(&GitHooksGenerator, &cwd),
-        GenerateCommands::Cicd => run_generator(&CiCdGenerator, &cwd),
-        GenerateCommands::Makefile => run_generator(&MakefileGenerator, &cwd),
-        GenerateCommands::Ide => run_generator(&IdeGenerator, &cwd),
+        GenerateCommands::GitHooks => run_generator(&GitHooksGenerator, root),
+        GenerateCommands::Cicd => run_generator(&CiCdGenerator, root),
+        GenerateCommands::Makefile => run_generator(&MakefileGenerator, root),
+        GenerateCommands::Ide => run_generator(&IdeGenerator, root),
         GenerateCommands::All => {
             println!("{}", "Running all generators...".bold());
             let generators: Vec<Box<dyn Generator>> = vec![
@@ -25,7 +23,7 @@ pub fn execute(command: &GenerateCommands) -> Result<()> {
                 Box::new(IdeGenerator),
             ];
             for gen in &generators {
-                run_generator(gen.as_ref(), &cwd)?;
+                run_generator(gen.as_ref(), root)?;
             }
             println!("\n{}", "✅ All generators complete".green().bold());
             Ok(())
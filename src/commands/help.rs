@@ -49,7 +49,7 @@ pub fn execute() {
         ("adopt infer-conventions", "Infer coding conventions (AI)"),
         ("adopt scan-git-history", "Extract decisions from git (AI)"),
         ("adopt gap-analysis", "Identify missing decisions (AI)"),
-        ("adopt all", "Run all adopt passes"),
+        ("adopt all", "Run all adopt passes (--jobs N scans in parallel)"),
     ]);
 
     print_section("Automation", &[
@@ -59,14 +59,30 @@ pub fn execute() {
 
     print_section("Pass Engine", &[
         ("pass run <name>", "Run a specific pass"),
-        ("pass list", "List all available passes"),
+        ("pass list", "List built-in and process-pass-* script passes"),
+        ("pass plan", "Preview run-all (--format dot|json)"),
         ("pass run-all", "Run all passes in dependency order"),
+        ("pipeline run <name>", "Run a pipeline (default/startup-fast/adopt or custom)"),
+        ("pipeline list", "List built-in and project pipelines"),
+        ("artifact history <name>", "List saved versions of an artifact"),
+        ("artifact show <name>[@n]", "Print an artifact or a saved version"),
+        ("artifact restore <name>@<n>", "Make a saved version current again"),
+    ]);
+
+    print_section("Cycles", &[
+        ("phase reopen <phase> --reason <why>", "Redo a phase; later artifacts become superseded"),
+        ("cycle new --type evolution --trigger <why>", "Archive this cycle, seed the next from its postmortem"),
+        ("cycle list", "Show cycle lineage"),
     ]);
 
     print_section("Utilities", &[
         ("learn <lesson> [-c category]", "Record a learning"),
         ("friction <branch> <desc> [-s severity]", "Record friction point"),
+        ("usage [--by phase|pass|branch|provider]", "AI token usage and estimated cost"),
+        ("cache prune [--older-than <days>]", "Delete cached AI responses"),
+        ("--no-cache | --refresh <command>", "Bypass, or re-fetch into, the AI response cache (ai.cache)"),
         ("completions bash|zsh|fish", "Generate shell completions"),
+        ("--project-dir <dir> <command>", "Run against another project (default: nearest .process/ upward)"),
     ]);
 
     println!("\n{}", "Use `process <command> --help` for detailed usage.".dimmed());
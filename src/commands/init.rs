@@ -2,11 +2,13 @@ use anyhow::{Result, Context};
 use colored::Colorize;
 use std::fs;
 use std::path::Path;
+use process_core::artifacts;
+use process_core::pass::ArtifactKind;
 use process_core::state::ProcessState;
 // use process_core::phase::Phase;
 
-pub async fn execute(force: bool) -> Result<()> {
-    let process_dir = Path::new(".process");
+pub async fn execute(root: &Path, force: bool) -> Result<()> {
+    let process_dir = root.join(artifacts::PROCESS_DIR);
 
     if process_dir.exists() && !force {
         println!("{}", "Project already initialized. Use --force to re-initialize.".yellow());
@@ -14,10 +16,10 @@ pub async fn execute(force: bool) -> Result<()> {
     }
 
     // 1. Create directory structure
-    fs::create_dir_all(process_dir).context("Failed to create .process directory")?;
+    fs::create_dir_all(&process_dir).context("Failed to create .process directory")?;
     
     // 2. Create seed.yaml template
-    let seed_path = process_dir.join("seed.yaml");
+    let seed_path = artifacts::path(root, &ArtifactKind::Seed);
     if !seed_path.exists() || force {
         let seed_content = r#"# .process/seed.yaml — 结构化输入，缺一不可
 idea: "一句话描述核心想法"
@@ -34,7 +36,7 @@ success_criteria:
 reversibility_budget: "high"
 # high = 可以大胆实验; medium = 适度谨慎; low = 每步都要可回退
 "#;
-        fs::write(&seed_path, seed_content).context("Failed to write seed.yaml")?;
+        artifacts::write(&seed_path, seed_content).context("Failed to write seed.yaml")?;
         println!("{} Created {}", "✔".green(), seed_path.display());
     }
 
@@ -48,13 +50,20 @@ reversibility_budget: "high"
 settings:
   auto_save: true
 "#;
-        fs::write(&config_path, config_content).context("Failed to write config.yaml")?;
+        artifacts::write(&config_path, config_content).context("Failed to write config.yaml")?;
         println!("{} Created {}", "✔".green(), config_path.display());
     }
 
-    // 4. Initialize State
+    // 4. Keep the lock file and cached AI responses out of version control
+    let gitignore_path = process_dir.join(".gitignore");
+    if !gitignore_path.exists() {
+        artifacts::write(&gitignore_path, format!("{}\ncache/\n", process_core::lock::LOCK_FILE))
+            .context("Failed to write .process/.gitignore")?;
+    }
+
+    // 5. Initialize State
     let state = ProcessState::default(); 
-    state.save().context("Failed to save initial state")?;
+    state.save(root).context("Failed to save initial state")?;
     println!("{} Initialized state to Seed", "✔".green());
     
     println!("{}", "Project initialized successfully! ".green().bold());
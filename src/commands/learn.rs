@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
+use process_core::artifacts;
+use process_core::pass::ArtifactKind;
 use process_core::state::ProcessState;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,16 +22,16 @@ struct Learning {
     phase: String,
 }
 
-pub fn execute(lesson: &str, category: &str) -> Result<()> {
+pub fn execute(root: &Path, lesson: &str, category: &str) -> Result<()> {
     println!("{}", "Recording Learning".bold().blue());
 
-    let state = ProcessState::load()?;
+    let state = ProcessState::load(root)?;
 
-    let learnings_path = Path::new(".process/learnings.yaml");
+    let learnings_path = artifacts::path(root, &ArtifactKind::Learnings);
 
     // Load existing or create new
     let mut file = if learnings_path.exists() {
-        let content = fs::read_to_string(learnings_path)
+        let content = fs::read_to_string(&learnings_path)
             .context("Failed to read learnings.yaml")?;
         serde_yaml::from_str::<LearningsFile>(&content)
             .unwrap_or(LearningsFile { learnings: vec![] })
@@ -50,7 +52,7 @@ pub fn execute(lesson: &str, category: &str) -> Result<()> {
     // Save
     let content = serde_yaml::to_string(&file)
         .context("Failed to serialize learnings")?;
-    fs::write(learnings_path, content)
+    artifacts::write(&learnings_path, content)
         .context("Failed to write learnings.yaml")?;
 
     println!("{} Learning recorded ({} total)", "✔".green(), file.learnings.len());
@@ -22,7 +22,6 @@ pub mod branch_merge;
 pub mod stabilize;
 pub mod postmortem;
 pub mod done;
-pub mod adopt_utils;
 pub mod adopt_scan_structure;
 pub mod adopt_scan_dependencies;
 pub mod adopt_infer_conventions;
@@ -32,4 +31,10 @@ pub mod adopt_all;
 pub mod generate;
 pub mod check;
 pub mod pass;
+pub mod pipeline;
+pub mod artifact;
+pub mod cycle;
+pub mod phase;
+pub mod usage;
+pub mod cache;
 pub mod help;
@@ -1,17 +1,20 @@
 use anyhow::Result;
-use crate::cli::PassCommands;
+use crate::cli::{PassCommands, PlanFormat};
 use colored::Colorize;
-use process_core::pass_manager::PassManager;
+use process_core::pass::{PassKind, PassServices};
+use process_core::pass_manager::{PlanEntry, RunOptions};
 
-pub fn execute(command: &PassCommands) -> Result<()> {
-    let cwd = std::env::current_dir()?;
-
-    // Build the pass manager with all registered passes
-    let mut manager = build_pass_manager();
+use crate::passes::{build_adopt_pass_manager, project_pass_manager, PassSet};
+use std::path::Path;
 
+pub async fn execute(root: &Path, command: &PassCommands) -> Result<()> {
+    // Built-in passes plus any process-pass-* scripts; the adopt passes
+    // have a manager of their own
     match command {
         PassCommands::List => {
             println!("{}", "━━━ Registered Passes ━━━".bold());
+            let manager = project_pass_manager(root, PassSet::Phases)?;
+            let adopt = build_adopt_pass_manager(PassServices::default());
             let passes = manager.list_passes();
             if passes.is_empty() {
                 println!("  (no passes registered yet)");
@@ -21,15 +24,29 @@ pub fn execute(command: &PassCommands) -> Result<()> {
                 }
                 println!("\n  {} pass(es) total", passes.len());
             }
+
+            println!("\n{}", "━━━ Adopt Passes ━━━".bold());
+            for (name, desc) in adopt.list_passes() {
+                println!("  {} — {}", name.cyan(), desc);
+            }
+        }
+        PassCommands::Plan { format } => {
+            let plan = project_pass_manager(root, PassSet::Phases)?.plan(root)?;
+            match format {
+                PlanFormat::Table => print_plan_table(&plan),
+                PlanFormat::Dot => print!("{}", plan_to_dot(&plan)),
+                PlanFormat::Json => println!("{}", serde_json::to_string_pretty(&plan)?),
+            }
         }
         PassCommands::Run { name } => {
             println!("{} Running pass: {}", "▶".cyan(), name.bold());
-            manager.run_pass(name, &cwd)?;
+            project_pass_manager(root, PassSet::of(name))?.run_pass(name, root).await?;
             println!("{}", "✅ Pass complete".green().bold());
         }
-        PassCommands::RunAll => {
+        PassCommands::RunAll { incremental, jobs } => {
             println!("{}", "Running all passes in dependency order...".bold());
-            manager.run_all(&cwd)?;
+            let options = RunOptions { incremental: *incremental, jobs: *jobs };
+            project_pass_manager(root, PassSet::Phases)?.run_all(root, options).await?;
             println!("{}", "✅ All passes complete".green().bold());
         }
     }
@@ -37,18 +54,102 @@ pub fn execute(command: &PassCommands) -> Result<()> {
     Ok(())
 }
 
-/// Build PassManager with all built-in passes
-fn build_pass_manager() -> PassManager {
-    let manager = PassManager::new();
-    
-    // Built-in passes will be registered here as they are migrated
-    // from existing commands into Pass implementations.
-    //
-    // Example (future):
-    //   manager.register(SeedInitPass);
-    //   manager.register(DivergeGeneratePass);
-    //   manager.register(ConvergeAnalyzePass);
-    //   manager.register(SkeletonGeneratePass);
-    
-    manager
+fn print_plan_table(plan: &[PlanEntry]) {
+    println!("{}", "━━━ Pass Plan ━━━".bold());
+
+    let rows: Vec<[String; 3]> = plan.iter().map(|entry| {
+        let mut reads = entry.requires.clone();
+        reads.extend(entry.optional_requires.iter().map(|r| format!("{}?", r)));
+        [entry.name.clone(), or_dash(&reads), or_dash(&entry.produces)]
+    }).collect();
+    // Each column as wide as its widest cell
+    let width = |col: usize, header: &str| {
+        rows.iter().map(|r| r[col].chars().count()).chain([header.chars().count()]).max().unwrap_or(0)
+    };
+    let (name_w, reads_w, writes_w) = (width(0, "Pass"), width(1, "Reads"), width(2, "Writes"));
+
+    println!(
+        "  {:>2}  {:<name_w$}  {:<11}  {:<reads_w$}  {:<writes_w$}  Status",
+        "#", "Pass", "Kind", "Reads", "Writes"
+    );
+
+    for (i, (entry, [name, reads, writes])) in plan.iter().zip(&rows).enumerate() {
+        let kind = match entry.kind {
+            PassKind::Sync => "sync",
+            PassKind::AiAssisted => "ai",
+            PassKind::Interactive => "interactive",
+        };
+        let status = if entry.will_run {
+            format!("run — {}", entry.reason).yellow()
+        } else {
+            format!("skip — {}", entry.reason).green()
+        };
+        println!(
+            "  {:>2}  {:<name_w$}  {:<11}  {:<reads_w$}  {:<writes_w$}  {}",
+            i + 1,
+            name,
+            kind,
+            reads,
+            writes,
+            status
+        );
+    }
+
+    let running = plan.iter().filter(|e| e.will_run).count();
+    let ai = plan.iter().filter(|e| e.will_run && e.kind == PassKind::AiAssisted).count();
+    let interactive: Vec<&str> = plan.iter()
+        .filter(|e| e.will_run && e.kind == PassKind::Interactive)
+        .map(|e| e.name.as_str())
+        .collect();
+
+    println!("\n  {} of {} pass(es) would run, {} calling AI", running, plan.len(), ai);
+    if !interactive.is_empty() {
+        println!("  {} Will block for input: {}", "⚠".yellow(), interactive.join(", "));
+    }
+}
+
+fn or_dash(items: &[String]) -> String {
+    if items.is_empty() {
+        "-".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+/// Graphviz rendering: one node per pass, edges labelled with the artifact
+/// flowing between them. Interactive passes are drawn as octagons and
+/// passes that would run are filled.
+fn plan_to_dot(plan: &[PlanEntry]) -> String {
+    let mut dot = String::from("digraph passes {\n    rankdir=LR;\n    node [shape=box, style=rounded];\n");
+
+    for entry in plan {
+        let mut attrs = vec![format!("label=\"{}\"", entry.name)];
+        if entry.kind == PassKind::Interactive {
+            attrs.push("shape=octagon".to_string());
+        }
+        if entry.will_run {
+            attrs.push("style=\"rounded,filled\"".to_string());
+            attrs.push("fillcolor=\"#fff3b0\"".to_string());
+        }
+        dot.push_str(&format!("    \"{}\" [{}];\n", entry.name, attrs.join(", ")));
+    }
+
+    for entry in plan {
+        for dep in &entry.depends_on {
+            let Some(producer) = plan.iter().find(|e| &e.name == dep) else {
+                continue;
+            };
+            let artifacts: Vec<&str> = producer.produces.iter()
+                .filter(|a| entry.requires.contains(a) || entry.optional_requires.contains(a))
+                .map(|a| a.as_str())
+                .collect();
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                dep, entry.name, artifacts.join(", ")
+            ));
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
 }
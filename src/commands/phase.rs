@@ -0,0 +1,41 @@
+use anyhow::{bail, Result};
+use crate::cli::PhaseCommands;
+use crate::decision_log;
+use colored::Colorize;
+use process_core::manifest::Manifest;
+use process_core::state::ProcessState;
+use std::path::Path;
+
+pub fn execute(root: &Path, command: &PhaseCommands) -> Result<()> {
+    match command {
+        PhaseCommands::Reopen { phase, reason } => {
+            if reason.trim().is_empty() {
+                bail!("A reason is required to reopen a phase");
+            }
+
+            let mut state = ProcessState::load(root)?;
+            let from = state.current_phase;
+            state.reopen(*phase)?;
+
+            let mut manifest = Manifest::load(root)?;
+            let superseded = manifest.supersede(*phase, reason);
+            manifest.save(root)?;
+            decision_log::record_reversal(root, from, *phase, reason, &superseded)?;
+            state.save(root)?;
+
+            println!("{} Reopened {} (was at {})", "↺".yellow(), phase.name().bold(), from);
+            if superseded.is_empty() {
+                println!("  No recorded artifacts superseded.");
+            } else {
+                println!("  Superseded:");
+                for name in &superseded {
+                    println!("    {} {}", "·".dimmed(), name);
+                }
+            }
+            println!("  Reversal recorded in decisions_log.yaml.");
+            println!("\n{}", format!("Next: redo the {} phase; `process pass plan` lists superseded passes to re-run.", phase.name()).dimmed());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use crate::cli::PipelineCommands;
+use colored::Colorize;
+use process_core::pass_manager::RunOptions;
+use process_core::pipeline::{Pipeline, PipelineSource, PipelineStep};
+
+use crate::passes::{project_pass_manager, PassSet};
+use std::path::Path;
+
+pub async fn execute(root: &Path, command: &PipelineCommands) -> Result<()> {
+    match command {
+        PipelineCommands::List => {
+            println!("{}", "━━━ Pipelines ━━━".bold());
+            for (pipeline, source) in Pipeline::list(root)? {
+                let source = match source {
+                    PipelineSource::BuiltIn => "built-in",
+                    PipelineSource::Project => "project",
+                    PipelineSource::File => "file",
+                };
+                println!(
+                    "  {} ({}, {} passes) — {}",
+                    pipeline.name.cyan(),
+                    source,
+                    pipeline.passes.len(),
+                    pipeline.description
+                );
+            }
+        }
+        PipelineCommands::Run { name, incremental, jobs } => {
+            let (pipeline, _) = Pipeline::load(root, name)?;
+            println!("{} Running pipeline: {}", "▶".cyan(), pipeline.name.bold());
+            for step in &pipeline.passes {
+                if let PipelineStep::Configured { pass, overrides } = step {
+                    let mut set = Vec::new();
+                    if let Some(p) = &overrides.provider { set.push(format!("provider={}", p)); }
+                    if let Some(m) = &overrides.model { set.push(format!("model={}", m)); }
+                    if let Some(t) = overrides.max_tokens { set.push(format!("max_tokens={}", t)); }
+                    println!("  {} {}: {}", "⚙".dimmed(), pass, set.join(", "));
+                }
+            }
+
+            let mut manager = project_pass_manager(root, PassSet::for_pipeline(&pipeline))?;
+            let options = RunOptions { incremental: *incremental, jobs: *jobs };
+            manager.run_pipeline(&pipeline, root, options).await?;
+            println!("{}", "✅ Pipeline complete".green().bold());
+        }
+    }
+
+    Ok(())
+}
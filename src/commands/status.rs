@@ -1,13 +1,16 @@
 use anyhow::Result;
 use colored::Colorize;
 use process_core::{phase::Phase, state::ProcessState};
+use process_core::artifacts;
+use process_core::pass::ArtifactKind;
 use std::fs;
 use std::path::Path;
 
-pub async fn execute() -> Result<()> {
-    let state = ProcessState::load()?;
+pub async fn execute(root: &Path) -> Result<()> {
+    let state = ProcessState::load(root)?;
 
     println!("━━━ Process Status ━━━");
+    println!("{}: {} ({})", "Cycle".bold(), state.cycle, state.cycle_type);
     println!("{}: {}", "Current Phase".bold(), state.current_phase);
     println!("{}: {}", "Last Updated".bold(), state.last_updated);
 
@@ -21,49 +24,32 @@ pub async fn execute() -> Result<()> {
 
     // Artifact checklist
     println!("\n{}", "Artifacts:".bold());
-    check_file("  seed.yaml", ".process/seed.yaml");
-    check_file("  diverge_summary.yaml", ".process/diverge_summary.yaml");
-    check_file("  converge_summary.yaml", ".process/converge_summary.yaml");
-    check_file("  skeleton.yaml", ".process/skeleton.yaml");
-    check_file("  learnings.yaml", ".process/learnings.yaml");
-    check_file("  friction.yaml", ".process/friction.yaml");
-    check_file("  postmortem.yaml", ".process/postmortem.yaml");
+    for kind in &artifacts::PROJECT_ARTIFACTS {
+        check_file(&format!("  {}", artifacts::relative_path(kind)), &artifacts::path(root, kind));
+    }
 
     // Branch status
-    let branches_dir = Path::new(".process/branches");
-    if branches_dir.exists() {
-        print_branch_status(branches_dir)?;
-    }
+    print_branch_status(root)?;
 
     Ok(())
 }
 
-fn check_file(label: &str, path: &str) {
-    if Path::new(path).exists() {
+fn check_file(label: &str, path: &Path) {
+    if path.exists() {
         println!("{} {}", "✓".green(), label);
     } else {
         println!("{} {}", "·".dimmed(), label.dimmed());
     }
 }
 
-fn print_branch_status(dir: &Path) -> Result<()> {
+fn print_branch_status(root: &Path) -> Result<()> {
     let mut branches: Vec<(String, String)> = Vec::new();
 
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.extension().is_some_and(|e| e == "yaml") {
-            let name = path.file_stem()
-                .unwrap_or_default()
-                .to_string_lossy()
-                .to_string();
-            if name.ends_with("-review") || name.ends_with("-abuse") {
-                continue;
-            }
-            let content = fs::read_to_string(&path).unwrap_or_default();
-            let status = extract_status(&content);
-            branches.push((name, status));
-        }
+    for name in artifacts::branch_names(root)? {
+        let path = artifacts::path(root, &ArtifactKind::BranchHypothesis(name.clone()));
+        let content = fs::read_to_string(&path).unwrap_or_default();
+        let status = extract_status(&content);
+        branches.push((name, status));
     }
 
     if !branches.is_empty() {
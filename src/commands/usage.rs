@@ -0,0 +1,81 @@
+use anyhow::Result;
+use crate::cli::UsageGroup;
+use colored::Colorize;
+use process_core::usage::{self, UsageEntry, UsageTotals};
+use std::path::Path;
+
+pub fn execute(root: &Path, by: Option<UsageGroup>) -> Result<()> {
+    let entries = usage::load(root)?;
+
+    println!("{}", "━━━ AI Usage ━━━".bold());
+    if entries.is_empty() {
+        println!("  (no AI calls recorded yet)");
+        return Ok(());
+    }
+
+    let mut total = UsageTotals::default();
+    entries.iter().for_each(|e| total.add(e));
+    println!(
+        "{}: {} calls, {} prompt + {} completion tokens, {}",
+        "Total".bold(),
+        total.calls,
+        total.prompt_tokens,
+        total.completion_tokens,
+        format_cost(&total)
+    );
+
+    for group in [UsageGroup::Phase, UsageGroup::Pass, UsageGroup::Branch, UsageGroup::Provider] {
+        if by.is_none_or(|b| b == group) {
+            print_group(group, &entries);
+        }
+    }
+
+    if total.unpriced > 0 {
+        println!(
+            "\n{}",
+            "Calls without a price (no usage reported, or no `ai.prices` entry for the model) are not in the cost.".dimmed()
+        );
+    }
+    Ok(())
+}
+
+fn print_group(group: UsageGroup, entries: &[UsageEntry]) {
+    let (title, groups) = match group {
+        UsageGroup::Phase => ("By phase", usage::totals_by(entries, |e| e.phase.to_string())),
+        UsageGroup::Pass => ("By pass", usage::totals_by(entries, |e| e.pass.clone())),
+        UsageGroup::Branch => (
+            "By branch",
+            usage::totals_by(entries, |e| e.branch.clone().unwrap_or_else(|| "(none)".to_string())),
+        ),
+        UsageGroup::Provider => ("By provider", usage::totals_by(entries, |e| match &e.model {
+            Some(model) => format!("{} ({})", e.provider, model),
+            None => e.provider.clone(),
+        })),
+    };
+
+    println!("\n{}", format!("{}:", title).bold());
+    let width = groups.keys().map(|k| k.chars().count()).max().unwrap_or(0);
+    for (name, totals) in &groups {
+        println!(
+            "  {:<width$}  {:>4} calls  {:>9} in  {:>9} out  {}",
+            name,
+            totals.calls,
+            totals.prompt_tokens,
+            totals.completion_tokens,
+            format_cost(totals),
+            width = width
+        );
+    }
+}
+
+fn format_cost(totals: &UsageTotals) -> String {
+    if totals.unpriced == totals.calls {
+        return "cost unknown".dimmed().to_string();
+    }
+    let cost = format!("${:.4}", totals.cost_usd);
+    if totals.unpriced > 0 {
+        format!("{} (+{} unpriced)", cost, totals.unpriced)
+    } else {
+        cost
+    }
+}
@@ -5,6 +5,9 @@ use dialoguer::Input;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
+use process_core::artifacts;
+use process_core::pass::ArtifactKind;
+use process_core::phase::Phase;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DecisionsLog {
@@ -13,6 +16,8 @@ pub struct DecisionsLog {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DecisionEntry {
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub entry_type: Option<String>,
     pub phase_transition: String,
     pub decision: String,
     pub reasoning: String,
@@ -24,7 +29,7 @@ pub struct DecisionEntry {
 
 /// Prompt the user for a decision record interactively.
 /// Returns Ok(true) if recorded, Ok(false) if skipped.
-pub fn prompt_decision(phase_transition: &str, skip: bool) -> Result<bool> {
+pub fn prompt_decision(project_root: &Path, phase_transition: &str, skip: bool) -> Result<bool> {
     if skip {
         println!("{} Decision recording skipped (--skip-decision)", "⚠".yellow());
         return Ok(false);
@@ -58,6 +63,7 @@ pub fn prompt_decision(phase_transition: &str, skip: bool) -> Result<bool> {
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━".cyan());
 
     let entry = DecisionEntry {
+        entry_type: None,
         phase_transition: phase_transition.to_string(),
         decision,
         reasoning,
@@ -67,17 +73,36 @@ pub fn prompt_decision(phase_transition: &str, skip: bool) -> Result<bool> {
         timestamp: Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
     };
 
-    append_decision(entry)?;
+    append_decision(project_root, entry)?;
     println!("{} 决策已记录到 decisions_log.yaml", "✔".green());
 
     Ok(true)
 }
 
-fn append_decision(entry: DecisionEntry) -> Result<()> {
-    let path = Path::new(".process/decisions_log.yaml");
+/// Record that a phase was reopened, superseding the listed artifacts
+pub fn record_reversal(project_root: &Path, from: Phase, reopened: Phase, reason: &str, superseded: &[String]) -> Result<()> {
+    let decision = if superseded.is_empty() {
+        format!("Reopen {}", reopened.name())
+    } else {
+        format!("Reopen {}; superseded: {}", reopened.name(), superseded.join(", "))
+    };
+    append_decision(project_root, DecisionEntry {
+        entry_type: Some("reversal".to_string()),
+        phase_transition: format!("{} → {}", from.name(), reopened.name()),
+        decision,
+        reasoning: reason.to_string(),
+        confidence: String::new(),
+        revisit_trigger: "N/A".to_string(),
+        decided_by: "human".to_string(),
+        timestamp: Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+    })
+}
+
+fn append_decision(project_root: &Path, entry: DecisionEntry) -> Result<()> {
+    let path = artifacts::path(project_root, &ArtifactKind::DecisionLog);
 
     let mut log = if path.exists() {
-        let content = fs::read_to_string(path)
+        let content = fs::read_to_string(&path)
             .context("Failed to read decisions_log.yaml")?;
         serde_yaml::from_str::<DecisionsLog>(&content)
             .unwrap_or(DecisionsLog { decisions: vec![] })
@@ -89,7 +114,7 @@ fn append_decision(entry: DecisionEntry) -> Result<()> {
 
     let content = serde_yaml::to_string(&log)
         .context("Failed to serialize decisions log")?;
-    fs::write(path, content)
+    artifacts::write(&path, content)
         .context("Failed to write decisions_log.yaml")?;
 
     Ok(())
@@ -1,13 +1,20 @@
 mod cli;
 mod commands;
 mod decision_log;
-mod prompts;
+mod passes;
 mod utils;
 
 use clap::{CommandFactory, Parser};
 use clap_complete::{generate, shells};
 use cli::{AdoptCommands, BranchCommands, Cli, Commands, ShellType};
 use colored::Colorize;
+use process_ai::cache::{self, CacheMode};
+use process_ai::error::ProviderError;
+use process_ai::notice;
+use process_core::artifacts;
+use process_core::lock::ProcessLock;
+use process_core::schema::SchemaError;
+use std::path::{Path, PathBuf};
 
 #[tokio::main]
 async fn main() {
@@ -24,69 +31,124 @@ async fn main() {
 
     if let Err(err) = run().await {
         eprintln!("{} {:#}", "error:".red().bold(), err);
+        // Artifact schema failures also show the offending lines
+        if let Some(diag) = err.chain().find_map(|e| e.downcast_ref::<SchemaError>()) {
+            eprintln!("\n{:?}", miette::Report::new(diag.clone()));
+        }
+        if let Some(hint) = err.chain().find_map(|e| e.downcast_ref::<ProviderError>()).and_then(provider_hint) {
+            eprintln!("{} {}", "hint:".cyan().bold(), hint);
+        }
         std::process::exit(1);
     }
 }
 
+/// What the user can do about an AI provider failure
+fn provider_hint(err: &ProviderError) -> Option<&'static str> {
+    match err {
+        ProviderError::Auth { .. } => Some("Check the provider's API key (`process ai-config show`, or the *_API_KEY environment variable)."),
+        ProviderError::RateLimited { .. } | ProviderError::Overloaded { .. } => {
+            Some("Retries are exhausted; wait a bit and re-run, or raise `settings.max_retries` in .process/config.yaml.")
+        }
+        ProviderError::Timeout { .. } => Some("Raise `settings.timeout_secs` (or `ai.<provider>.timeout_secs`) in .process/config.yaml."),
+        ProviderError::BadRequest { .. } => Some("Check the configured model name and max_tokens for this provider."),
+        _ => None,
+    }
+}
+
 async fn run() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    notice::set_handler(utils::print_notice);
+    let root = project_root(&cli)?;
+    let root = root.as_path();
+
+    if cli.no_cache {
+        cache::override_mode(CacheMode::Off);
+    } else if cli.refresh {
+        cache::override_mode(CacheMode::Refresh);
+    }
+
+    // Commands that modify .process/ hold its lock until they finish.
+    // Read-only ones skip it, and only migrate old layouts when no one
+    // else holds it.
+    let _lock = if root.join(artifacts::PROCESS_DIR).is_dir() {
+        let holder = std::env::args().collect::<Vec<_>>().join(" ");
+        if cli.command.is_read_only() {
+            if let Ok(lock) = ProcessLock::acquire(root, &holder) {
+                migrate_artifacts(root)?;
+                drop(lock);
+            }
+            None
+        } else {
+            let lock = ProcessLock::acquire(root, &holder)?;
+            migrate_artifacts(root)?;
+            Some(lock)
+        }
+    } else {
+        None
+    };
 
     match cli.command {
-        Commands::Init { force } => commands::init::execute(force).await?,
-        Commands::Status => commands::status::execute().await?,
-        Commands::AiConfig(cmd) => commands::ai_config::execute(&cmd).await?,
-        Commands::SeedValidate => commands::seed_validate::execute()?,
+        Commands::Init { force } => commands::init::execute(root, force).await?,
+        Commands::Status => commands::status::execute(root).await?,
+        Commands::AiConfig(cmd) => commands::ai_config::execute(root, &cmd).await?,
+        Commands::SeedValidate => commands::seed_validate::execute(root).await?,
         Commands::Diverge { skip_decision } => {
-            commands::diverge::execute(skip_decision).await?
+            commands::diverge::execute(root, skip_decision).await?
         }
-        Commands::DivergeValidate => commands::diverge_validate::execute()?,
-        Commands::DivergeChallenge => commands::diverge_challenge::execute()?,
+        Commands::DivergeValidate => commands::diverge_validate::execute(root).await?,
+        Commands::DivergeChallenge => commands::diverge_challenge::execute(root).await?,
         Commands::Converge { skip_decision } => {
-            commands::converge::execute(skip_decision).await?
+            commands::converge::execute(root, skip_decision).await?
         }
-        Commands::ConvergeValidate => commands::converge_validate::execute()?,
-        Commands::ConvergeChallenge => commands::converge_challenge::execute()?,
+        Commands::ConvergeValidate => commands::converge_validate::execute(root).await?,
+        Commands::ConvergeChallenge => commands::converge_challenge::execute(root).await?,
         Commands::Skeleton { skip_decision } => {
-            commands::skeleton::execute(skip_decision).await?
+            commands::skeleton::execute(root, skip_decision).await?
         }
-        Commands::SkeletonValidate => commands::skeleton_validate::execute()?,
+        Commands::SkeletonValidate => commands::skeleton_validate::execute(root).await?,
         Commands::Branch(cmd) => match cmd {
-            BranchCommands::New { name } => commands::branch_new::execute(&name)?,
-            BranchCommands::Start { name } => commands::branch_start::execute(&name)?,
-            BranchCommands::Implement { name } => commands::branch_implement::execute(&name).await?,
-            BranchCommands::Review { name, role } => commands::branch_review::execute(&name, role.as_deref()).await?,
-            BranchCommands::Abuse { name } => commands::branch_abuse::execute(&name).await?,
-            BranchCommands::Gate { name } => commands::branch_gate::execute(&name)?,
-            BranchCommands::Merge { name } => commands::branch_merge::execute(&name)?,
+            BranchCommands::New { name } => commands::branch_new::execute(root, &name)?,
+            BranchCommands::Start { name } => commands::branch_start::execute(root, &name)?,
+            BranchCommands::Implement { name } => commands::branch_implement::execute(root, &name).await?,
+            BranchCommands::Review { name, role } => commands::branch_review::execute(root, &name, role.as_deref()).await?,
+            BranchCommands::Abuse { name } => commands::branch_abuse::execute(root, &name).await?,
+            BranchCommands::Gate { name } => commands::branch_gate::execute(root, &name)?,
+            BranchCommands::Merge { name } => commands::branch_merge::execute(root, &name)?,
         },
         Commands::Adopt(cmd) => match cmd {
-            AdoptCommands::ScanStructure => commands::adopt_scan_structure::execute()?,
-            AdoptCommands::ScanDependencies => commands::adopt_scan_dependencies::execute()?,
+            AdoptCommands::ScanStructure => commands::adopt_scan_structure::execute(root).await?,
+            AdoptCommands::ScanDependencies => commands::adopt_scan_dependencies::execute(root).await?,
             AdoptCommands::InferConventions => {
-                commands::adopt_infer_conventions::execute().await?
+                commands::adopt_infer_conventions::execute(root).await?
             }
             AdoptCommands::ScanGitHistory { max_commits } => {
-                commands::adopt_scan_git_history::execute(max_commits).await?
+                commands::adopt_scan_git_history::execute(root, max_commits).await?
             }
-            AdoptCommands::GapAnalysis => commands::adopt_gap_analysis::execute().await?,
-            AdoptCommands::All { max_commits } => {
-                commands::adopt_all::execute(max_commits).await?
+            AdoptCommands::GapAnalysis => commands::adopt_gap_analysis::execute(root).await?,
+            AdoptCommands::All { max_commits, jobs } => {
+                commands::adopt_all::execute(root, max_commits, jobs).await?
             }
         },
         Commands::Learn { lesson, category } => {
-            commands::learn::execute(&lesson, &category)?
+            commands::learn::execute(root, &lesson, &category)?
         }
         Commands::Friction { branch, description, severity } => {
-            commands::friction::execute(&branch, &description, &severity)?
+            commands::friction::execute(root, &branch, &description, &severity)?
         }
         Commands::Stabilize { skip_decision } => {
-            commands::stabilize::execute(skip_decision)?
+            commands::stabilize::execute(root, skip_decision)?
         }
-        Commands::Postmortem => commands::postmortem::execute().await?,
-        Commands::Done => commands::done::execute()?,
-        Commands::Generate(cmd) => commands::generate::execute(&cmd)?,
-        Commands::Check(cmd) => commands::check::execute(&cmd)?,
-        Commands::Pass(cmd) => commands::pass::execute(&cmd)?,
+        Commands::Postmortem => commands::postmortem::execute(root).await?,
+        Commands::Done => commands::done::execute(root)?,
+        Commands::Generate(cmd) => commands::generate::execute(root, &cmd)?,
+        Commands::Check(cmd) => commands::check::execute(root, &cmd)?,
+        Commands::Pass(cmd) => commands::pass::execute(root, &cmd).await?,
+        Commands::Pipeline(cmd) => commands::pipeline::execute(root, &cmd).await?,
+        Commands::Artifact(cmd) => commands::artifact::execute(root, &cmd)?,
+        Commands::Phase(cmd) => commands::phase::execute(root, &cmd)?,
+        Commands::Cycle(cmd) => commands::cycle::execute(root, &cmd)?,
+        Commands::Cache(cmd) => commands::cache::execute(root, &cmd)?,
+        Commands::Usage { by } => commands::usage::execute(root, by)?,
         Commands::Guide => commands::help::execute(),
         Commands::Completions { shell } => {
             let mut cmd = Cli::command();
@@ -101,3 +163,35 @@ async fn run() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// `--project-dir` if given, otherwise the nearest directory at or above
+/// the current one that contains `.process/`. Outside any project this is
+/// the current directory, where `init` creates one.
+fn project_root(cli: &Cli) -> anyhow::Result<PathBuf> {
+    if let Some(dir) = &cli.project_dir {
+        if !dir.is_dir() {
+            anyhow::bail!("--project-dir {} is not a directory", dir.display());
+        }
+        return Ok(dir.clone());
+    }
+    let cwd = std::env::current_dir()?;
+    Ok(artifacts::find_project_root(&cwd).unwrap_or(cwd))
+}
+
+/// Move artifacts left in an older `.process/` layout to their canonical
+/// paths. Notes go to stderr so machine-readable output stays clean.
+fn migrate_artifacts(root: &Path) -> anyhow::Result<()> {
+    let migration = artifacts::migrate(root)?;
+    for (from, to) in &migration.moved {
+        eprintln!("{} Migrated {} → {}", "↻".cyan(), from.display(), to.display());
+    }
+    for (from, to) in &migration.conflicts {
+        eprintln!(
+            "{} Both {} and {} exist; using the latter. Merge and delete the old file.",
+            "⚠".yellow(),
+            from.display(),
+            to.display()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anyhow::Result;
+use colored::Colorize;
+use async_trait::async_trait;
+use process_core::pass::{ArtifactKind, Pass, PassContext, PassKind};
+
+use super::complete;
+
+/// adopt.gap_analysis — AI compares the adopted artifacts and reports gaps
+pub struct GapAnalysisPass;
+
+impl GapAnalysisPass {
+    pub fn output() -> ArtifactKind {
+        ArtifactKind::Custom("gap-report".to_string())
+    }
+}
+
+#[async_trait]
+impl Pass for GapAnalysisPass {
+    fn name(&self) -> &'static str {
+        "adopt.gap_analysis"
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        vec![]
+    }
+
+    fn optional_requires(&self) -> Vec<ArtifactKind> {
+        vec![
+            ArtifactKind::Skeleton,
+            ArtifactKind::Rules,
+            ArtifactKind::Seed,
+            ArtifactKind::DecisionLog,
+        ]
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        vec![Self::output()]
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::AiAssisted
+    }
+
+    fn description(&self) -> &'static str {
+        "Identify gaps across adopted artifacts"
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        // 1. Read available artifacts
+        let skeleton = ctx.artifact(&ArtifactKind::Skeleton);
+        let rules = ctx.artifact(&ArtifactKind::Rules);
+        let seed = ctx.artifact(&ArtifactKind::Seed);
+        let decisions = ctx.artifact(&ArtifactKind::DecisionLog);
+
+        let artifact_count = [&skeleton, &rules, &seed, &decisions]
+            .iter()
+            .filter(|a| a.is_some())
+            .count();
+
+        if artifact_count == 0 {
+            anyhow::bail!(
+                "No artifacts found. Run scan-structure, scan-dependencies, or infer-conventions first."
+            );
+        }
+
+        println!(
+            "{} Found {} existing artifacts",
+            "✔".green(),
+            artifact_count.to_string().cyan()
+        );
+
+        // 2. Build prompt variables
+        let mut vars = tera::Context::new();
+
+        // Truncate skeleton to 200 lines before inserting
+        let skeleton_truncated = skeleton.map(|s| {
+            s.lines().take(200).collect::<Vec<_>>().join("\n")
+        });
+        vars.insert("skeleton", &skeleton_truncated);
+        vars.insert("rules", &rules);
+        vars.insert("seed", &seed);
+        vars.insert("decisions", &decisions);
+
+        // 3. Call AI
+        println!("Calling AI to analyze gaps...");
+        let output = complete(ctx, "adopt_gap_analysis", &vars).await?;
+
+        ctx.save_artifact(&Self::output(), &output)?;
+        println!(
+            "{} Output saved to {}",
+            "✔".green(),
+            ctx.artifact_path(&Self::output()).display()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use anyhow::Result;
+use colored::Colorize;
+use async_trait::async_trait;
+use process_core::pass::{ArtifactKind, Pass, PassContext, PassKind};
+use process_core::schema::Rules;
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+use super::adopt_utils::IGNORE_DIRS;
+use super::complete_artifact;
+
+const MAX_SAMPLE_FILES: usize = 10;
+const MAX_LINES_PER_FILE: usize = 100;
+
+/// adopt.infer_conventions — AI infers rules.yaml from sampled source files
+pub struct InferConventionsPass;
+
+#[async_trait]
+impl Pass for InferConventionsPass {
+    fn name(&self) -> &'static str {
+        "adopt.infer_conventions"
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        vec![]
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Rules]
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::AiAssisted
+    }
+
+    fn description(&self) -> &'static str {
+        "Infer coding conventions into rules.yaml"
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        let root = ctx.project_root;
+
+        // 1. Sample source files from different directories
+        let samples = collect_source_samples(root)?;
+        println!(
+            "{} Sampled {} source files",
+            "✔".green(),
+            samples.len().to_string().cyan()
+        );
+
+        // 2. Read linter/formatter configs
+        let linter_configs = read_linter_configs(root);
+
+        // 3. Read skeleton.yaml if available. Not declared as an optional input:
+        //    skeleton.generate consumes Rules, so ordering on it would be a cycle.
+        let skeleton = ctx.load_artifact(&ArtifactKind::Skeleton).ok();
+
+        // 4. Build prompt variables
+        let mut vars = tera::Context::new();
+
+        // Convert tuples to objects for Tera iteration
+        let samples_json: Vec<serde_json::Value> = samples
+            .iter()
+            .map(|(path, content)| serde_json::json!({"path": path, "content": content}))
+            .collect();
+        vars.insert("samples", &samples_json);
+
+        let configs_json: Vec<serde_json::Value> = linter_configs
+            .iter()
+            .map(|(name, content)| serde_json::json!({"name": name, "content": content}))
+            .collect();
+        vars.insert("linter_configs", &configs_json);
+
+        // Truncate skeleton to 100 lines before inserting
+        let skeleton_truncated = skeleton.as_ref().map(|s| {
+            s.lines().take(100).collect::<Vec<_>>().join("\n")
+        });
+        vars.insert("skeleton", &skeleton_truncated);
+
+        // 5. Call AI
+        println!("Calling AI to infer conventions...");
+        let output = complete_artifact::<Rules>(ctx, "adopt_infer_conventions", &vars).await?;
+
+        ctx.save_artifact(&ArtifactKind::Rules, &output)?;
+        println!(
+            "{} Output saved to {}",
+            "✔".green(),
+            ctx.artifact_path(&ArtifactKind::Rules).display()
+        );
+
+        Ok(())
+    }
+}
+
+fn collect_source_samples(root: &Path) -> Result<Vec<(String, String)>> {
+    let source_exts = [
+        "rs", "ts", "tsx", "js", "jsx", "py", "go", "java", "rb", "cpp", "c", "cs", "swift",
+        "kt",
+    ];
+    let mut samples: Vec<(String, String)> = Vec::new();
+    let mut seen_dirs: Vec<String> = Vec::new();
+
+    let walker = WalkDir::new(root).into_iter().filter_entry(|e| {
+        let name = e.file_name().to_string_lossy();
+        !IGNORE_DIRS.contains(&name.as_ref())
+    });
+
+    for entry in walker.filter_map(|e| e.ok()) {
+        if samples.len() >= MAX_SAMPLE_FILES {
+            break;
+        }
+
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let ext = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        if !source_exts.contains(&ext.as_str()) {
+            continue;
+        }
+
+        // Prefer files from different directories
+        let dir = path
+            .parent()
+            .unwrap_or(root)
+            .to_string_lossy()
+            .to_string();
+
+        if seen_dirs.len() < MAX_SAMPLE_FILES && seen_dirs.contains(&dir) {
+            continue;
+        }
+        seen_dirs.push(dir);
+
+        let rel = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string();
+
+        if let Ok(content) = fs::read_to_string(path) {
+            let truncated: String = content
+                .lines()
+                .take(MAX_LINES_PER_FILE)
+                .collect::<Vec<_>>()
+                .join("\n");
+            samples.push((rel, truncated));
+        }
+    }
+
+    Ok(samples)
+}
+
+fn read_linter_configs(root: &Path) -> Vec<(String, String)> {
+    let config_files = [
+        ".editorconfig",
+        "rustfmt.toml",
+        ".rustfmt.toml",
+        "clippy.toml",
+        ".eslintrc",
+        ".eslintrc.js",
+        ".eslintrc.json",
+        ".eslintrc.yaml",
+        ".prettierrc",
+        ".prettierrc.json",
+        "biome.json",
+    ];
+
+    let mut configs = Vec::new();
+    for name in &config_files {
+        if let Ok(content) = fs::read_to_string(root.join(name)) {
+            configs.push((name.to_string(), content));
+        }
+    }
+    configs
+}
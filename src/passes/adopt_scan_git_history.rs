@@ -0,0 +1,126 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use async_trait::async_trait;
+use process_core::pass::{ArtifactKind, Pass, PassContext, PassKind};
+use process_core::schema::DecisionLog;
+use std::path::Path;
+use std::process::Command;
+
+use super::complete_artifact;
+
+/// adopt.scan_git_history — AI reconstructs decisions_log.yaml from git log
+pub struct ScanGitHistoryPass {
+    pub max_commits: usize,
+}
+
+impl Default for ScanGitHistoryPass {
+    fn default() -> Self {
+        Self { max_commits: 200 }
+    }
+}
+
+#[async_trait]
+impl Pass for ScanGitHistoryPass {
+    fn name(&self) -> &'static str {
+        "adopt.scan_git_history"
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        vec![]
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::DecisionLog]
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::AiAssisted
+    }
+
+    fn description(&self) -> &'static str {
+        "Reconstruct past decisions from git history"
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        let root = ctx.project_root;
+
+        // 1. Check if git repo
+        let is_git = Command::new("git")
+            .args(["rev-parse", "--is-inside-work-tree"])
+            .current_dir(root)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+
+        // Not fatal: the other adopt passes still apply
+        if !is_git {
+            println!("{} Not a git repository, skipping git history scan", "⚠".yellow());
+            return Ok(());
+        }
+
+        // 2. Gather git log
+        let regular_log = run_git_log(root, self.max_commits)?;
+        let merge_log = run_git_merge_log(root, 50)?;
+
+        if regular_log.is_empty() {
+            println!("{} No git commits found, skipping git history scan", "⚠".yellow());
+            return Ok(());
+        }
+
+        println!(
+            "{} Collected git history ({} commit limit)",
+            "✔".green(),
+            self.max_commits.to_string().cyan()
+        );
+
+        // 3. Build prompt and call AI
+        let mut vars = tera::Context::new();
+        vars.insert("commit_log", &regular_log);
+        let merge_log_opt = if merge_log.is_empty() { None } else { Some(&merge_log) };
+        vars.insert("merge_log", &merge_log_opt);
+
+        println!("Calling AI to analyze git history...");
+        let output = complete_artifact::<DecisionLog>(ctx, "adopt_scan_git_history", &vars).await?;
+
+        ctx.save_artifact(&ArtifactKind::DecisionLog, &output)?;
+        println!(
+            "{} Output saved to {}",
+            "✔".green(),
+            ctx.artifact_path(&ArtifactKind::DecisionLog).display()
+        );
+
+        Ok(())
+    }
+}
+
+fn run_git_log(root: &Path, max_commits: usize) -> Result<String> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--oneline",
+            "--no-merges",
+            "-n",
+            &max_commits.to_string(),
+        ])
+        .current_dir(root)
+        .output()
+        .context("Failed to run git log")?;
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn run_git_merge_log(root: &Path, max: usize) -> Result<String> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--merges",
+            "--oneline",
+            "-n",
+            &max.to_string(),
+        ])
+        .current_dir(root)
+        .output()
+        .context("Failed to run git log --merges")?;
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
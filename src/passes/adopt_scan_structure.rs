@@ -0,0 +1,143 @@
+use anyhow::Result;
+use colored::Colorize;
+use async_trait::async_trait;
+use process_core::pass::{ArtifactKind, Pass, PassContext, PassKind};
+use std::collections::HashMap;
+use walkdir::WalkDir;
+
+use super::adopt_utils::{classify_file, detect_frameworks, detect_language, IGNORE_DIRS};
+
+const MAX_FILES: usize = 500;
+
+/// adopt.scan_structure — walk an existing project and emit skeleton.yaml
+pub struct ScanStructurePass;
+
+#[async_trait]
+impl Pass for ScanStructurePass {
+    fn name(&self) -> &'static str {
+        "adopt.scan_structure"
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        vec![]
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Skeleton]
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Sync
+    }
+
+    fn description(&self) -> &'static str {
+        "Scan project layout into skeleton.yaml"
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        let root = ctx.project_root;
+
+        let mut extensions: HashMap<String, usize> = HashMap::new();
+        let mut files: Vec<(String, String)> = Vec::new();
+        let mut root_files: Vec<String> = Vec::new();
+
+        let walker = WalkDir::new(root).into_iter().filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            !IGNORE_DIRS.contains(&name.as_ref())
+        });
+
+        for entry in walker.filter_map(|e| e.ok()) {
+            let path = entry.path();
+
+            // Collect root-level file names
+            if path.parent() == Some(root) && path.is_file() {
+                if let Some(name) = path.file_name() {
+                    root_files.push(name.to_string_lossy().to_string());
+                }
+            }
+
+            if !path.is_file() {
+                continue;
+            }
+
+            // Count extensions
+            if let Some(ext) = path.extension() {
+                let ext_str = ext.to_string_lossy().to_lowercase();
+                *extensions.entry(ext_str).or_default() += 1;
+            }
+
+            // Normalize path relative to the project root
+            let rel = path
+                .strip_prefix(root)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .to_string();
+
+            if rel.is_empty() || rel == "." {
+                continue;
+            }
+
+            let category = classify_file(&rel);
+            files.push((rel, category.to_string()));
+
+            if files.len() >= MAX_FILES {
+                println!(
+                    "{} File list capped at {} entries",
+                    "⚠".yellow(),
+                    MAX_FILES
+                );
+                break;
+            }
+        }
+
+        let language = detect_language(&extensions);
+        let frameworks = detect_frameworks(&root_files);
+
+        // Build YAML output
+        let mut yaml = String::new();
+        yaml.push_str("# skeleton.yaml — generated by adopt scan-structure\n");
+        yaml.push_str("metadata:\n");
+        yaml.push_str("  source: \"adopt.scan-structure\"\n");
+        yaml.push_str(&format!("  language: \"{}\"\n", language));
+
+        if !frameworks.is_empty() {
+            yaml.push_str("  frameworks:\n");
+            for fw in &frameworks {
+                yaml.push_str(&format!("    - \"{}\"\n", fw));
+            }
+        }
+
+        yaml.push_str(&format!("  total_files: {}\n", files.len()));
+        yaml.push_str("\nfiles:\n");
+
+        for (path, category) in &files {
+            yaml.push_str(&format!(
+                "  - path: \"{}\"\n    description: \"{}\"\n",
+                path, category
+            ));
+        }
+
+        ctx.save_artifact(&ArtifactKind::Skeleton, &yaml)?;
+
+        println!("{} Detected language: {}", "✔".green(), language.cyan());
+        if !frameworks.is_empty() {
+            println!(
+                "{} Detected frameworks: {}",
+                "✔".green(),
+                frameworks.join(", ").cyan()
+            );
+        }
+        println!(
+            "{} Scanned {} files",
+            "✔".green(),
+            files.len().to_string().cyan()
+        );
+        println!(
+            "{} Output saved to {}",
+            "✔".green(),
+            ctx.artifact_path(&ArtifactKind::Skeleton).display()
+        );
+
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
+use process_core::artifacts;
 use process_core::state::ProcessState;
 
 pub const IGNORE_DIRS: &[&str] = &[
@@ -20,14 +21,14 @@ pub const IGNORE_DIRS: &[&str] = &[
     ".cache",
 ];
 
-pub fn ensure_process_dir() -> Result<()> {
-    let process_dir = Path::new(".process");
+pub fn ensure_process_dir(project_root: &Path) -> Result<()> {
+    let process_dir = project_root.join(artifacts::PROCESS_DIR);
 
     if process_dir.exists() {
         return Ok(());
     }
 
-    fs::create_dir_all(process_dir).context("Failed to create .process directory")?;
+    fs::create_dir_all(&process_dir).context("Failed to create .process directory")?;
     println!("{} Created .process/", "✔".green());
 
     // Minimal config.yaml
@@ -37,12 +38,12 @@ pub fn ensure_process_dir() -> Result<()> {
 settings:
   auto_save: true
 "#;
-    fs::write(&config_path, config_content).context("Failed to write config.yaml")?;
+    artifacts::write(&config_path, config_content).context("Failed to write config.yaml")?;
     println!("{} Created {}", "✔".green(), config_path.display());
 
     // Initialize state
     let state = ProcessState::default();
-    state.save().context("Failed to save initial state")?;
+    state.save(project_root).context("Failed to save initial state")?;
     println!("{} Initialized state to Seed", "✔".green());
 
     Ok(())
@@ -0,0 +1,240 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use dialoguer::Input;
+use async_trait::async_trait;
+use process_core::pass::{ArtifactKind, Pass, PassContext, PassKind};
+use process_core::schema::Rules;
+use serde::{Deserialize, Serialize};
+
+use super::complete_artifact;
+
+/// converge.analyze — AI prunes the proposals into one approach plus rules
+pub struct ConvergeAnalyzePass;
+
+#[async_trait]
+impl Pass for ConvergeAnalyzePass {
+    fn name(&self) -> &'static str {
+        "converge.analyze"
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Seed, ArtifactKind::Proposals]
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Rules]
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::AiAssisted
+    }
+
+    fn description(&self) -> &'static str {
+        "Analyze proposals, choose one and extract rules"
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        let mut vars = tera::Context::new();
+        vars.insert("seed", ctx.artifact(&ArtifactKind::Seed).unwrap_or_default());
+        vars.insert("diverge_summary", ctx.artifact(&ArtifactKind::Proposals).unwrap_or_default());
+
+        println!("Calling AI to converge proposals...");
+        let output = complete_artifact::<Rules>(ctx, "converge", &vars).await?;
+
+        ctx.save_artifact(&ArtifactKind::Rules, &output)?;
+        println!("{} Output saved to {}", "✔".green(), ctx.artifact_path(&ArtifactKind::Rules).display());
+
+        Ok(())
+    }
+}
+
+/// converge.validate — structural checks on rules.yaml
+pub struct ConvergeValidatePass;
+
+#[async_trait]
+impl Pass for ConvergeValidatePass {
+    fn name(&self) -> &'static str {
+        "converge.validate"
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Rules]
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        vec![]
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Sync
+    }
+
+    fn description(&self) -> &'static str {
+        "Validate rules format"
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        let content = ctx.artifact(&ArtifactKind::Rules).unwrap_or_default();
+
+        // ID prefixes, policy and selection are checked by the schema on load
+        let rules: Rules = serde_yaml::from_str(content)
+            .context("Invalid YAML format in rules.yaml")?;
+
+        println!("{} Rules validated successfully", "✔".green());
+        println!("  - {} invariants", rules.invariants.len());
+        println!("  - {} conventions", rules.conventions.len());
+        println!("  - {} rejected approaches", rules.rejected_approaches.len());
+        println!("  - Selected: {}", rules.selected_approach.name.cyan());
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ConvergeChallenges {
+    selected_concerns: SelectedConcerns,
+    rejected_regrets: Vec<RejectedRegret>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SelectedConcerns {
+    biggest_worry: String,
+    hidden_assumption: String,
+    what_if_wrong: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RejectedRegret {
+    approach: String,
+    what_we_lose: String,
+    conditions_to_reconsider: String,
+}
+
+/// converge.challenge — the human critiques the chosen approach
+pub struct ConvergeChallengePass;
+
+impl ConvergeChallengePass {
+    pub fn output() -> ArtifactKind {
+        ArtifactKind::Custom("converge_challenges".to_string())
+    }
+}
+
+#[async_trait]
+impl Pass for ConvergeChallengePass {
+    fn name(&self) -> &'static str {
+        "converge.challenge"
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Rules]
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        vec![Self::output()]
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Interactive
+    }
+
+    fn description(&self) -> &'static str {
+        "Challenge the chosen approach (human critiques selection)"
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        let rejected = rejected_approaches(ctx.artifact(&ArtifactKind::Rules).unwrap_or_default())?;
+
+        println!("{}", "━━━ 对选中方案的质疑 ━━━".bold().cyan());
+
+        let worry: String = Input::new()
+            .with_prompt("选中方案最大的隐患是什么？")
+            .interact_text()
+            .context("Failed to read input")?;
+
+        let assumption: String = Input::new()
+            .with_prompt("它依赖了什么隐含假设？")
+            .interact_text()
+            .context("Failed to read input")?;
+
+        let what_if: String = Input::new()
+            .with_prompt("如果这个选择是错的，后果是什么？")
+            .interact_text()
+            .context("Failed to read input")?;
+
+        if worry.trim().is_empty() {
+            bail!("Concern cannot be empty. Think harder.");
+        }
+
+        let selected_concerns = SelectedConcerns {
+            biggest_worry: worry,
+            hidden_assumption: assumption,
+            what_if_wrong: what_if,
+        };
+
+        // Challenge rejected approaches
+        println!("\n{}", "━━━ 对被拒方案的遗憾 ━━━".bold().cyan());
+
+        let mut regrets = Vec::new();
+        for name in &rejected {
+            println!("被拒方案: {}", name.cyan());
+
+            let lose: String = Input::new()
+                .with_prompt("放弃它我们失去了什么？")
+                .interact_text()
+                .context("Failed to read input")?;
+
+            let reconsider: String = Input::new()
+                .with_prompt("什么条件下应该重新考虑？")
+                .default("N/A".to_string())
+                .interact_text()
+                .context("Failed to read input")?;
+
+            regrets.push(RejectedRegret {
+                approach: name.clone(),
+                what_we_lose: lose,
+                conditions_to_reconsider: reconsider,
+            });
+            println!();
+        }
+
+        let challenges = ConvergeChallenges {
+            selected_concerns,
+            rejected_regrets: regrets,
+        };
+
+        let output = serde_yaml::to_string(&challenges)
+            .context("Failed to serialize")?;
+
+        ctx.save_artifact(&Self::output(), &output)?;
+        println!("{} Challenges saved to {}", "✔".green(), ctx.artifact_path(&Self::output()).display());
+
+        Ok(())
+    }
+}
+
+/// Names of the approaches rules.yaml records as rejected
+fn rejected_approaches(content: &str) -> Result<Vec<String>> {
+    let rules: Rules = serde_yaml::from_str(content).context("Failed to parse rules.yaml")?;
+    Ok(rules.rejected_approaches.into_iter().map(|r| r.name).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use process_core::schema::{ConflictResolution, Invariant, RejectedApproach, SelectedApproach};
+
+    #[test]
+    fn test_challenge_asks_about_every_rejected_approach() {
+        let rejected = |name: &str| RejectedApproach { name: name.to_string(), reason: "r".into() };
+        let rules = Rules {
+            invariants: vec![Invariant { id: "INV-1".into(), rule: "r".into(), rationale: "why".into() }],
+            conventions: Vec::new(),
+            conflict_resolution: ConflictResolution { policy: "human_final_say".into() },
+            rejected_approaches: vec![rejected("Thin client"), rejected("Event sourcing")],
+            selected_approach: SelectedApproach { name: "Local-first SQLite".into(), rationale: "why".into() },
+        };
+        let yaml = serde_yaml::to_string(&rules).unwrap();
+
+        assert_eq!(rejected_approaches(&yaml).unwrap(), ["Thin client", "Event sourcing"]);
+    }
+}
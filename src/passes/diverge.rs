@@ -0,0 +1,221 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use dialoguer::Input;
+use async_trait::async_trait;
+use process_core::pass::{ArtifactKind, Pass, PassContext, PassKind};
+use process_core::schema::Proposals;
+use serde::{Deserialize, Serialize};
+
+use super::complete_artifact;
+
+/// diverge.generate — AI generates divergent architectural proposals
+pub struct DivergeGeneratePass;
+
+#[async_trait]
+impl Pass for DivergeGeneratePass {
+    fn name(&self) -> &'static str {
+        "diverge.generate"
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Seed]
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Proposals]
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::AiAssisted
+    }
+
+    fn description(&self) -> &'static str {
+        "Generate ≥2 architectural proposals from the seed"
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        let mut vars = tera::Context::new();
+        vars.insert("seed", ctx.artifact(&ArtifactKind::Seed).unwrap_or_default());
+
+        println!("Calling AI to generate proposals...");
+        let output = complete_artifact::<Proposals>(ctx, "diverge", &vars).await?;
+
+        ctx.save_artifact(&ArtifactKind::Proposals, &output)?;
+        println!("{} Output saved to {}", "✔".green(), ctx.artifact_path(&ArtifactKind::Proposals).display());
+
+        Ok(())
+    }
+}
+
+/// diverge.validate — structural checks on the proposals
+pub struct DivergeValidatePass;
+
+#[async_trait]
+impl Pass for DivergeValidatePass {
+    fn name(&self) -> &'static str {
+        "diverge.validate"
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Proposals]
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        vec![]
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Sync
+    }
+
+    fn description(&self) -> &'static str {
+        "Validate proposal format"
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        let content = ctx.artifact(&ArtifactKind::Proposals).unwrap_or_default();
+
+        // Proposal count and required fields are checked by the schema on load
+        let output: Proposals = serde_yaml::from_str(content)
+            .context("Invalid YAML format in diverge_summary.yaml")?;
+
+        println!("{} Diverge output validated ({} proposals)", "✔".green(), output.proposals.len());
+        if !output.comparison_dimensions.is_empty() {
+            println!("  - {} comparison dimensions", output.comparison_dimensions.len());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ChallengesFile {
+    challenges: Vec<ProposalChallenge>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ProposalChallenge {
+    proposal: String,
+    weaknesses_i_see: String,
+    what_could_go_wrong: String,
+    question_for_ai: String,
+}
+
+/// diverge.challenge — the human critiques each AI proposal
+pub struct DivergeChallengePass;
+
+impl DivergeChallengePass {
+    pub fn output() -> ArtifactKind {
+        ArtifactKind::Custom("diverge_challenges".to_string())
+    }
+}
+
+#[async_trait]
+impl Pass for DivergeChallengePass {
+    fn name(&self) -> &'static str {
+        "diverge.challenge"
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Proposals]
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        vec![Self::output()]
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Interactive
+    }
+
+    fn description(&self) -> &'static str {
+        "Challenge each proposal (human critiques AI)"
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        let proposals = proposal_names(ctx.artifact(&ArtifactKind::Proposals).unwrap_or_default())?;
+
+        println!("Found {} proposals. You must challenge each one.\n",
+            proposals.len());
+
+        let mut challenges = Vec::new();
+
+        for name in &proposals {
+            println!("{}", format!("━━━ Challenge: {} ━━━", name).bold().cyan());
+
+            let weaknesses: String = Input::new()
+                .with_prompt("你看到的弱点是什么？")
+                .interact_text()
+                .context("Failed to read input")?;
+
+            let risk: String = Input::new()
+                .with_prompt("最坏情况会怎样？")
+                .interact_text()
+                .context("Failed to read input")?;
+
+            let question: String = Input::new()
+                .with_prompt("想问 AI 什么？")
+                .interact_text()
+                .context("Failed to read input")?;
+
+            // Validate non-empty
+            if weaknesses.trim().is_empty() {
+                bail!("Weakness cannot be empty. Think harder.");
+            }
+
+            challenges.push(ProposalChallenge {
+                proposal: name.clone(),
+                weaknesses_i_see: weaknesses,
+                what_could_go_wrong: risk,
+                question_for_ai: question,
+            });
+
+            println!();
+        }
+
+        let file = ChallengesFile { challenges };
+        let output = serde_yaml::to_string(&file)
+            .context("Failed to serialize challenges")?;
+
+        ctx.save_artifact(&Self::output(), &output)?;
+        println!("{} Challenges saved to {}", "✔".green(), ctx.artifact_path(&Self::output()).display());
+
+        Ok(())
+    }
+}
+
+/// Names of the proposals in diverge_summary.yaml
+fn proposal_names(content: &str) -> Result<Vec<String>> {
+    let summary: Proposals = serde_yaml::from_str(content)
+        .context("Failed to parse diverge_summary.yaml")?;
+    if summary.proposals.is_empty() {
+        bail!("No proposals found in diverge_summary.yaml");
+    }
+    Ok(summary.proposals.into_iter().map(|p| p.name).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use process_core::schema::Proposal;
+
+    #[test]
+    fn test_challenge_reads_names_from_serialized_proposals() {
+        let proposal = |name: &str| Proposal {
+            name: name.to_string(),
+            summary: Some("s".into()),
+            architecture: "a".into(),
+            tradeoffs: vec!["t".into()],
+            risks: vec!["r".into()],
+            constraint_alignment: None,
+        };
+        let summary = Proposals {
+            proposals: vec![proposal("Local-first SQLite"), proposal("Thin client")],
+            comparison_dimensions: Vec::new(),
+        };
+        let yaml = serde_yaml::to_string(&summary).unwrap();
+
+        assert_eq!(proposal_names(&yaml).unwrap(), ["Local-first SQLite", "Thin client"]);
+        assert!(proposal_names("proposals: []\n").is_err());
+    }
+}
@@ -0,0 +1,263 @@
+//! Built-in passes.
+//!
+//! Every phase step lives here as a `process_core::pass::Pass`. The phase
+//! commands in `commands/` only check state, run their pass through the
+//! `PassManager`, and record the human decision. `pass` and `pipeline`
+//! commands also pick up `process-pass-*` scripts (see
+//! `process_core::external`).
+//!
+//! The adopt passes draft seed, skeleton and rules from an existing
+//! codebase, the same artifacts the phase passes produce, so they are
+//! registered on a manager of their own.
+
+pub mod adopt_utils;
+pub mod adopt_gap_analysis;
+pub mod adopt_infer_conventions;
+pub mod adopt_scan_dependencies;
+pub mod adopt_scan_git_history;
+pub mod adopt_scan_structure;
+pub mod converge;
+pub mod diverge;
+pub mod postmortem;
+pub mod seed;
+pub mod skeleton;
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+use process_ai::provider::{AiProvider, CompletionRequest, ResponseSchema};
+use process_ai::structured;
+use process_core::external::{self, ExternalPass};
+use process_core::pass::{ArtifactKind, PassContext, PassServices};
+use process_core::pass_manager::PassManager;
+use process_core::pipeline::Pipeline;
+use process_core::schema::Schema;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::utils::{complete_accepted_live, complete_live, strip_markdown_code_block};
+
+/// Build PassManager with all built-in passes
+pub fn build_pass_manager(services: PassServices) -> PassManager {
+    let mut manager = PassManager::with_services(services);
+
+    manager.register(seed::SeedValidatePass);
+
+    manager.register(diverge::DivergeGeneratePass);
+    manager.register(diverge::DivergeValidatePass);
+    manager.register(diverge::DivergeChallengePass);
+
+    manager.register(converge::ConvergeAnalyzePass);
+    manager.register(converge::ConvergeValidatePass);
+    manager.register(converge::ConvergeChallengePass);
+
+    manager.register(skeleton::SkeletonGeneratePass);
+    manager.register(skeleton::SkeletonValidatePass);
+
+    manager.register(postmortem::PostmortemGeneratePass);
+    manager.register(postmortem::PostmortemReviewPass);
+
+    manager
+}
+
+/// Build PassManager with the adopt passes
+pub fn build_adopt_pass_manager(services: PassServices) -> PassManager {
+    let mut manager = PassManager::with_services(services);
+
+    manager.register(adopt_scan_structure::ScanStructurePass);
+    manager.register(adopt_scan_dependencies::ScanDependenciesPass);
+    manager.register(adopt_infer_conventions::InferConventionsPass);
+    manager.register(adopt_scan_git_history::ScanGitHistoryPass::default());
+    manager.register(adopt_gap_analysis::GapAnalysisPass);
+
+    manager
+}
+
+/// Which built-in manager a pass belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassSet {
+    Phases,
+    Adopt,
+}
+
+impl PassSet {
+    /// The set holding the pass called `name`
+    pub fn of(name: &str) -> Self {
+        if name.starts_with("adopt.") { PassSet::Adopt } else { PassSet::Phases }
+    }
+
+    /// The set a pipeline runs on: adopt when it lists any adopt pass
+    pub fn for_pipeline(pipeline: &Pipeline) -> Self {
+        if pipeline.pass_names().iter().any(|name| Self::of(name) == PassSet::Adopt) {
+            PassSet::Adopt
+        } else {
+            PassSet::Phases
+        }
+    }
+
+    pub fn build(self, services: PassServices) -> PassManager {
+        match self {
+            PassSet::Phases => build_pass_manager(services),
+            PassSet::Adopt => build_adopt_pass_manager(services),
+        }
+    }
+}
+
+/// A built-in set plus the project's and `PATH`'s script passes
+pub fn project_pass_manager(project_root: &Path, set: PassSet) -> Result<PassManager> {
+    with_scripts(set.build(PassServices::load(project_root)?), project_root)
+}
+
+/// Add the project's and `PATH`'s script passes. A script that reuses a
+/// built-in name replaces it; scripts that fail to describe themselves
+/// are skipped with a warning.
+fn with_scripts(mut manager: PassManager, project_root: &Path) -> Result<PassManager> {
+    for path in external::find_scripts(project_root) {
+        match ExternalPass::describe(&path) {
+            Ok(pass) => manager.register(pass),
+            Err(e) => eprintln!("{} Skipping script pass {}: {:#}", "⚠".yellow(), path.display(), e),
+        }
+    }
+    Ok(manager)
+}
+
+/// Run one built-in pass against the project
+pub async fn run(project_root: &Path, name: &str) -> Result<()> {
+    PassSet::of(name).build(PassServices::load(project_root)?).run_pass(name, project_root).await
+}
+
+/// Render a prompt template, send it to the pass's AI provider and
+/// return the response with any markdown fence stripped. Pipeline
+/// overrides and `ai.routes` pick the provider, model and limits.
+pub(crate) async fn complete(ctx: &PassContext<'_>, template: &str, vars: &tera::Context) -> Result<String> {
+    let (provider, request) = prepare(ctx, template, vars).await?;
+    let response = complete_live(provider.as_ref(), &request).await?;
+
+    Ok(strip_markdown_code_block(&response.content).to_string())
+}
+
+/// `complete` for an artifact with a schema: the provider is asked for
+/// `T`'s JSON Schema, the answer parsed and checked against `T`'s rules
+/// (re-asking with the problems when it doesn't fit) and returned as YAML
+/// for the artifact
+pub(crate) async fn complete_artifact<T: Schema + Serialize>(
+    ctx: &PassContext<'_>,
+    template: &str,
+    vars: &tera::Context,
+) -> Result<String> {
+    let (provider, request) = prepare(ctx, template, vars).await?;
+    let request = CompletionRequest {
+        response_schema: Some(ResponseSchema { name: T::NAME.to_string(), schema: T::json_schema() }),
+        ..request
+    };
+    complete_accepted_live(provider.as_ref(), &request, accept_artifact::<T>).await
+}
+
+/// A reply that parses as `T` and breaks none of its rules, as YAML
+fn accept_artifact<T: Schema + Serialize>(content: &str) -> Result<String> {
+    let value: T = structured::parse(content)?;
+    let problems: Vec<String> = value.check().into_iter().map(|v| v.message).collect();
+    if !problems.is_empty() {
+        bail!("{} schema rules broken: {}", T::NAME, problems.join("; "));
+    }
+    Ok(serde_yaml::to_string(&value)?)
+}
+
+/// The routed provider and request for a prompt template
+async fn prepare(
+    ctx: &PassContext<'_>,
+    template: &str,
+    vars: &tera::Context,
+) -> Result<(Arc<dyn AiProvider>, CompletionRequest)> {
+    let prompt = ctx.prompts.render(template, vars)?;
+    let system = ctx.prompts.render_system(template, vars)?;
+
+    let provider = ctx.provider_for(Some(template)).await?;
+    println!("Using Provider: {}", provider.label().cyan());
+
+    let request = CompletionRequest {
+        system,
+        max_tokens: Some(4096),
+        template: Some(template.to_string()),
+        ..CompletionRequest::new(prompt)
+    };
+    Ok((provider, request.routed(&ctx.route(Some(template)))))
+}
+
+/// Artifact content, or a "(no <file> found)" placeholder for prompts
+pub(crate) fn artifact_or_placeholder(ctx: &PassContext, kind: &ArtifactKind) -> String {
+    ctx.artifact(kind)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("(no {} found)", ctx.artifact_filename(kind)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(manager: &PassManager, name: &str) -> usize {
+        manager
+            .execution_order()
+            .iter()
+            .position(|n| n == name)
+            .unwrap_or_else(|| panic!("pass '{}' not in execution order", name))
+    }
+
+    #[test]
+    fn test_builtin_passes_registered() {
+        for expected in [
+            "seed.validate",
+            "diverge.generate",
+            "converge.analyze",
+            "skeleton.generate",
+            "postmortem.generate",
+            "adopt.scan_structure",
+            "adopt.gap_analysis",
+        ] {
+            let manager = PassSet::of(expected).build(PassServices::default());
+            assert!(manager.get(expected).is_some(), "missing pass {}", expected);
+        }
+    }
+
+    #[test]
+    fn test_builtin_dag_orders_phases() {
+        let mut manager = build_pass_manager(PassServices::default());
+        manager.resolve_order().unwrap();
+
+        assert!(position(&manager, "diverge.generate") < position(&manager, "diverge.validate"));
+        assert!(position(&manager, "diverge.generate") < position(&manager, "converge.analyze"));
+        assert!(position(&manager, "converge.analyze") < position(&manager, "skeleton.generate"));
+        assert!(position(&manager, "skeleton.generate") < position(&manager, "skeleton.validate"));
+        assert!(position(&manager, "postmortem.generate") < position(&manager, "postmortem.review"));
+
+        let mut adopt = build_adopt_pass_manager(PassServices::default());
+        adopt.resolve_order().unwrap();
+        assert!(position(&adopt, "adopt.scan_structure") < position(&adopt, "adopt.gap_analysis"));
+    }
+
+    #[test]
+    fn test_artifact_replies_must_follow_schema_rules() {
+        use process_core::schema::Rules;
+
+        let rules = r#"{"invariants":[{"id":"RULE-1","rule":"r","rationale":"why"}],
+            "conflict_resolution":{"policy":"human_final_say"},
+            "selected_approach":{"name":"a","rationale":"why"}}"#;
+        let err = accept_artifact::<Rules>(rules).unwrap_err();
+        assert!(err.to_string().contains("invariant ID 'RULE-1' must start with 'INV-'"), "{}", err);
+
+        let yaml = accept_artifact::<Rules>(&rules.replace("RULE-1", "INV-1")).unwrap();
+        assert!(yaml.contains("id: INV-1"));
+    }
+
+    #[test]
+    fn test_builtin_pipelines_reference_registered_passes() {
+        let tmp = std::env::temp_dir();
+        for name in ["default", "startup-fast", "adopt"] {
+            let (pipeline, _) = Pipeline::load(&tmp, name).unwrap();
+            let manager = PassSet::for_pipeline(&pipeline).build(PassServices::default());
+            for pass in pipeline.pass_names() {
+                assert!(manager.get(&pass).is_some(), "pipeline {} lists unknown pass {}", name, pass);
+            }
+        }
+    }
+}
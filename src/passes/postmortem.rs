@@ -0,0 +1,158 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use dialoguer::Input;
+use async_trait::async_trait;
+use process_core::pass::{ArtifactKind, Pass, PassContext, PassKind};
+use serde::{Deserialize, Serialize};
+
+use super::{artifact_or_placeholder, complete};
+use crate::decision_log::DecisionsLog;
+
+/// postmortem.generate — AI retrospective over learnings, friction and rules
+pub struct PostmortemGeneratePass;
+
+#[async_trait]
+impl Pass for PostmortemGeneratePass {
+    fn name(&self) -> &'static str {
+        "postmortem.generate"
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        vec![]
+    }
+
+    fn optional_requires(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Rules, ArtifactKind::Learnings, ArtifactKind::Friction]
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Postmortem]
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::AiAssisted
+    }
+
+    fn description(&self) -> &'static str {
+        "AI retrospective over learnings, friction and rules"
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        let mut vars = tera::Context::new();
+        vars.insert("learnings", &artifact_or_placeholder(ctx, &ArtifactKind::Learnings));
+        vars.insert("friction", &artifact_or_placeholder(ctx, &ArtifactKind::Friction));
+        vars.insert("rules", &artifact_or_placeholder(ctx, &ArtifactKind::Rules));
+
+        println!("Calling AI for retrospective analysis...");
+        let output = complete(ctx, "postmortem", &vars).await?;
+
+        ctx.save_artifact(&ArtifactKind::Postmortem, &output)?;
+        println!("{} Postmortem saved to {}", "✔".green(), ctx.artifact_path(&ArtifactKind::Postmortem).display());
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DecisionReview {
+    decision: String,
+    phase: String,
+    outcome: String,
+    lesson: String,
+}
+
+/// postmortem.review — the human grades each logged decision; the grades
+/// are saved to decision-review.yaml as `decision_quality_review`
+pub struct PostmortemReviewPass;
+
+impl PostmortemReviewPass {
+    pub fn output() -> ArtifactKind {
+        ArtifactKind::Custom("decision-review".to_string())
+    }
+}
+
+#[async_trait]
+impl Pass for PostmortemReviewPass {
+    fn name(&self) -> &'static str {
+        "postmortem.review"
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Postmortem]
+    }
+
+    fn optional_requires(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::DecisionLog]
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        vec![Self::output()]
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Interactive
+    }
+
+    fn description(&self) -> &'static str {
+        "Review the quality of each recorded decision"
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        let Some(content) = ctx.artifact(&ArtifactKind::DecisionLog) else {
+            println!("{} No decisions_log.yaml found, skipping decision review", "⚠".yellow());
+            return Ok(());
+        };
+
+        let log: DecisionsLog = serde_yaml::from_str(content)
+            .context("Failed to parse decisions_log.yaml")?;
+
+        if log.decisions.is_empty() {
+            println!("{} No decisions recorded, skipping review", "⚠".yellow());
+            return Ok(());
+        }
+
+        println!("\n{}", "━━━ 决策质量回顾 ━━━".bold().cyan());
+        println!("Review each decision you made during the project.\n");
+
+        let mut reviews = Vec::new();
+
+        for entry in &log.decisions {
+            println!("{}", format!("━━━ {} ━━━", entry.phase_transition).bold().cyan());
+            println!("  Decision: {}", entry.decision);
+            println!("  Reasoning: {}", entry.reasoning);
+            println!("  Confidence: {}", entry.confidence);
+
+            let outcome: String = Input::new()
+                .with_prompt("结果如何？ [correct/wrong/pending]")
+                .default("pending".to_string())
+                .interact_text()
+                .context("Failed to read outcome")?;
+
+            let lesson: String = Input::new()
+                .with_prompt("学到了什么？")
+                .default("N/A".to_string())
+                .interact_text()
+                .context("Failed to read lesson")?;
+
+            reviews.push(DecisionReview {
+                decision: entry.decision.clone(),
+                phase: entry.phase_transition.clone(),
+                outcome,
+                lesson,
+            });
+
+            println!();
+        }
+
+        println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+
+        let reviews_yaml = serde_yaml::to_string(&reviews)
+            .context("Failed to serialize decision reviews")?;
+        let review = format!("decision_quality_review:\n{}", reviews_yaml);
+
+        ctx.save_artifact(&Self::output(), &review)?;
+        println!("{} Decision quality review saved to {}", "✔".green(), ctx.artifact_path(&Self::output()).display());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use async_trait::async_trait;
+use process_core::pass::{ArtifactKind, Pass, PassContext, PassKind};
+use process_core::schema::Seed;
+
+/// seed.validate — check the 6-field seed spec
+pub struct SeedValidatePass;
+
+#[async_trait]
+impl Pass for SeedValidatePass {
+    fn name(&self) -> &'static str {
+        "seed.validate"
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Seed]
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        vec![]
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Sync
+    }
+
+    fn description(&self) -> &'static str {
+        "Validate seed.yaml against the 6-field spec"
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        let content = ctx.artifact(&ArtifactKind::Seed).unwrap_or_default();
+
+        // Structure and field rules are checked by the seed schema on load
+        let seed: Seed = serde_yaml::from_str(content).context("Invalid seed.yaml")?;
+
+        println!("{} Seed validated successfully", "✔".green());
+        println!("  - Idea: {}", seed.idea.cyan());
+        println!("  - {} constraints, {} non-goals, {} success criteria",
+            seed.constraints.len(), seed.non_goals.len(), seed.success_criteria.len());
+        println!("  - Reversibility: {}", seed.reversibility_budget);
+
+        Ok(())
+    }
+}
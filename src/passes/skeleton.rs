@@ -0,0 +1,96 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use async_trait::async_trait;
+use process_core::pass::{ArtifactKind, Pass, PassContext, PassKind};
+use process_core::schema::Skeleton;
+
+use super::complete_artifact;
+
+/// skeleton.generate — AI derives the project file plan from seed + rules
+pub struct SkeletonGeneratePass;
+
+#[async_trait]
+impl Pass for SkeletonGeneratePass {
+    fn name(&self) -> &'static str {
+        "skeleton.generate"
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Seed, ArtifactKind::Rules]
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Skeleton]
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::AiAssisted
+    }
+
+    fn description(&self) -> &'static str {
+        "Generate project skeleton from seed and rules"
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        let mut vars = tera::Context::new();
+        vars.insert("seed", ctx.artifact(&ArtifactKind::Seed).unwrap_or_default());
+        vars.insert("rules", ctx.artifact(&ArtifactKind::Rules).unwrap_or_default());
+
+        println!("Calling AI to generate skeleton...");
+        let output = complete_artifact::<Skeleton>(ctx, "skeleton", &vars).await?;
+
+        ctx.save_artifact(&ArtifactKind::Skeleton, &output)?;
+        println!("{} Output saved to {}", "✔".green(), ctx.artifact_path(&ArtifactKind::Skeleton).display());
+
+        Ok(())
+    }
+}
+
+/// skeleton.validate — path safety and completeness checks on skeleton.yaml
+pub struct SkeletonValidatePass;
+
+#[async_trait]
+impl Pass for SkeletonValidatePass {
+    fn name(&self) -> &'static str {
+        "skeleton.validate"
+    }
+
+    fn requires(&self) -> Vec<ArtifactKind> {
+        vec![ArtifactKind::Skeleton]
+    }
+
+    fn produces(&self) -> Vec<ArtifactKind> {
+        vec![]
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Sync
+    }
+
+    fn description(&self) -> &'static str {
+        "Validate skeleton format and file paths"
+    }
+
+    async fn run(&self, ctx: &mut PassContext<'_>) -> Result<()> {
+        let content = ctx.artifact(&ArtifactKind::Skeleton).unwrap_or_default();
+
+        // Path safety is checked by the skeleton schema on load
+        let output: Skeleton = serde_yaml::from_str(content)
+            .context("Invalid YAML format in skeleton.yaml")?;
+
+        let has_file = |name: &str| output.files.iter().any(|f| f.path.eq_ignore_ascii_case(name));
+        let has_readme = has_file("readme.md");
+        let has_gitignore = has_file(".gitignore");
+
+        if !has_readme {
+            println!("{}", "Warning: No README.md found in skeleton".yellow());
+        }
+        if !has_gitignore {
+            println!("{}", "Warning: No .gitignore found in skeleton".yellow());
+        }
+
+        println!("{} Skeleton validated ({} files)", "✔".green(), output.files.len());
+
+        Ok(())
+    }
+}
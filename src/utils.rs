@@ -1,15 +1,112 @@
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::path::Path;
 use std::sync::Arc;
 use anyhow::Result;
-use process_config::config::Config;
+use colored::Colorize;
+use process_config::config::{Config, Route};
 use process_ai::{
+    cache::ResponseCache,
+    notice::Notice,
     registry::AiRegistry,
-    provider::AiProvider,
-    providers::claude::ClaudeProvider,
-    providers::openai::OpenAiProvider,
-    providers::ollama::OllamaProvider,
-    providers::claude_cli::ClaudeCliProvider,
-    providers::manual::ManualProvider,
+    provider::{AiProvider, CompletionRequest, CompletionResponse},
+    structured,
 };
+use process_core::phase::Phase;
+use process_core::usage::{self, UsageScope};
+use serde::de::DeserializeOwned;
+
+/// Set while one completion is rendering to the terminal, so parallel
+/// passes don't interleave their output
+static RENDERING: AtomicBool = AtomicBool::new(false);
+
+/// Holds the terminal for one completion's streamed text, when stderr is a
+/// terminal and no other completion has it; released on drop
+struct LiveRender {
+    render: bool,
+}
+
+impl LiveRender {
+    fn acquire() -> Self {
+        let render = std::io::stderr().is_terminal()
+            && RENDERING.compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire).is_ok();
+        Self { render }
+    }
+
+    /// Echoes chunks to stderr when rendering, else drops them
+    fn sink(&self) -> impl FnMut(&str) + Send {
+        let render = self.render;
+        move |chunk: &str| {
+            if render {
+                let mut err = std::io::stderr().lock();
+                let _ = write!(err, "{}", chunk.dimmed());
+                let _ = err.flush();
+            }
+        }
+    }
+}
+
+impl Drop for LiveRender {
+    fn drop(&mut self) {
+        if self.render {
+            RENDERING.store(false, Ordering::Release);
+        }
+    }
+}
+
+/// Show a notice from the AI plumbing on stderr, on a line of its own
+/// when a completion is streaming there
+pub fn print_notice(notice: &Notice) {
+    let lead = if RENDERING.load(Ordering::Acquire) { "\n" } else { "" };
+    let mark = if notice.is_warning() { "⚠".yellow() } else { "●".dimmed() };
+    eprintln!("{}{} {}", lead, mark, notice);
+}
+
+/// Run a completion, echoing the text to stderr as it streams in when
+/// stderr is a terminal. Only one completion renders at a time; others
+/// (parallel passes) run quietly.
+pub async fn complete_live(provider: &dyn AiProvider, request: &CompletionRequest) -> Result<CompletionResponse> {
+    let live = LiveRender::acquire();
+    let result = provider.complete_stream(request, &mut live.sink()).await;
+    if !live.render {
+        return result;
+    }
+
+    match &result {
+        Ok(response) if response.cached => eprintln!("\n{}", "(cached response)".dimmed()),
+        Ok(response) => match &response.usage {
+            Some(usage) => eprintln!(
+                "\n{}",
+                format!("({} prompt + {} completion tokens)", usage.prompt_tokens, usage.completion_tokens).dimmed()
+            ),
+            None => eprintln!(),
+        },
+        Err(_) => eprintln!(),
+    }
+    result
+}
+
+/// `complete_live` for a request carrying a `response_schema`: the answer
+/// is parsed into `T`, re-asking with the parse error when it doesn't fit
+/// (see `structured::complete_typed`)
+pub async fn complete_typed_live<T: DeserializeOwned>(provider: &dyn AiProvider, request: &CompletionRequest) -> Result<T> {
+    complete_accepted_live(provider, request, structured::parse).await
+}
+
+/// `complete_typed_live` with the reply checked by `accept` (see
+/// `structured::complete_accepted`)
+pub async fn complete_accepted_live<T>(
+    provider: &dyn AiProvider,
+    request: &CompletionRequest,
+    accept: impl Fn(&str) -> Result<T>,
+) -> Result<T> {
+    let live = LiveRender::acquire();
+    let result = structured::complete_accepted(provider, request, &mut live.sink(), accept).await;
+    if live.render {
+        eprintln!();
+    }
+    result
+}
 
 /// Strip markdown code block markers from AI responses
 pub fn strip_markdown_code_block(content: &str) -> &str {
@@ -34,56 +131,65 @@ pub fn strip_markdown_code_block(content: &str) -> &str {
     }
 }
 
-/// Initialize AI registry with configured providers
-pub fn create_ai_registry(config: &Config) -> AiRegistry {
-    let mut registry = AiRegistry::new();
-    
-    // Claude API provider (priority 90 when key available)
-    registry.register(ClaudeProvider::new(config.ai.claude.clone()));
-
-    // OpenAI provider (priority 80 when key available)
-    registry.register(OpenAiProvider::new(config.ai.openai.clone()));
-
-    // Ollama local provider (priority 30, always registered)
-    registry.register(OllamaProvider::new(config.ai.ollama.clone()));
-
-    // Claude CLI provider (priority 95 when binary found)
-    registry.register(ClaudeCliProvider::new());
-
-    // Manual provider (priority 1, always available on TTY)
-    registry.register(ManualProvider::new());
+/// Get the configured AI provider
+pub async fn get_ai_provider(root: &Path, config: &Config) -> Result<Arc<dyn AiProvider>> {
+    let registry = AiRegistry::for_project(root, config);
+    registry.get_provider(&config.ai.provider).await
+}
 
-    registry
+/// Serve a branch command's completions (`branch.review`, ...) from the
+/// response cache when it is on, and add them to the usage ledger
+pub fn wrap_branch_provider(provider: Arc<dyn AiProvider>, root: &Path, config: &Config, command: &str, branch: &str) -> Arc<dyn AiProvider> {
+    let provider = ResponseCache::for_project(root, config.ai.cache).wrap(provider);
+    let scope = UsageScope {
+        phase: Phase::Branching,
+        pass: command.to_string(),
+        branch: Some(branch.to_string()),
+    };
+    usage::meter(provider, root, &config.ai, scope)
 }
 
-/// Get the configured AI provider
-pub async fn get_ai_provider(config: &Config) -> Result<Arc<dyn AiProvider>> {
-    let registry = create_ai_registry(config);
-    registry.get_provider(&config.ai.provider).await
+/// The provider for a branch command's completions from `template`: the
+/// `ai.routes` provider if a rule names one, else `ai.provider`, wrapped as
+/// by `wrap_branch_provider`. The route is returned for the request.
+pub async fn get_routed_branch_provider(
+    root: &Path,
+    config: &Config,
+    command: &str,
+    template: &str,
+    branch: &str,
+) -> Result<(Arc<dyn AiProvider>, Route)> {
+    let route = config.ai.route(command, Some(template));
+    let name = route.provider.as_deref().unwrap_or(&config.ai.provider);
+    let provider = AiRegistry::for_project(root, config).get_provider(name).await?;
+    Ok((wrap_branch_provider(provider, root, config, command, branch), route))
 }
 
 /// Load AI provider with optional branch-level override.
 /// If the branch YAML contains an `ai_config` section, it overrides the global config.
+/// Otherwise `route`'s provider applies; with one, the branch's choice of
+/// provider and model stands and they are cleared from `route`.
 pub async fn get_branch_ai_provider(
+    root: &Path,
     global_config: &Config,
     branch_content: &str,
+    route: &mut Route,
 ) -> Result<(Arc<dyn AiProvider>, String)> {
     let branch_yaml: serde_yaml::Value = serde_yaml::from_str(branch_content)
         .unwrap_or(serde_yaml::Value::Null);
 
+    let mut config = global_config.clone();
     if let Some(ai_cfg) = branch_yaml.get("ai_config") {
-        // Build a Config with branch overrides merged on top of global
-        let mut config = global_config.clone();
-
+        // Merge branch overrides on top of the global config
+        route.provider = None;
+        route.model = None;
         if let Some(p) = ai_cfg.get("provider").and_then(|v| v.as_str()) {
             config.ai.provider = p.to_string();
         }
 
         // Override provider-specific config
         if let Some(claude) = ai_cfg.get("claude") {
-            let mut pc = config.ai.claude.clone().unwrap_or(process_config::config::ProviderConfig {
-                api_key: None, model: None, base_url: None, max_tokens: None,
-            });
+            let mut pc = config.ai.claude.clone().unwrap_or_default();
             if let Some(v) = claude.get("api_key").and_then(|v| v.as_str()) { pc.api_key = Some(v.to_string()); }
             if let Some(v) = claude.get("model").and_then(|v| v.as_str()) { pc.model = Some(v.to_string()); }
             if let Some(v) = claude.get("base_url").and_then(|v| v.as_str()) { pc.base_url = Some(v.to_string()); }
@@ -91,24 +197,20 @@ pub async fn get_branch_ai_provider(
             config.ai.claude = Some(pc);
         }
         if let Some(openai) = ai_cfg.get("openai") {
-            let mut pc = config.ai.openai.clone().unwrap_or(process_config::config::ProviderConfig {
-                api_key: None, model: None, base_url: None, max_tokens: None,
-            });
+            let mut pc = config.ai.openai.clone().unwrap_or_default();
             if let Some(v) = openai.get("api_key").and_then(|v| v.as_str()) { pc.api_key = Some(v.to_string()); }
             if let Some(v) = openai.get("model").and_then(|v| v.as_str()) { pc.model = Some(v.to_string()); }
             if let Some(v) = openai.get("base_url").and_then(|v| v.as_str()) { pc.base_url = Some(v.to_string()); }
             if let Some(v) = openai.get("max_tokens").and_then(|v| v.as_u64()) { pc.max_tokens = Some(v as usize); }
             config.ai.openai = Some(pc);
         }
-
-        let provider_name = config.ai.provider.clone();
-        let provider = get_ai_provider(&config).await?;
-        Ok((provider, provider_name))
-    } else {
-        let name = global_config.ai.provider.clone();
-        let provider = get_ai_provider(global_config).await?;
-        Ok((provider, name))
+    } else if let Some(p) = &route.provider {
+        config.ai.provider = p.clone();
     }
+
+    let provider_name = config.ai.provider.clone();
+    let provider = get_ai_provider(root, &config).await?;
+    Ok((provider, provider_name))
 }
 
 #[cfg(test)]
@@ -0,0 +1,90 @@
+//! End-to-end runs of the CLI against recorded AI responses
+//! (`tests/fixtures/ai/`, served by the `replay` provider), so the phase
+//! commands are exercised with no network and no API key.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn fixtures() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ai")
+}
+
+/// Run `process <args>` against `project`, isolated from the user's
+/// global config
+fn process(project: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_process-cli"))
+        .arg("--project-dir")
+        .arg(project)
+        .args(args)
+        .env("HOME", project)
+        .env_remove("PROCESS_CLI__AI__PROVIDER")
+        .output()
+        .expect("failed to run process-cli")
+}
+
+fn succeed(project: &Path, args: &[&str]) -> String {
+    let output = process(project, args);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(
+        output.status.success(),
+        "`process {}` failed\nstdout:\n{}\nstderr:\n{}",
+        args.join(" "),
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    stdout
+}
+
+/// An initialised project whose AI answers come from `fixtures`
+fn replay_project(fixtures: &Path) -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().unwrap();
+    succeed(tmp.path(), &["init"]);
+    std::fs::write(
+        tmp.path().join(".process/config.yaml"),
+        format!("ai:\n  provider: replay\n  replay:\n    dir: {}\nsettings:\n  auto_save: true\n", fixtures.display()),
+    )
+    .unwrap();
+    tmp
+}
+
+fn read(project: &Path, file: &str) -> String {
+    std::fs::read_to_string(project.join(".process").join(file)).unwrap()
+}
+
+#[test]
+fn test_diverge_converge_skeleton_offline() {
+    let project = replay_project(&fixtures());
+    let root = project.path();
+
+    succeed(root, &["diverge", "--skip-decision"]);
+    succeed(root, &["diverge-validate"]);
+    succeed(root, &["converge", "--skip-decision"]);
+    succeed(root, &["converge-validate"]);
+    succeed(root, &["skeleton", "--skip-decision"]);
+    succeed(root, &["skeleton-validate"]);
+
+    assert!(read(root, "diverge_summary.yaml").contains("Local-first SQLite"));
+    assert!(read(root, "rules.yaml").contains("INV-1"));
+    assert!(read(root, "skeleton.yaml").contains("src/store.rs"));
+    assert!(succeed(root, &["status"]).contains("Skeleton"));
+
+    // Each artifact is attributed to the replay provider, and every call is in the ledger
+    let manifest = read(root, "manifest.yaml");
+    assert_eq!(manifest.matches("provider: replay").count(), 3 * 2, "{}", manifest);
+    let usage = succeed(root, &["usage", "--by", "pass"]);
+    for pass in ["diverge.generate", "converge.analyze", "skeleton.generate"] {
+        assert!(usage.contains(pass), "{}", usage);
+    }
+    assert!(usage.contains("1832 prompt + 470 completion tokens"), "{}", usage);
+}
+
+#[test]
+fn test_missing_fixture_names_the_template() {
+    let empty = tempfile::tempdir().unwrap();
+    let project = replay_project(empty.path());
+
+    let output = process(project.path(), &["diverge", "--skip-decision"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No replay fixture for template 'diverge'"), "{}", stderr);
+}